@@ -0,0 +1,152 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use amplify_syn::{DataInner, FieldKind, Fields};
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use syn::{Error, Result};
+
+use crate::params::{FieldAttr, StrictDerive};
+
+impl StrictDerive {
+    pub fn derive_arbitrary(&self) -> Result<TokenStream2> {
+        let crate_name = &self.conf.strict_crate;
+        let type_name = &self.data.name;
+
+        let mut generics = self.data.generics.clone();
+        let params = generics.type_params().map(|p| p.ident.clone()).collect::<Vec<_>>();
+        let where_clause = generics.make_where_clause();
+        for param in params {
+            where_clause
+                .predicates
+                .push(parse_quote! { #param: #crate_name::arbitrary::StrictArbitrary });
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let strategy = match &self.data.inner {
+            DataInner::Struct(fields) => self.derive_fields_strategy(fields, quote! { Self })?,
+            DataInner::Enum(variants) => {
+                let mut strategies = Vec::with_capacity(variants.len());
+                // Arbitrary tags of unknown variants may collide with the known ones
+                for variant in variants {
+                    let name = &variant.name;
+                    if Some(name) == self.conf.unknown.as_ref() {
+                        continue;
+                    }
+                    strategies.push(
+                        self.derive_fields_strategy(&variant.fields, quote! { Self::#name })?,
+                    );
+                }
+                quote! {
+                    #crate_name::proptest::strategy::Union::new(vec![#( #strategies ),*]).boxed()
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "arbitrary values can be derived only for structures and enums",
+                ))
+            }
+        };
+
+        Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics #crate_name::arbitrary::StrictArbitrary for #type_name #ty_generics #where_clause {
+                fn strict_arbitrary() -> #crate_name::proptest::strategy::BoxedStrategy<Self> {
+                    use #crate_name::proptest::strategy::Strategy;
+                    #strategy
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #crate_name::proptest::arbitrary::Arbitrary for #type_name #ty_generics #where_clause {
+                type Parameters = ();
+                type Strategy = #crate_name::proptest::strategy::BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                    <Self as #crate_name::arbitrary::StrictArbitrary>::strict_arbitrary()
+                }
+            }
+        })
+    }
+
+    fn derive_fields_strategy(&self, fields: &Fields, ctor: TokenStream2) -> Result<TokenStream2> {
+        let crate_name = &self.conf.strict_crate;
+
+        // Strategies are combined into nested pairs, so there is no limit on the number of fields
+        let mut strategy = quote! { #crate_name::proptest::strategy::Just(()) };
+        let mut pattern = quote! { () };
+        let mut push = |binding: &Ident, ty: &syn::Type| {
+            strategy = quote! {
+                (<#ty as #crate_name::arbitrary::StrictArbitrary>::strict_arbitrary(), #strategy)
+            };
+            pattern = quote! { (#binding, #pattern) };
+        };
+
+        let value = match fields {
+            Fields::Unit => {
+                return Ok(quote! {
+                    #crate_name::proptest::strategy::LazyJust::new(|| #ctor).boxed()
+                });
+            }
+            Fields::Named(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                for named_field in fields.iter().rev() {
+                    let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+                    let name = &named_field.name;
+                    if let Some(ref expr) = attr.present_if {
+                        return Err(Error::new_spanned(
+                            expr,
+                            "arbitrary values can't be derived for the types with `present_if` \
+                             fields",
+                        ));
+                    }
+                    if attr.skip {
+                        items.push(quote! { #name: ::core::default::Default::default() });
+                    } else {
+                        push(name, &named_field.field.ty);
+                        items.push(quote! { #name });
+                    }
+                }
+                items.reverse();
+                quote! { #ctor { #( #items ),* } }
+            }
+            Fields::Unnamed(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                for (index, field) in fields.iter().enumerate().rev() {
+                    let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+                    if attr.skip {
+                        items.push(quote! { ::core::default::Default::default() });
+                    } else {
+                        let binding = Ident::new(&format!("_{index}"), Span::call_site());
+                        push(&binding, &field.ty);
+                        items.push(quote! { #binding });
+                    }
+                }
+                items.reverse();
+                quote! { #ctor( #( #items ),* ) }
+            }
+        };
+
+        Ok(quote! {
+            #strategy.prop_map(|#pattern| #value).boxed()
+        })
+    }
+}
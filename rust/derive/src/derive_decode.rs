@@ -23,12 +23,18 @@ use amplify_syn::{DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedF
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use syn::{Error, Result};
 
-use crate::params::{FieldAttr, StrictDerive, VariantAttr};
+use crate::params::{present_ident, FieldAttr, StrictDerive, VariantAttr};
 
 struct DeriveDecode<'a>(&'a StrictDerive);
 
 impl StrictDerive {
     pub fn derive_decode(&self) -> Result<TokenStream2> {
+        if self.conf.encode_only {
+            return Err(Error::new(
+                Span::call_site(),
+                "StrictDecode can't be derived for the types marked with `encode_only`",
+            ));
+        }
         let res = self.data.derive(
             &self.conf.strict_crate,
             &ident!(StrictDecode),
@@ -41,25 +47,72 @@ impl StrictDerive {
 fn derive_struct_fields(
     fields: &Items<NamedField>,
     self_name: TokenStream2,
+    extensible: bool,
 ) -> Result<TokenStream2> {
     let mut skipped = Vec::new();
     let mut field_name = Vec::with_capacity(fields.len());
-    let mut field_rename = Vec::with_capacity(fields.len());
+    let mut field_value = Vec::with_capacity(fields.len());
+    let mut defaulted = false;
     for named_field in fields {
         let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
 
         let name = &named_field.name;
         let rename = attr.field_name(name);
+        let present = present_ident(name);
 
         if attr.skip {
             skipped.push(quote! { #name })
+        } else if let Some(presence) =
+            attr.presence(&present, &field_name, |name| quote! { &#name })
+        {
+            if attr.default.is_some() || attr.decode_with.is_some() {
+                return Err(Error::new(
+                    name.span(),
+                    "conditional fields can't have default values or be decoded with `decode_with`",
+                ));
+            }
+            if defaulted {
+                return Err(Error::new(
+                    name.span(),
+                    "only trailing fields may have default values; move this field before the \
+                     fields with default values",
+                ));
+            }
+            field_name.push(name.clone());
+            field_value.push(quote! {{
+                #presence
+                r.read_field_if(fname!(#rename), #present)?
+            }});
+        } else if let Some(default) = &attr.default {
+            if !extensible {
+                return Err(Error::new(
+                    name.span(),
+                    "default values of fields are allowed only in structures marked with \
+                     `#[strict_type(extensible)]`",
+                ));
+            }
+            if attr.decode_with.is_some() {
+                return Err(Error::new(
+                    name.span(),
+                    "fields with default values can't be decoded with `decode_with`",
+                ));
+            }
+            defaulted = true;
+            field_name.push(name.clone());
+            field_value.push(quote! { r.read_field_or_else(fname!(#rename), || #default)? });
+        } else if defaulted {
+            return Err(Error::new(
+                name.span(),
+                "only trailing fields may have default values; add a default value to this field \
+                 or move it before the fields with default values",
+            ));
         } else {
-            field_name.push(quote! { #name });
-            field_rename.push(quote! { #rename });
+            field_name.push(name.clone());
+            field_value.push(attr.decode_value(quote! { r.read_field(fname!(#rename))? }));
         }
     }
     Ok(quote! {
-        #( let #field_name = r.read_field(fname!(#field_rename))?; )*
+        #( let #field_name = #field_value; )*
         Ok(#self_name {
             #(#field_name,)*
             #(#skipped: Default::default()),*
@@ -69,6 +122,7 @@ fn derive_struct_fields(
 
 fn derive_tuple_fields(fields: &Items<Field>, self_name: TokenStream2) -> Result<TokenStream2> {
     let mut field_idx = Vec::with_capacity(fields.len());
+    let mut field_value = Vec::with_capacity(fields.len());
     let mut field_vars = Vec::with_capacity(fields.len());
     for (index, field) in fields.iter().enumerate() {
         let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
@@ -77,11 +131,12 @@ fn derive_tuple_fields(fields: &Items<Field>, self_name: TokenStream2) -> Result
         } else {
             let index = Ident::new(&format!("_{index}"), Span::call_site());
             field_idx.push(quote! { #index });
+            field_value.push(attr.decode_value(quote! { r.read_field()? }));
             field_vars.push(quote! { #index });
         }
     }
     Ok(quote! {
-        #( let #field_idx = r.read_field()?; )*
+        #( let #field_idx = #field_value; )*
         Ok(#self_name( #( #field_vars ),* ))
     })
 }
@@ -97,13 +152,31 @@ impl DeriveInner for DeriveDecode<'_> {
 
     fn derive_struct_inner(&self, fields: &Items<NamedField>) -> Result<TokenStream2> {
         let crate_name = &self.0.conf.strict_crate;
-        let inner = derive_struct_fields(fields, quote! { Self })?;
-        Ok(quote! {
-            fn strict_decode(reader: &mut impl #crate_name::TypedRead) -> Result<Self, #crate_name::DecodeError> {
-                use #crate_name::{TypedRead, ReadStruct, fname};
+        let inner = derive_struct_fields(fields, quote! { Self }, self.0.conf.extensible)?;
+        let mut extensions = None;
+        for named_field in fields {
+            if FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?.extensions {
+                extensions = Some(&named_field.name);
+            }
+        }
+        let read = match extensions {
+            Some(name) => quote! {
+                let mut value: Self = reader.read_struct(|r| {
+                    #inner
+                })?;
+                value.#name = reader.read_extensions()?;
+                Ok(value)
+            },
+            None => quote! {
                 reader.read_struct(|r| {
                     #inner
                 })
+            },
+        };
+        Ok(quote! {
+            fn strict_decode(reader: &mut impl #crate_name::TypedRead) -> Result<Self, #crate_name::DecodeError> {
+                use #crate_name::{TypedRead, ReadStruct, fname};
+                #read
             }
         })
     }
@@ -130,10 +203,20 @@ impl DeriveInner for DeriveDecode<'_> {
             }
         } else {
             let mut read_variants = Vec::with_capacity(variants.len());
+            let mut read_unknown = None;
             for var in variants {
                 let attr = VariantAttr::try_from(var.attr.clone())?;
                 let var_name = &var.name;
                 let name = attr.variant_name(var_name);
+                if Some(var_name) == self.0.conf.unknown.as_ref() {
+                    read_unknown = Some(match &var.fields {
+                        Fields::Unnamed(fields) if fields.len() == 2 => quote! {
+                            |tag, r| Ok(Self::#var_name(tag, #crate_name::ReadRaw::read_raw_to_end(r)?))
+                        },
+                        _ => quote! { |tag, _| Ok(Self::#var_name(tag)) },
+                    });
+                    continue;
+                }
                 match &var.fields {
                     Fields::Unit => {
                         read_variants.push(quote! {
@@ -151,6 +234,10 @@ impl DeriveInner for DeriveDecode<'_> {
                         });
                     }
                     Fields::Unnamed(fields) => {
+                        for field in fields {
+                            FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?
+                                .check_variant_field()?;
+                        }
                         let inner = derive_tuple_fields(fields, quote! { Self::#var_name })?;
                         read_variants.push(quote! {
                             #name => r.read_tuple(|r| {
@@ -159,7 +246,13 @@ impl DeriveInner for DeriveDecode<'_> {
                         });
                     }
                     Fields::Named(fields) => {
-                        let inner = derive_struct_fields(fields, quote! { Self::#var_name })?;
+                        for named_field in fields {
+                            let attr =
+                                FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+                            attr.check_variant_field()?;
+                        }
+                        let inner =
+                            derive_struct_fields(fields, quote! { Self::#var_name }, false)?;
                         read_variants.push(quote! {
                             #name => r.read_struct(|r| {
                                 #inner
@@ -169,15 +262,25 @@ impl DeriveInner for DeriveDecode<'_> {
                 }
             }
 
-            quote! {
-                #[allow(unused_imports)]
-                use #crate_name::{ReadUnion, ReadTuple, ReadStruct, fname};
-                reader.read_union(|field_name, r| {
+            let read_known = quote! {
+                |field_name, r| {
                     match field_name.as_str() {
                         #( #read_variants )*
                         _ => unreachable!(),
                     }
-                })
+                }
+            };
+            let read_union = match read_unknown {
+                Some(read_unknown) => {
+                    quote! { reader.read_union_lenient(#read_known, #read_unknown) }
+                }
+                None => quote! { reader.read_union(#read_known) },
+            };
+
+            quote! {
+                #[allow(unused_imports)]
+                use #crate_name::{ReadUnion, ReadTuple, ReadStruct, fname};
+                #read_union
             }
         };
 
@@ -19,7 +19,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use amplify_syn::{DeriveInner, Field, FieldKind, Items, NamedField, Variant};
+use amplify_syn::{DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedField, Variant};
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use syn::{Error, Result};
 
@@ -58,10 +58,8 @@ impl DeriveInner for DeriveDumb<'_> {
         for named in fields {
             let attr = FieldAttr::with(named.field.attr.clone(), FieldKind::Named)?;
             let name = &named.name;
-            items.push(match attr.dumb {
-                None => quote! { #name: StrictDumb::strict_dumb() },
-                Some(dumb_value) => quote! { #name: #dumb_value },
-            });
+            let value = attr.dumb_value(crate_name);
+            items.push(quote! { #name: #value });
         }
 
         Ok(quote! {
@@ -89,10 +87,7 @@ impl DeriveInner for DeriveDumb<'_> {
         let mut items = Vec::with_capacity(fields.len());
         for field in fields {
             let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
-            items.push(match attr.dumb {
-                None => quote! { StrictDumb::strict_dumb() },
-                Some(dumb_value) => quote! { #dumb_value },
-            });
+            items.push(attr.dumb_value(crate_name));
         }
 
         Ok(quote! {
@@ -112,19 +107,58 @@ impl DeriveInner for DeriveDumb<'_> {
             });
         }
 
+        let crate_name = &self.0.conf.strict_crate;
+
         let mut dumb_variant = None;
         for variant in variants {
             let attr = VariantAttr::try_from(variant.attr.clone())?;
             let name = &variant.name;
-            if attr.dumb {
-                dumb_variant = Some(quote! { Self::#name });
+            if attr.dumb && attr.dumb_value.is_some() {
+                return Err(Error::new(
+                    name.span(),
+                    "enum variant can't have both `dumb` and `dumb = ...` attributes",
+                ));
             }
+            if let Some(dumb_value) = attr.dumb_value {
+                dumb_variant = Some(quote! { #dumb_value });
+            } else if attr.dumb {
+                dumb_variant = Some(match &variant.fields {
+                    Fields::Unit => quote! { Self::#name },
+                    Fields::Named(fields) => {
+                        let mut items = Vec::with_capacity(fields.len());
+                        for named in fields {
+                            let attr = FieldAttr::with(named.field.attr.clone(), FieldKind::Named)?;
+                            let field = &named.name;
+                            let value = attr.dumb_value(crate_name);
+                            items.push(quote! { #field: #value });
+                        }
+                        quote! { Self::#name { #( #items ),* } }
+                    }
+                    Fields::Unnamed(fields) => {
+                        let mut items = Vec::with_capacity(fields.len());
+                        for field in fields {
+                            let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+                            items.push(attr.dumb_value(crate_name));
+                        }
+                        quote! { Self::#name( #( #items ),* ) }
+                    }
+                });
+            }
+        }
+        // Primitive enums have no fields to construct, so the first variant is
+        // used unless some other one is marked as dumb
+        if dumb_variant.is_none() && variants.enum_kind() == EnumKind::Primitive {
+            dumb_variant = variants.iter().next().map(|variant| {
+                let name = &variant.name;
+                quote! { Self::#name }
+            });
         }
         let dumb_variant = dumb_variant.ok_or_else(|| {
             Error::new(
                 Span::call_site(),
-                "enum must mark one of its variants with `#[strict_type(dumb)]` attribute, or \
-                 provide a dumb value in eponym attribute at container level",
+                "enum must mark one of its variants with `#[strict_type(dumb)]` or \
+                 `#[strict_type(dumb = ...)]` attribute, or provide a dumb value in eponym \
+                 attribute at container level",
             )
         })?;
 
@@ -21,9 +21,9 @@
 
 use amplify_syn::{DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedField, Variant};
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
-use syn::{Error, Index, Result};
+use syn::{Error, Index, Result, Type};
 
-use crate::params::{FieldAttr, StrictDerive, VariantAttr};
+use crate::params::{present_ident, FieldAttr, StrictDerive, VariantAttr};
 
 struct DeriveEncode<'a>(&'a StrictDerive);
 
@@ -33,6 +33,20 @@ impl StrictDerive {
     }
 }
 
+/// Type of the value referenced by a field holding borrowed data, which is
+/// written and defined in place of the reference. References to strings and
+/// slices are encoded by themselves.
+fn referent(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Slice(_) => None,
+            Type::Path(path) if path.path.is_ident("str") => None,
+            elem => Some(elem),
+        },
+        _ => None,
+    }
+}
+
 impl DeriveInner for DeriveEncode<'_> {
     fn derive_unit_inner(&self) -> Result<TokenStream2> {
         Err(Error::new(
@@ -45,24 +59,63 @@ impl DeriveInner for DeriveEncode<'_> {
     fn derive_struct_inner(&self, fields: &Items<NamedField>) -> Result<TokenStream2> {
         let crate_name = &self.0.conf.strict_crate;
 
-        let mut orig_name = Vec::with_capacity(fields.len());
-        let mut field_name = Vec::with_capacity(fields.len());
+        let mut write_fields = Vec::with_capacity(fields.len());
+        let mut presence = Vec::new();
+        let mut preceding = Vec::with_capacity(fields.len());
+        let mut extensions = None;
         for named_field in fields {
             let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
-            if !attr.skip {
-                orig_name.push(&named_field.name);
-                field_name.push(attr.field_name(&named_field.name));
+            let name = &named_field.name;
+            if attr.extensions {
+                extensions = Some(name);
+            } else if !attr.skip {
+                let (field, ty) = match referent(&named_field.field.ty) {
+                    Some(ty) => (quote! { self.#name }, ty),
+                    None => (quote! { &self.#name }, &named_field.field.ty),
+                };
+                let value = attr.encode_value(field.clone());
+                let rename = attr.field_name(name);
+                let present = present_ident(name);
+                match attr.presence(&present, &preceding, |name| quote! { &self.#name }) {
+                    Some(stmt) => {
+                        // The value of an absent field is not written, thus it
+                        // must be the one the field is decoded into
+                        presence.push(quote! {
+                            #stmt
+                            if !#present && #field != &<#ty as ::core::default::Default>::default() {
+                                return Err(::std::io::Error::new(
+                                    ::std::io::ErrorKind::InvalidInput,
+                                    concat!("absent field '", #rename, "' has a non-default value"),
+                                ));
+                            }
+                        });
+                        write_fields.push(quote! {
+                            .write_field_if(fname!(#rename), #present, #value)?
+                        });
+                    }
+                    None => write_fields.push(quote! { .write_field(fname!(#rename), #value)? }),
+                }
+                preceding.push(name.clone());
             }
         }
 
+        let write_struct = quote! {
+            #( #presence )*
+            writer.write_struct::<Self>(|w| {
+                Ok(w
+                    #( #write_fields )*
+                    .complete())
+            })
+        };
+        let inner = match extensions {
+            Some(name) => quote! { #write_struct?.write_extensions(&self.#name) },
+            None => write_struct,
+        };
+
         Ok(quote! {
             fn strict_encode<W: #crate_name::TypedWrite>(&self, writer: W) -> ::std::io::Result<W> {
                 use #crate_name::{TypedWrite, WriteStruct, fname};
-                writer.write_struct::<Self>(|w| {
-                    Ok(w
-                        #( .write_field(fname!(#field_name), &self.#orig_name)? )*
-                        .complete())
-                })
+                #inner
             }
         })
     }
@@ -70,21 +123,24 @@ impl DeriveInner for DeriveEncode<'_> {
     fn derive_tuple_inner(&self, fields: &Items<Field>) -> Result<TokenStream2> {
         let crate_name = &self.0.conf.strict_crate;
 
-        let no = fields.iter().enumerate().filter_map(|(index, field)| {
-            let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed).ok()?;
-            if attr.skip {
-                None
-            } else {
-                Some(Index::from(index))
+        let mut field_value = Vec::with_capacity(fields.len());
+        for (index, field) in fields.iter().enumerate() {
+            let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+            if !attr.skip {
+                let index = Index::from(index);
+                field_value.push(match referent(&field.ty) {
+                    Some(_) => attr.encode_value(quote! { self.#index }),
+                    None => attr.encode_value(quote! { &self.#index }),
+                });
             }
-        });
+        }
 
         Ok(quote! {
             fn strict_encode<W: #crate_name::TypedWrite>(&self, writer: W) -> ::std::io::Result<W> {
                 use #crate_name::{TypedWrite, WriteTuple};
                 writer.write_tuple::<Self>(|w| {
                     Ok(w
-                        #( .write_field(&self.#no)? )*
+                        #( .write_field(#field_value)? )*
                         .complete())
                 })
             }
@@ -101,10 +157,29 @@ impl DeriveInner for DeriveEncode<'_> {
         } else {
             let mut define_variants = Vec::with_capacity(variants.len());
             let mut write_variants = Vec::with_capacity(variants.len());
+            let mut write_unknown = TokenStream2::new();
             for var in variants {
                 let attr = VariantAttr::try_from(var.attr.clone())?;
                 let var_name = &var.name;
                 let name = attr.variant_name(var_name);
+                if Some(var_name) == self.0.conf.unknown.as_ref() {
+                    write_unknown = match &var.fields {
+                        Fields::Unnamed(fields) if fields.len() == 2 => quote! {
+                            if let Self::#var_name(tag, data) = self {
+                                return writer.write_union_unknown::<Self>(*tag, data);
+                            }
+                        },
+                        _ => quote! {
+                            if let Self::#var_name(tag) = self {
+                                return writer.write_union_unknown::<Self>(*tag, &[]);
+                            }
+                        },
+                    };
+                    write_variants.push(quote! {
+                        Self::#var_name(..) => unreachable!(),
+                    });
+                    continue;
+                }
                 match &var.fields {
                     Fields::Unit => {
                         define_variants.push(quote! {
@@ -133,15 +208,28 @@ impl DeriveInner for DeriveEncode<'_> {
                     Fields::Unnamed(fields) => {
                         let mut field_ty = Vec::with_capacity(fields.len());
                         let mut field_idx = Vec::with_capacity(fields.len());
+                        let mut field_value = Vec::with_capacity(fields.len());
                         for (index, field) in fields.iter().enumerate() {
                             let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+                            attr.check_variant_field()?;
 
-                            if !attr.skip {
-                                let ty = &field.ty;
-                                let index = Ident::new(&format!("_{index}"), Span::call_site());
-                                field_ty.push(quote! { #ty });
-                                field_idx.push(quote! { #index });
+                            if attr.skip {
+                                field_idx.push(quote! { _ });
+                                continue;
+                            }
+                            let index = Ident::new(&format!("_{index}"), Span::call_site());
+                            match referent(&field.ty) {
+                                Some(ty) => {
+                                    field_ty.push(quote! { #ty });
+                                    field_value.push(quote! { *#index });
+                                }
+                                None => {
+                                    let ty = &field.ty;
+                                    field_ty.push(quote! { #ty });
+                                    field_value.push(quote! { #index });
+                                }
                             }
+                            field_idx.push(quote! { #index });
                         }
                         define_variants.push(quote! {
                             .define_tuple(vname!(#name), |d| {
@@ -150,24 +238,35 @@ impl DeriveInner for DeriveEncode<'_> {
                         });
                         write_variants.push(quote! {
                             Self::#var_name( #( #field_idx ),* ) => writer.write_tuple(vname!(#name), |w| {
-                                Ok(w #( .write_field(#field_idx)? )* .complete())
+                                Ok(w #( .write_field(#field_value)? )* .complete())
                             })?,
                         });
                     }
                     Fields::Named(fields) => {
                         let mut field_ty = Vec::with_capacity(fields.len());
                         let mut field_name = Vec::with_capacity(fields.len());
+                        let mut field_value = Vec::with_capacity(fields.len());
                         let mut field_rename = Vec::with_capacity(fields.len());
                         for named_field in fields {
                             let attr =
                                 FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+                            attr.check_variant_field()?;
 
-                            let ty = &named_field.field.ty;
                             let name = &named_field.name;
                             let rename = attr.field_name(name);
 
                             if !attr.skip {
-                                field_ty.push(quote! { #ty });
+                                match referent(&named_field.field.ty) {
+                                    Some(ty) => {
+                                        field_ty.push(quote! { #ty });
+                                        field_value.push(quote! { *#name });
+                                    }
+                                    None => {
+                                        let ty = &named_field.field.ty;
+                                        field_ty.push(quote! { #ty });
+                                        field_value.push(quote! { #name });
+                                    }
+                                }
                                 field_name.push(quote! { #name });
                                 field_rename.push(quote! { fname!(#rename) });
                             }
@@ -179,8 +278,8 @@ impl DeriveInner for DeriveEncode<'_> {
                             })
                         });
                         write_variants.push(quote! {
-                            Self::#var_name { #( #field_name ),* } => writer.write_struct(vname!(#name), |w| {
-                                Ok(w #( .write_field(#field_rename, #field_name)? )* .complete())
+                            Self::#var_name { #( #field_name, )* .. } => writer.write_struct(vname!(#name), |w| {
+                                Ok(w #( .write_field(#field_rename, #field_value)? )* .complete())
                             })?,
                         });
                     }
@@ -190,6 +289,7 @@ impl DeriveInner for DeriveEncode<'_> {
             quote! {
                 #[allow(unused_imports)]
                 use #crate_name::{DefineUnion, WriteUnion, DefineTuple, DefineStruct, WriteTuple, WriteStruct, fname, vname};
+                #write_unknown
                 writer.write_union::<Self>(|definer| {
                     let writer = definer
                         #( #define_variants )*
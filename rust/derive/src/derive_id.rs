@@ -0,0 +1,199 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use amplify_syn::{DataInner, FieldKind, Fields};
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Result, Type};
+
+use crate::params::{FieldAttr, StrictDerive};
+
+impl StrictDerive {
+    pub fn derive_strict_id(&self) -> Result<TokenStream2> {
+        let crate_name = &self.conf.strict_crate;
+        let type_name = &self.data.name;
+
+        let mut generics = self.data.generics.clone();
+        let params = generics.type_params().map(|p| p.ident.clone()).collect::<Vec<_>>();
+        let where_clause = generics.make_where_clause();
+        for param in params {
+            where_clause.predicates.push(parse_quote! { #param: #crate_name::StrictTypeId });
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let strict_name = match self.conf.rename {
+            Some(ref rename) => rename.value(),
+            None => type_name.to_string(),
+        };
+
+        // Strict field and variant names (with tags) are taken from the trait
+        // constants, so only the field types are collected here. The wire
+        // layout lists the types of the fields which are present in the
+        // encoding, without separating named and unnamed fields.
+        let mut layout = Vec::new();
+        let mut wire = Vec::new();
+        let (names, tags) = match &self.data.inner {
+            DataInner::Struct(fields) => {
+                self.layout_fields(fields, &mut layout)?;
+                self.wire_fields(fields, &mut wire)?;
+                let names = match fields {
+                    Fields::Named(_) => quote! {
+                        let fields = <Self as #crate_name::StrictStruct>::ALL_FIELDS;
+                        let mut i = 0;
+                        while i < fields.len() {
+                            hasher = hasher.write(fields[i].as_bytes());
+                            i += 1;
+                        }
+                    },
+                    _ => TokenStream2::new(),
+                };
+                (names, TokenStream2::new())
+            }
+            DataInner::Enum(variants) => {
+                for variant in variants {
+                    layout.push(quote! { hasher = hasher.write_u8(b'|'); });
+                    self.layout_fields(&variant.fields, &mut layout)?;
+                    // Unknown variant is absent in `ALL_VARIANTS`, as well as its tags
+                    if Some(&variant.name) != self.conf.unknown.as_ref() {
+                        wire.push(quote! { hasher = hasher.write_u8(b'|'); });
+                        self.wire_fields(&variant.fields, &mut wire)?;
+                    }
+                }
+                let names = quote! {
+                    let variants = <Self as #crate_name::StrictSum>::ALL_VARIANTS;
+                    let mut i = 0;
+                    while i < variants.len() {
+                        hasher = hasher.write_u8(variants[i].0).write(variants[i].1.as_bytes());
+                        i += 1;
+                    }
+                };
+                let tags = quote! {
+                    let variants = <Self as #crate_name::StrictSum>::ALL_VARIANTS;
+                    let mut i = 0;
+                    while i < variants.len() {
+                        hasher = hasher.write_u8(variants[i].0);
+                        i += 1;
+                    }
+                };
+                (names, tags)
+            }
+            _ => (TokenStream2::new(), TokenStream2::new()),
+        };
+
+        Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics #crate_name::StrictTypeId for #type_name #ty_generics #where_clause {
+                const STRICT_ID: u64 = {
+                    let mut hasher = #crate_name::ConstHasher::new()
+                        .write(<Self as #crate_name::StrictType>::STRICT_LIB_NAME.as_bytes())
+                        .write(#strict_name.as_bytes());
+                    #( #layout )*
+                    #names
+                    hasher.finish()
+                };
+
+                const WIRE_FINGERPRINT: u64 = {
+                    let mut hasher = #crate_name::ConstHasher::new();
+                    #( #wire )*
+                    #tags
+                    hasher.finish()
+                };
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #type_name #ty_generics #where_clause {
+                /// Strict type id, computed from the type name, library and layout.
+                pub const STRICT_ID: u64 = <Self as #crate_name::StrictTypeId>::STRICT_ID;
+
+                /// Returns strict type id, computed from the type name, library
+                /// and layout.
+                pub const fn strict_type_id() -> u64 { Self::STRICT_ID }
+
+                /// Fingerprint of the wire format, computed from the field
+                /// types, their order and variant tags, but not from the names.
+                pub const WIRE_FINGERPRINT: u64 =
+                    <Self as #crate_name::StrictTypeId>::WIRE_FINGERPRINT;
+            }
+        })
+    }
+
+    /// Returns the code adding the field type id to the hasher. Fields with
+    /// custom encoding are identified by the paths of their encoding functions
+    /// instead, since their wire format doesn't depend on the field type.
+    fn field_id(&self, ty: &Type, attr: &FieldAttr, id: TokenStream2) -> TokenStream2 {
+        let crate_name = &self.conf.strict_crate;
+        match attr.encode_with.as_ref().or(attr.decode_with.as_ref()) {
+            Some(path) => {
+                let path = quote! { #path }.to_string();
+                quote! { hasher = hasher.write(#path.as_bytes()); }
+            }
+            None => quote! { hasher = hasher.write_u64(<#ty as #crate_name::StrictTypeId>::#id); },
+        }
+    }
+
+    fn wire_fields(&self, fields: &Fields, wire: &mut Vec<TokenStream2>) -> Result<()> {
+        let fields: Vec<_> = match fields {
+            Fields::Unit => return Ok(()),
+            Fields::Named(fields) => {
+                fields.iter().map(|named_field| (&named_field.field, FieldKind::Named)).collect()
+            }
+            Fields::Unnamed(fields) => {
+                fields.iter().map(|field| (field, FieldKind::Unnamed)).collect()
+            }
+        };
+        for (field, kind) in fields {
+            let attr = FieldAttr::with(field.attr.clone(), kind)?;
+            if attr.extensions {
+                // All extension records are encoded in the same way
+                wire.push(quote! { hasher = hasher.write(b"extensions"); });
+                continue;
+            }
+            if attr.skip {
+                continue;
+            }
+            wire.push(self.field_id(&field.ty, &attr, quote! { WIRE_FINGERPRINT }));
+        }
+        Ok(())
+    }
+
+    fn layout_fields(&self, fields: &Fields, layout: &mut Vec<TokenStream2>) -> Result<()> {
+        let (fields, kind, open, close): (Vec<_>, _, _, _) = match fields {
+            Fields::Unit => {
+                layout.push(quote! { hasher = hasher.write_u8(b'(').write_u8(b')'); });
+                return Ok(());
+            }
+            Fields::Named(fields) => {
+                let fields = fields.iter().map(|named_field| &named_field.field).collect();
+                (fields, FieldKind::Named, b'{', b'}')
+            }
+            Fields::Unnamed(fields) => (fields.iter().collect(), FieldKind::Unnamed, b'(', b')'),
+        };
+        layout.push(quote! { hasher = hasher.write_u8(#open); });
+        for field in fields {
+            let attr = FieldAttr::with(field.attr.clone(), kind)?;
+            if attr.skip {
+                continue;
+            }
+            layout.push(self.field_id(&field.ty, &attr, quote! { STRICT_ID }));
+        }
+        layout.push(quote! { hasher = hasher.write_u8(#close); });
+        Ok(())
+    }
+}
@@ -22,9 +22,9 @@
 use amplify_syn::{
     DataInner, DeriveInner, EnumKind, Field, FieldKind, Fields, Items, NamedField, Variant,
 };
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Ident, TokenStream as TokenStream2, TokenTree};
 use quote::ToTokens;
-use syn::Result;
+use syn::{Error, Expr, ExprBlock, ExprLit, Lit, Result, Stmt};
 
 use crate::params::{EnumAttr, FieldAttr, StrictDerive, VariantAttr, VariantTags};
 
@@ -32,9 +32,10 @@ struct DeriveType<'a>(&'a StrictDerive);
 struct DeriveProduct;
 struct DeriveTuple;
 struct DeriveStruct;
-struct DeriveSum(EnumAttr);
+struct DeriveSum(EnumAttr, Option<Ident>);
 struct DeriveEnum;
 struct DeriveUnion;
+struct DeriveNoDumb;
 
 impl StrictDerive {
     pub fn derive_type(&self) -> Result<TokenStream2> {
@@ -83,8 +84,11 @@ impl StrictDerive {
                     TokenStream2::new()
                 };
 
-                let impl_struct_enum =
-                    self.data.derive(trait_crate, &ident!(StrictSum), &DeriveSum(enum_attr))?;
+                let impl_struct_enum = self.data.derive(
+                    trait_crate,
+                    &ident!(StrictSum),
+                    &DeriveSum(enum_attr, self.conf.unknown.clone()),
+                )?;
 
                 quote! {
                     #impl_into_u8
@@ -111,10 +115,178 @@ impl StrictDerive {
             _ => TokenStream2::new(),
         };
 
+        // Encode-only types may hold borrowed data without dumb values, while
+        // products and unions must have them
+        let impl_dumb = match &self.data.inner {
+            DataInner::Enum(variants) if variants.enum_kind() == EnumKind::Primitive => {
+                TokenStream2::new()
+            }
+            _ if self.conf.encode_only => {
+                self.data.derive(trait_crate, &ident!(StrictDumb), &DeriveNoDumb)?
+            }
+            _ => TokenStream2::new(),
+        };
+
+        let impl_arbitrary =
+            if self.conf.arbitrary { self.derive_arbitrary()? } else { TokenStream2::new() };
+
+        let impl_strict_id =
+            if self.conf.strict_id { self.derive_strict_id()? } else { TokenStream2::new() };
+
+        let check_extensions = self.derive_extensions_check()?;
+        let check_tags = self.derive_tags_check();
+
         Ok(quote! {
             #impl_type
             #impl_outer
             #impl_inner
+            #impl_dumb
+            #impl_arbitrary
+            #impl_strict_id
+            #check_extensions
+            #check_tags
+        })
+    }
+
+    /// Fails the compilation if two variants have the same tag. Unlike the
+    /// check at the macro expansion time, this covers the tags given by
+    /// constants and other expressions. Variants of generic enums can't be
+    /// accessed from a constant, so they are not checked.
+    fn derive_tags_check(&self) -> TokenStream2 {
+        if !matches!(self.data.inner, DataInner::Enum(_)) || !self.data.generics.params.is_empty() {
+            return TokenStream2::new();
+        }
+        let crate_name = &self.conf.strict_crate;
+        let type_name = &self.data.name;
+        let msg = format!("enum `{type_name}` has several variants with the same tag");
+        quote! {
+            const _: () = {
+                let variants = <#type_name as #crate_name::StrictSum>::ALL_VARIANTS;
+                let mut i = 0;
+                while i < variants.len() {
+                    let mut j = i + 1;
+                    while j < variants.len() {
+                        assert!(variants[i].0 != variants[j].0, #msg);
+                        j += 1;
+                    }
+                    i += 1;
+                }
+            };
+        }
+    }
+
+    /// Analyzes structure fields for TLV extensions: returns whether the
+    /// encoding ends with the extension records and the types of the fields
+    /// which are followed by other data, thus must not end with them.
+    fn extensions_layout(&self) -> Result<(TokenStream2, Vec<&syn::Type>)> {
+        let crate_name = &self.conf.strict_crate;
+        let mut fields = vec![];
+        match &self.data.inner {
+            DataInner::Struct(Fields::Named(named)) => {
+                for named_field in named {
+                    let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+                    if attr.extensions {
+                        let non_terminal = fields.into_iter().flatten().collect();
+                        return Ok((quote! { true }, non_terminal));
+                    }
+                    if !attr.skip {
+                        fields.push(attr.encode_with.is_none().then_some(&named_field.field.ty));
+                    }
+                }
+            }
+            DataInner::Struct(Fields::Unnamed(unnamed)) => {
+                for field in unnamed {
+                    let attr = FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?;
+                    if !attr.skip {
+                        fields.push(attr.encode_with.is_none().then_some(&field.ty));
+                    }
+                }
+            }
+            // Variant data are followed by the data of the outer types, since
+            // unions are not extensible
+            DataInner::Enum(variants) => {
+                for variant in variants {
+                    if Some(&variant.name) == self.conf.unknown.as_ref() {
+                        continue;
+                    }
+                    let items = match &variant.fields {
+                        Fields::Named(named) => {
+                            named.iter().map(|named| (&named.field, FieldKind::Named)).collect()
+                        }
+                        Fields::Unnamed(unnamed) => {
+                            unnamed.iter().map(|field| (field, FieldKind::Unnamed)).collect()
+                        }
+                        Fields::Unit => vec![],
+                    };
+                    for (field, kind) in items {
+                        let attr = FieldAttr::with(field.attr.clone(), kind)?;
+                        if !attr.skip && attr.encode_with.is_none() {
+                            fields.push(Some(&field.ty));
+                        }
+                    }
+                }
+                fields.push(None);
+            }
+            _ => {}
+        }
+        // Fields encoded with a proxy are not required to be strict types, as
+        // well as fields of generic types. A recursive type can't end with the
+        // extensions through its last field referring to itself, and taking
+        // such a field into account would make the constant cyclic.
+        let has_extensions = match fields.pop() {
+            Some(Some(ty))
+                if self.data.generics.params.is_empty() && !self.refers_to_self(ty, true) =>
+            {
+                quote! { <#ty as #crate_name::StrictType>::HAS_EXTENSIONS }
+            }
+            _ => quote! { false },
+        };
+        // `Self` can't be used outside of the type implementations
+        let non_terminal =
+            fields.into_iter().flatten().filter(|ty| !self.refers_to_self(ty, false)).collect();
+        Ok((has_extensions, non_terminal))
+    }
+
+    /// Whether the type contains `Self` or, if `by_name` is set, the name of
+    /// the derived type.
+    fn refers_to_self(&self, ty: &syn::Type, by_name: bool) -> bool {
+        fn contains(tokens: TokenStream2, name: &Ident, by_name: bool) -> bool {
+            tokens.into_iter().any(|token| match token {
+                TokenTree::Ident(ident) => ident == "Self" || (by_name && &ident == name),
+                TokenTree::Group(group) => contains(group.stream(), name, by_name),
+                _ => false,
+            })
+        }
+        contains(ty.to_token_stream(), &self.data.name, by_name)
+    }
+
+    /// Fails the compilation if a field followed by other data ends with TLV
+    /// extensions. The value of `HAS_EXTENSIONS` is evaluated as well, which
+    /// fails for the last fields being collections (or other types asserting
+    /// their items to be followed by other data) of types with extensions.
+    /// Field types of generic types may depend on the type parameters, so
+    /// they are not checked.
+    fn derive_extensions_check(&self) -> Result<TokenStream2> {
+        if !self.data.generics.params.is_empty() {
+            return Ok(TokenStream2::new());
+        }
+        let crate_name = &self.conf.strict_crate;
+        let name = &self.data.name;
+        let type_name = name.to_string();
+        let (_, non_terminal) = self.extensions_layout()?;
+        let check = non_terminal.into_iter().map(|ty| {
+            let msg = format!(
+                "field of type `{}` in `{type_name}` ends with TLV extensions, thus it must be \
+                 the last field of a structure",
+                ty.to_token_stream()
+            );
+            quote! {
+                const _: () = assert!(!<#ty as #crate_name::StrictType>::HAS_EXTENSIONS, #msg);
+            }
+        });
+        Ok(quote! {
+            #( #check )*
+            const _: bool = <#name as #crate_name::StrictType>::HAS_EXTENSIONS;
         })
     }
 }
@@ -127,14 +299,17 @@ impl DeriveType<'_> {
         let strict_name = match self.0.conf.rename {
             Some(ref rename) => quote! {
                 fn strict_name() -> Option<#crate_name::TypeName> {
-                    Some(tn!(#rename))
+                    Some(#crate_name::tn!(#rename))
                 }
             },
             None => TokenStream2::new(),
         };
 
+        let (has_extensions, _) = self.0.extensions_layout()?;
+
         Ok(quote! {
             const STRICT_LIB_NAME: &'static str = #lib_name;
+            const HAS_EXTENSIONS: bool = #has_extensions;
 
             #strict_name
         })
@@ -179,15 +354,49 @@ impl DeriveInner for DeriveSum {
         let mut orders = Vec::with_capacity(variants.len());
         let mut idents = Vec::with_capacity(variants.len());
         let mut renames = Vec::with_capacity(variants.len());
+        let mut unknown = TokenStream2::new();
+
+        // The variant for unknown tags has no tag on its own and is not a part of
+        // the type definition
+        let (known, catch_all): (Vec<_>, Vec<_>) =
+            variants.iter().partition(|variant| Some(&variant.name) != self.1.as_ref());
+        for variant in catch_all {
+            let attr = VariantAttr::try_from(variant.attr.clone())?;
+            let name = &variant.name;
+            let rename = attr.variant_name(name);
+            unknown = quote! { Self::#name(..) => #rename, };
+        }
 
-        for (index, variant) in variants.iter().enumerate() {
+        // Tags known at the macro expansion time, used to detect collisions
+        let mut known_tags = Vec::<(u8, &Ident)>::with_capacity(variants.len());
+        for (index, variant) in known.into_iter().enumerate() {
             let attr = VariantAttr::try_from(variant.attr.clone())?;
             let name = &variant.name;
             let rename = attr.variant_name(name);
+            let literal_tag = match (&self.0.tags, &attr.tag) {
+                (_, Some(tag)) => tag_literal(tag)?,
+                (VariantTags::Order, None) => Some(index as u8),
+                _ => None,
+            };
+            if let Some(tag) = literal_tag {
+                if let Some((_, other)) = known_tags.iter().find(|(t, _)| *t == tag) {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("variants `{other}` and `{name}` have the same tag {tag}"),
+                    ));
+                }
+                known_tags.push((tag, name));
+            }
             let tag = match (&self.0.tags, &attr.tag) {
-                (_, Some(tag)) => tag.to_token_stream(),
-                (VariantTags::Repr, None) => quote! { Self::#name },
-                (VariantTags::Order, None) => quote! { #index },
+                // Tag expressions must have `u8` type, so they are not silently
+                // truncated. The binding is evaluated inside the associated
+                // constant, so the expression may refer to `Self`.
+                (_, Some(tag)) => {
+                    let tag = tag_expr(tag);
+                    quote! { { let tag: u8 = #tag; tag } }
+                }
+                (VariantTags::Repr, None) => quote! { Self::#name as u8 },
+                (VariantTags::Order, None) => quote! { #index as u8 },
                 (VariantTags::Custom, None) => {
                     panic!("tag is required for variant `{}`", variant.name)
                 }
@@ -203,12 +412,13 @@ impl DeriveInner for DeriveSum {
 
         Ok(quote! {
             const ALL_VARIANTS: &'static [(u8, &'static str)] = &[
-                #( (#orders as u8, #renames) ),*
+                #( (#orders, #renames) ),*
             ];
 
             fn variant_name(&self) -> &'static str {
                 match self {
                     #( Self::#idents => #renames, )*
+                    #unknown
                 }
             }
         })
@@ -225,7 +435,12 @@ impl DeriveInner for DeriveTuple {
     }
 
     fn derive_tuple_inner(&self, fields: &Items<Field>) -> Result<TokenStream2> {
-        let field_count = fields.len();
+        let mut field_count = 0usize;
+        for field in fields {
+            if !FieldAttr::with(field.attr.clone(), FieldKind::Unnamed)?.skip {
+                field_count += 1;
+            }
+        }
         Ok(quote! {
             const FIELD_COUNT: u8 = #field_count as u8;
         })
@@ -279,3 +494,51 @@ impl DeriveInner for DeriveUnion {
         Ok(TokenStream2::new())
     }
 }
+
+impl DeriveNoDumb {
+    fn derive_dumb(&self) -> Result<TokenStream2> {
+        Ok(quote! {
+            fn strict_dumb() -> Self {
+                panic!("encode-only type {} has no dumb value", ::core::any::type_name::<Self>())
+            }
+        })
+    }
+}
+
+impl DeriveInner for DeriveNoDumb {
+    fn derive_unit_inner(&self) -> Result<TokenStream2> { self.derive_dumb() }
+    fn derive_struct_inner(&self, _fields: &Items<NamedField>) -> Result<TokenStream2> {
+        self.derive_dumb()
+    }
+    fn derive_tuple_inner(&self, _fields: &Items<Field>) -> Result<TokenStream2> {
+        self.derive_dumb()
+    }
+    fn derive_enum_inner(&self, _variants: &Items<Variant>) -> Result<TokenStream2> {
+        self.derive_dumb()
+    }
+}
+
+/// Value of the tag given by an integer literal, which must fit `u8`. Tags
+/// given by other expressions can't be evaluated by the macro.
+fn tag_literal(tag: &Expr) -> Result<Option<u8>> {
+    match tag {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse::<u8>().map(Some).map_err(|_| {
+            Error::new(lit.span(), format!("tag {lit} doesn't fit into `u8` (0..=255)"))
+        }),
+        _ => Ok(None),
+    }
+}
+
+/// Tag expression to be assigned to a `u8` constant. Compound expressions have
+/// to be put into braces in the attribute, which are removed here.
+fn tag_expr(tag: &Expr) -> TokenStream2 {
+    match tag {
+        Expr::Block(ExprBlock { block, .. }) => match block.stmts.as_slice() {
+            [Stmt::Expr(expr)] => expr.to_token_stream(),
+            _ => tag.to_token_stream(),
+        },
+        _ => tag.to_token_stream(),
+    }
+}
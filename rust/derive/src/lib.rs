@@ -58,6 +58,8 @@ mod derive_dumb;
 mod derive_type;
 mod derive_encode;
 mod derive_decode;
+mod derive_arbitrary;
+mod derive_id;
 
 use proc_macro::TokenStream;
 use syn::DeriveInput;
@@ -65,6 +67,15 @@ use syn::DeriveInput;
 use crate::params::StrictDerive;
 
 /// Derives [`StrictDumb`] implementation for the type.
+///
+/// The dumb value of an enum is the
+/// variant marked with `#[strict_type(dumb)]` attribute, having all its fields
+/// set to their dumb values. If some of the variant fields don't implement
+/// `StrictDumb`, the variant may provide the dumb value explicitly as an
+/// expression, like `#[strict_type(dumb = { Self::Data(Payload::empty()) })]`.
+/// Enums having only unit variants, like the ones with `tags = repr`, don't
+/// need the attribute: unless one of their variants is marked as dumb, the
+/// first variant is used.
 #[proc_macro_derive(StrictDumb, attributes(strict_type))]
 pub fn derive_strict_dumb(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
@@ -75,6 +86,60 @@ pub fn derive_strict_dumb(input: TokenStream) -> TokenStream {
 }
 
 /// Derives [`StrictType`] implementation for the type.
+///
+/// The strict type name is the name of the Rust type, unless given with
+/// `#[strict_type(rename = "Name")]` attribute. Together with the other
+/// derives this replaces `impl_strict_newtype!` macro for newtypes like
+/// `struct Foo(u64)`, which are encoded in the same way as their inner value.
+///
+/// Recursive types, like trees, should hold the recursive values in
+/// `Option<Box<Self>>` fields (or in confined collections). `Box` adds nothing
+/// to the encoding, so such a field takes a single tag byte followed by the
+/// value, if it is present. The dumb value of `Box<T>` is provided by its
+/// `Default` implementation, thus a recursive type must implement `Default`
+/// instead of deriving `StrictDumb`.
+///
+/// If the type is marked with `#[strict_type(arbitrary)]` attribute, the macro
+/// also derives `StrictArbitrary` and `proptest::arbitrary::Arbitrary`
+/// implementations, which require `proptest` feature of `strict_encoding`
+/// crate.
+///
+/// If the type is marked with `#[strict_type(strict_id)]` attribute, the macro
+/// implements `StrictTypeId` trait for it and also adds inherent `STRICT_ID`
+/// constant and `strict_type_id()` const function to the type. The id is a
+/// 64-bit FNV-1a hash computed at compile time from the library name, the
+/// strict type name, strict field or variant names (with variant tags) in
+/// their declaration order, and the `StrictTypeId::STRICT_ID` of the field
+/// types. Thus, renaming the type, its fields or variants (including with
+/// `rename` attribute), reordering fields or variants, changing variant tags
+/// or field types changes the id, while renaming Rust identifiers without
+/// changing their strict names keeps it intact. Since the ids of the field
+/// types are used, the id doesn't depend on the paths or aliases used for the
+/// field types, but it differs for different instantiations of generic types;
+/// all field types (and, for generic types, type parameters) must implement
+/// `StrictTypeId`. Fields with `encode_with` or `decode_with` attribute are
+/// identified by the paths of the encoding functions instead.
+///
+/// The same attribute adds `WIRE_FINGERPRINT` constant, which identifies the
+/// wire format of the type rather than the type itself. It is a 64-bit FNV-1a
+/// hash of the `StrictTypeId::WIRE_FINGERPRINT` of the types of all encoded
+/// fields, in their declaration order (per variant for enums), followed by
+/// the variant tags. The library, type, field and variant names do not enter
+/// the fingerprint, so the types which differ only by names (including
+/// `rename` attributes, also in the nested types) share it, as well as the
+/// structures and tuples with the same field types. Reordering the fields of
+/// different types, changing field types or variant tags changes the
+/// fingerprint.
+///
+/// Enum variant tags given with `#[strict_type(tag = ...)]` attribute may be
+/// integer literals, constants (including associated constants) or other
+/// constant expressions, which must have `u8` type: wider constants fail the
+/// compilation instead of being truncated. Compound expressions must be put
+/// into braces, like `#[strict_type(tag = { BASE + 1 })]`. Variants
+/// having the same tag fail the compilation: the conflicting variants are
+/// named when the tags are given by integer literals or by the variant order,
+/// while the tags computed from constant expressions are checked by a
+/// constant assertion (except for generic enums).
 #[proc_macro_derive(StrictType, attributes(strict_type))]
 pub fn derive_strict_type(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
@@ -85,6 +150,57 @@ pub fn derive_strict_type(input: TokenStream) -> TokenStream {
 }
 
 /// Derives [`StrictEncode`] implementation for the type.
+///
+/// A structure or tuple field marked with `#[strict_type(encode_with = path)]`
+/// is encoded by first converting it with `path`, which must have signature
+/// `fn(&FieldType) -> Proxy`, where `Proxy: StrictEncode + StrictType`. The
+/// proxy is written instead of the field value. The attribute is not
+/// supported for the fields of enum variants.
+///
+/// The last field of a structure may be marked with
+/// `#[strict_type(extensions)]` attribute; it must have
+/// `Confined<BTreeMap<u16, Vec<u8>>, 0, MAX>` type. The field is not a part of
+/// the structure type definition; instead, after all other fields it is
+/// written as a sequence of type-length-value records (see
+/// `TypedWrite::write_extensions`), which are read back until the end of the
+/// data. Thus, such a structure (as well as any type ending with it, including
+/// `Option`, `Box` and other wrappers) may be encoded only at the end of the
+/// data: the derive fails the compilation if the `extensions` field is not the
+/// last one, and if a type ending with the extensions is used as a
+/// non-terminal field of a non-generic structure or tuple or as a field of an
+/// enum variant. Collections, tuples and arrays fail the constant evaluation
+/// of their `StrictType::HAS_EXTENSIONS` for such item types. A type referring
+/// to itself in its last field is assumed not to end with the extensions,
+/// while types referring to each other through their last fields fail the
+/// compilation with a cycle in the constant evaluation.
+///
+/// A structure field marked with `#[strict_type(present_if = predicate)]`
+/// attribute is conditional: it is written and read only when the predicate
+/// holds, and otherwise is absent from the data and gets its `Default` value
+/// on decoding. The predicate is an expression over the preceding fields,
+/// which are referenced by their names and are bound to references to the
+/// field values; it may evaluate either to `bool` or to `&bool`, like
+/// `#[strict_type(present_if = has_value)]` or
+/// `#[strict_type(present_if = { flags & HAS_VALUE != 0 })]`. This is an
+/// opt-in for interoperability with the protocols having optional fields,
+/// which breaks the invariant of strict encoding that every field is always
+/// present in the data: the type definition still contains the field, while
+/// its value is not written when the field is absent. Thus, the types of such
+/// fields must implement `Default` and `PartialEq`, and encoding fails if an
+/// absent field doesn't have the default value, since it would be lost.
+/// Conditional fields are not supported in enum variants, can't have default
+/// values or be decoded with `decode_with`, and the types having them can't
+/// derive arbitrary values.
+///
+/// Types holding borrowed data, like `enum Msg<'a> { Data(&'a TinyBlob) }`,
+/// can't be decoded and usually have no dumb values. They may be marked with
+/// `#[strict_type(encode_only)]` attribute, in which case `StrictType` derive
+/// also implements `StrictDumb`, which panics, so the types must not derive
+/// `StrictDumb` or implement `Default`. They are written like the equivalent
+/// owned types, producing the same data and JSON projection: a field of type
+/// `&T` is written and defined as `T`, unless `T` is `str` or a slice. Such
+/// types must not derive `StrictDecode`, and can't have `unknown` variant or
+/// `strict_id` attribute.
 #[proc_macro_derive(StrictEncode, attributes(strict_type))]
 pub fn derive_strict_encode(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
@@ -95,6 +211,47 @@ pub fn derive_strict_encode(input: TokenStream) -> TokenStream {
 }
 
 /// Derives [`StrictDecode`] implementation for the type.
+///
+/// A field marked with `#[strict_type(decode_with = path)]` is decoded by
+/// reading a proxy value and converting it with `path`, which must have
+/// signature `fn(Proxy) -> Result<FieldType, DecodeError>`, where
+/// `Proxy: StrictDecode`. The proxy type is inferred from the function
+/// argument, so it must match the type written by the `encode_with` function.
+/// Like `encode_with`, the attribute is not supported for the fields of enum
+/// variants.
+///
+/// By default, decoding an enum fails on a tag which doesn't belong to any of
+/// its variants. An enum marked with `#[strict_type(unknown = Variant)]`
+/// attribute decodes such tags into the given tuple variant instead, which
+/// must be either `Variant(u8)`, carrying the raw tag, or
+/// `Variant(u8, Vec<u8>)`, carrying the raw tag and all the remaining data.
+/// The first form is allowed only if none of the known variants has fields,
+/// since otherwise an unknown variant may have data, which would be left
+/// unread.
+/// The variant is encoded back as the raw tag followed by the data and is not
+/// a part of the type definition (it is absent in `ALL_VARIANTS`). This is an
+/// opt-in which makes the format lenient: data with unknown variants are
+/// accepted, and, since their length can't be known, an enum with the second
+/// form of the variant may be decoded only at the end of the input.
+///
+/// Fields may be added to a structure in a backward-compatible way by marking
+/// the structure with `#[strict_type(extensible)]` and giving each of the new
+/// fields a default value with `#[strict_type(default = expr)]` attribute.
+/// When the data end where such a field is expected, the field gets the
+/// default value instead of failing the decoding. The compatibility rules are:
+/// - this is an opt-in: the fields of structures which are not `extensible` are always required;
+/// - only trailing fields may have default values, so a field having no default value can't follow
+///   the one with a default value;
+/// - new fields must be appended after all existing ones, and the existing fields must not be
+///   changed;
+/// - the defaults are applied only when the data end, thus an extensible structure may be decoded
+///   only at the end of the input; if it is followed by other data, those will be read as the new
+///   fields;
+/// - a field truncated in the middle is not absent and fails the decoding with the end-of-data
+///   error, unless the reader doesn't count the bytes read (see `ReadRaw::raw_count`), in which
+///   case any end of data is taken for an absent field.
+///
+/// The encoding is not affected: all the fields are always written.
 #[proc_macro_derive(StrictDecode, attributes(strict_type))]
 pub fn derive_strict_decode(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
@@ -22,13 +22,13 @@
 use std::collections::HashMap;
 
 use amplify_syn::{
-    ArgValueReq, AttrReq, DataType, EnumKind, FieldKind, ListReq, ParametrizedAttr, TypeClass,
-    ValueClass,
+    ArgValueReq, AttrReq, DataInner, DataType, EnumKind, FieldKind, Fields, Items, ListReq,
+    NamedField, ParametrizedAttr, TypeClass, ValueClass,
 };
 use heck::ToLowerCamelCase;
-use proc_macro2::{Ident, Span};
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::ToTokens;
-use syn::{DeriveInput, Error, Expr, LitInt, LitStr, Path, Result};
+use syn::{Attribute, Data, DeriveInput, Error, Expr, LitStr, Meta, NestedMeta, Path, Result};
 
 const ATTR: &str = "strict_type";
 const ATTR_CRATE: &str = "crate";
@@ -46,6 +46,14 @@ const ATTR_TAG: &str = "tag";
 const ATTR_SKIP: &str = "skip";
 const ATTR_INTO_U8: &str = "into_u8";
 const ATTR_TRY_FROM_U8: &str = "try_from_u8";
+const ATTR_ARBITRARY: &str = "arbitrary";
+const ATTR_STRICT_ID: &str = "strict_id";
+const ATTR_UNKNOWN: &str = "unknown";
+const ATTR_EXTENSIBLE: &str = "extensible";
+const ATTR_DEFAULT: &str = "default";
+const ATTR_EXTENSIONS: &str = "extensions";
+const ATTR_ENCODE_ONLY: &str = "encode_only";
+const ATTR_PRESENT_IF: &str = "present_if";
 
 pub struct ContainerAttr {
     pub strict_crate: Path,
@@ -56,6 +64,11 @@ pub struct ContainerAttr {
     pub encode_with: Option<Path>,
     #[allow(dead_code)]
     pub decode_with: Option<Path>,
+    pub arbitrary: bool,
+    pub strict_id: bool,
+    pub unknown: Option<Ident>,
+    pub extensible: bool,
+    pub encode_only: bool,
 }
 
 pub struct EnumAttr {
@@ -66,14 +79,20 @@ pub struct EnumAttr {
 
 pub struct FieldAttr {
     pub dumb: Option<Expr>,
+    pub default: Option<Expr>,
     pub rename: Option<LitStr>,
     pub skip: bool,
+    pub extensions: bool,
+    pub present_if: Option<Expr>,
+    pub encode_with: Option<Path>,
+    pub decode_with: Option<Path>,
 }
 
 pub struct VariantAttr {
     pub dumb: bool,
+    pub dumb_value: Option<Expr>,
     pub rename: Option<LitStr>,
-    pub tag: Option<LitInt>,
+    pub tag: Option<Expr>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -92,6 +111,7 @@ impl ContainerAttr {
             (ATTR_DUMB, ArgValueReq::optional(ValueClass::Expr)),
             (ATTR_ENCODE_WITH, ArgValueReq::optional(TypeClass::Path)),
             (ATTR_DECODE_WITH, ArgValueReq::optional(TypeClass::Path)),
+            (ATTR_UNKNOWN, ArgValueReq::optional(TypeClass::Path)),
         ]
     }
 }
@@ -99,9 +119,12 @@ impl ContainerAttr {
 impl EnumAttr {
     fn attr_req(map: HashMap<&str, ArgValueReq>, kind: EnumKind) -> AttrReq {
         let mut req = AttrReq::with(map);
+        let mut paths =
+            vec![path!(arbitrary), path!(strict_id), path!(extensible), path!(encode_only)];
         if kind == EnumKind::Primitive {
-            req.path_req = ListReq::any_of(vec![path!(try_from_u8), path!(into_u8)], false);
+            paths.extend([path!(try_from_u8), path!(into_u8)]);
         }
+        req.path_req = ListReq::any_of(paths, false);
         req
     }
 }
@@ -129,6 +152,21 @@ impl TryFrom<ParametrizedAttr> for ContainerAttr {
                 .arg_value(ATTR_DECODE_WITH)
                 .or_else(|_| params.arg_value(ATTR_WITH))
                 .ok(),
+            arbitrary: params.has_verbatim(ATTR_ARBITRARY),
+            strict_id: params.has_verbatim(ATTR_STRICT_ID),
+            extensible: params.has_verbatim(ATTR_EXTENSIBLE),
+            encode_only: params.has_verbatim(ATTR_ENCODE_ONLY),
+            unknown: params
+                .arg_value::<Path>(ATTR_UNKNOWN)
+                .ok()
+                .map(|path| match path.get_ident() {
+                    Some(ident) => Ok(ident.clone()),
+                    None => Err(Error::new_spanned(
+                        path,
+                        "`unknown` attribute must contain an enum variant name",
+                    )),
+                })
+                .transpose()?,
         })
     }
 }
@@ -180,26 +218,125 @@ impl EnumAttr {
     }
 }
 
+/// Name of the variable holding presence of the conditional field `name`.
+pub fn present_ident(name: &Ident) -> Ident {
+    Ident::new(&format!("__present_{name}"), Span::call_site())
+}
+
 impl FieldAttr {
     pub fn with(mut params: ParametrizedAttr, kind: FieldKind) -> Result<Self> {
-        let mut map =
-            HashMap::from_iter(vec![(ATTR_DUMB, ArgValueReq::optional(ValueClass::Expr))]);
+        let mut map = HashMap::from_iter(vec![
+            (ATTR_DUMB, ArgValueReq::optional(ValueClass::Expr)),
+            (ATTR_ENCODE_WITH, ArgValueReq::optional(TypeClass::Path)),
+            (ATTR_DECODE_WITH, ArgValueReq::optional(TypeClass::Path)),
+        ]);
 
         if kind == FieldKind::Named {
             map.insert(ATTR_RENAME, ArgValueReq::optional(ValueClass::str()));
+            map.insert(ATTR_DEFAULT, ArgValueReq::optional(ValueClass::Expr));
+            map.insert(ATTR_PRESENT_IF, ArgValueReq::optional(ValueClass::Expr));
         }
 
         let mut attr_req = AttrReq::with(map);
-        attr_req.path_req = ListReq::maybe_one(path!(skip));
+        attr_req.path_req = if kind == FieldKind::Named {
+            ListReq::any_of(vec![path!(skip), path!(extensions)], false)
+        } else {
+            ListReq::maybe_one(path!(skip))
+        };
         params.check(attr_req)?;
 
+        // Extensions are written after the structure, so all field-level
+        // processing treats them as a skipped field
+        let extensions = params.has_verbatim(ATTR_EXTENSIONS);
         Ok(FieldAttr {
             rename: params.arg_value(ATTR_RENAME).ok(),
             dumb: params.arg_value(ATTR_DUMB).ok(),
-            skip: params.has_verbatim(ATTR_SKIP),
+            default: params.arg_value(ATTR_DEFAULT).ok(),
+            skip: params.has_verbatim(ATTR_SKIP) || extensions,
+            extensions,
+            present_if: params.arg_value(ATTR_PRESENT_IF).ok(),
+            encode_with: params.arg_value(ATTR_ENCODE_WITH).ok(),
+            decode_with: params.arg_value(ATTR_DECODE_WITH).ok(),
+        })
+    }
+
+    /// Union variant definitions require field types, so the field values
+    /// can't be encoded or decoded with custom functions.
+    pub fn check_variant_field(&self) -> Result<()> {
+        if let Some(ref expr) = self.present_if {
+            return Err(Error::new_spanned(
+                expr,
+                "`present_if` is not supported on enum variant fields",
+            ));
+        }
+        if let Some(ref path) = self.encode_with {
+            return Err(Error::new_spanned(
+                path,
+                "`encode_with` is not supported on enum variant fields",
+            ));
+        }
+        match self.decode_with {
+            Some(ref path) => Err(Error::new_spanned(
+                path,
+                "`decode_with` is not supported on enum variant fields",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Statement computing presence of a conditional field into `ident`. The
+    /// predicate refers to the preceding fields by their names, which are bound
+    /// to references to the field values, and may evaluate either to `bool` or
+    /// to `&bool`.
+    pub fn presence(
+        &self,
+        ident: &Ident,
+        preceding: &[Ident],
+        value: impl Fn(&Ident) -> TokenStream2,
+    ) -> Option<TokenStream2> {
+        let predicate = self.present_if.as_ref()?;
+        let bindings = preceding.iter().map(|name| {
+            let value = value(name);
+            quote! {
+                #[allow(unused_variables)]
+                let #name = #value;
+            }
+        });
+        Some(quote! {
+            let #ident: bool = {
+                #( #bindings )*
+                *::core::borrow::Borrow::<bool>::borrow(&(#predicate))
+            };
         })
     }
 
+    /// Expression for the field value in the dumb value of its type. Skipped
+    /// fields, which are usually caches, take their default values and are not
+    /// required to implement `StrictDumb`.
+    pub fn dumb_value(&self, crate_name: &Path) -> TokenStream2 {
+        match (&self.dumb, self.skip) {
+            (Some(dumb_value), _) => quote! { #dumb_value },
+            (None, true) => quote! { ::core::default::Default::default() },
+            (None, false) => quote! { #crate_name::StrictDumb::strict_dumb() },
+        }
+    }
+
+    /// Expression for the field value which is passed to the writer.
+    pub fn encode_value(&self, value: TokenStream2) -> TokenStream2 {
+        match self.encode_with {
+            Some(ref path) => quote! { &#path(#value) },
+            None => value,
+        }
+    }
+
+    /// Expression converting the field value returned by the reader.
+    pub fn decode_value(&self, value: TokenStream2) -> TokenStream2 {
+        match self.decode_with {
+            Some(ref path) => quote! { #path(#value)? },
+            None => value,
+        }
+    }
+
     pub fn field_name(&self, name: &Ident) -> LitStr {
         match self.rename {
             None => LitStr::new(&name.to_string().to_lower_camel_case(), name.span()),
@@ -214,17 +351,21 @@ impl TryFrom<ParametrizedAttr> for VariantAttr {
     fn try_from(mut params: ParametrizedAttr) -> Result<Self> {
         let map = HashMap::from_iter(vec![
             (ATTR_RENAME, ArgValueReq::optional(ValueClass::str())),
-            (ATTR_TAG, ArgValueReq::optional(ValueClass::int())),
+            (ATTR_TAG, ArgValueReq::optional(ValueClass::Expr)),
+            (ATTR_DUMB, ArgValueReq::optional(ValueClass::Expr)),
         ]);
 
-        let mut req = AttrReq::with(map);
-        req.path_req = ListReq::maybe_one(path!(dumb));
-        params.check(req)?;
+        // Verbatim `dumb` is otherwise taken as a `dumb = ...` argument without value
+        let dumb = params.has_verbatim(ATTR_DUMB);
+        params.paths.retain(|path| !path.is_ident(ATTR_DUMB));
+
+        params.check(AttrReq::with(map))?;
 
         Ok(VariantAttr {
             rename: params.arg_value(ATTR_RENAME).ok(),
             tag: params.arg_value(ATTR_TAG).ok(),
-            dumb: params.has_verbatim("dumb"),
+            dumb,
+            dumb_value: params.arg_value(ATTR_DUMB).ok(),
         })
     }
 }
@@ -246,6 +387,103 @@ impl VariantAttr {
     }
 }
 
+/// The catch-all variant for unknown tags must be a tuple variant carrying
+/// the raw tag, optionally followed by the raw variant data.
+fn check_unknown_variant(unknown: &Ident, data: &Data) -> Result<()> {
+    let Data::Enum(data) = data else {
+        return Err(Error::new(unknown.span(), "`unknown` attribute can be used only with enums"));
+    };
+    let Some(variant) = data.variants.iter().find(|variant| &variant.ident == unknown) else {
+        return Err(Error::new(unknown.span(), format!("enum has no variant `{unknown}`")));
+    };
+    match &variant.fields {
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 2 => Ok(()),
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            // Unknown variants of a union with data may carry data themselves, and the
+            // `(u8)` form would leave it unread.
+            match data
+                .variants
+                .iter()
+                .find(|other| &other.ident != unknown && !other.fields.is_empty())
+            {
+                None => Ok(()),
+                Some(other) => Err(Error::new_spanned(
+                    variant,
+                    format!(
+                        "variant `{}` has fields, so unknown variants may carry data which `(u8)` \
+                         would leave unread; use `(u8, Vec<u8>)` instead",
+                        other.ident
+                    ),
+                )),
+            }
+        }
+        _ => Err(Error::new_spanned(
+            variant,
+            "variant for unknown tags must be either `(u8)` or `(u8, Vec<u8>)` tuple variant",
+        )),
+    }
+}
+
+/// Extension records are read until the end of the data, so the field holding
+/// them must be the last one of a structure.
+fn check_extensions(data: &DataType, conf: &ContainerAttr) -> Result<()> {
+    let check_fields = |fields: &Items<NamedField>, in_struct: bool| -> Result<()> {
+        let last = fields.len().saturating_sub(1);
+        for (index, named_field) in fields.iter().enumerate() {
+            let attr = FieldAttr::with(named_field.field.attr.clone(), FieldKind::Named)?;
+            if !attr.extensions {
+                continue;
+            }
+            let name = &named_field.name;
+            if !in_struct {
+                return Err(Error::new(
+                    name.span(),
+                    "`extensions` attribute is not supported on enum variant fields",
+                ));
+            }
+            if index != last {
+                return Err(Error::new(
+                    name.span(),
+                    "`extensions` field must be the last field of the structure",
+                ));
+            }
+            if conf.extensible {
+                return Err(Error::new(
+                    name.span(),
+                    "`extensions` field can't be used in `extensible` structures",
+                ));
+            }
+        }
+        Ok(())
+    };
+    match &data.inner {
+        DataInner::Struct(Fields::Named(fields)) => check_fields(fields, true),
+        DataInner::Enum(variants) => {
+            for variant in variants {
+                if let Fields::Named(fields) = &variant.fields {
+                    check_fields(fields, false)?;
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn has_int_repr(attrs: &[Attribute]) -> bool {
+    const INT_REPRS: [&str; 12] =
+        ["u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+    attrs.iter().filter(|attr| attr.path.is_ident("repr")).any(|attr| {
+        let Ok(Meta::List(list)) = attr.parse_meta() else {
+            return false;
+        };
+        list.nested.iter().any(|nested| {
+            matches!(nested, NestedMeta::Meta(Meta::Path(path))
+                if INT_REPRS.iter().any(|int| path.is_ident(int)))
+        })
+    })
+}
+
 pub struct StrictDerive {
     pub data: DataType,
     pub conf: ContainerAttr,
@@ -256,8 +494,37 @@ impl TryFrom<DeriveInput> for StrictDerive {
 
     fn try_from(input: DeriveInput) -> Result<Self> {
         let params = ParametrizedAttr::with(ATTR, &input.attrs)?;
+        let repr_tags = params
+            .arg_value::<Path>(ATTR_TAGS)
+            .map(|path| path.is_ident(ATTR_TAGS_REPR))
+            .unwrap_or_default();
+        if repr_tags && matches!(input.data, Data::Enum(_)) && !has_int_repr(&input.attrs) {
+            return Err(Error::new(
+                input.ident.span(),
+                "enums with `tags = repr` must have an explicit integer representation, like \
+                 `#[repr(u8)]`, which defines the values of the variant tags",
+            ));
+        }
         let conf = ContainerAttr::try_from(params)?;
+        if let Some(unknown) = &conf.unknown {
+            check_unknown_variant(unknown, &input.data)?;
+        }
+        if conf.extensible
+            && !matches!(&input.data, Data::Struct(data) if matches!(data.fields, syn::Fields::Named(_)))
+        {
+            return Err(Error::new(
+                input.ident.span(),
+                "only structures with named fields may be `extensible`",
+            ));
+        }
+        if conf.encode_only && (conf.unknown.is_some() || conf.strict_id) {
+            return Err(Error::new(
+                input.ident.span(),
+                "`encode_only` types can't have `unknown` variant or `strict_id`",
+            ));
+        }
         let data = DataType::with(input, ident!(strict_type))?;
+        check_extensions(&data, &conf)?;
         Ok(Self { data, conf })
     }
 }
@@ -0,0 +1,113 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate amplify;
+#[macro_use]
+extern crate strict_encoding_derive;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use amplify::confinement::{Confined, TinyString, TinyVec};
+use strict_encoding::arbitrary::StrictArbitrary;
+use strict_encoding::proptest::prelude::*;
+use strict_encoding::stl::{AlphaCaps, AlphaNum};
+use strict_encoding::{RString, StrictDumb};
+use strict_encoding_test::test_object_encoding_roundtrip;
+
+const TEST_LIB: &str = "TestLib";
+const MAX: usize = u16::MAX as usize;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = order, arbitrary)]
+enum Kind {
+    #[strict_type(dumb)]
+    Empty,
+    Value(u16),
+    Named {
+        id: RString<AlphaCaps, AlphaNum, 1, 8>,
+        flag: bool,
+    },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, arbitrary)]
+struct Record {
+    kind: Kind,
+    payload: Confined<Vec<u8>, 1, 32>,
+    comment: Option<TinyString>,
+    pairs: TinyVec<(u8, i64)>,
+}
+
+impl StrictDumb for Record {
+    fn strict_dumb() -> Self {
+        Self {
+            kind: Kind::Empty,
+            payload: Confined::with(0),
+            comment: None,
+            pairs: empty!(),
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_roundtrip(record in any::<Record>()) {
+        prop_assert!(!record.payload.is_empty() && record.payload.len() <= 32);
+        prop_assert!(test_object_encoding_roundtrip::<_, MAX>(&record).is_ok());
+    }
+
+    // Most of `u8` values are needed to fill these with unique items
+    #[test]
+    fn arbitrary_unique_items(
+        set in Confined::<BTreeSet<u8>, 200, 255>::strict_arbitrary(),
+        map in Confined::<BTreeMap<u8, bool>, 200, 255>::strict_arbitrary(),
+    ) {
+        prop_assert!(set.len() >= 200 && set.len() <= 255);
+        prop_assert!(map.len() >= 200 && map.len() <= 255);
+    }
+}
+
+mod shadowed {
+    use super::*;
+
+    /// Shadows the prelude trait which must not be used by the derived code.
+    #[allow(dead_code)]
+    struct Default;
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType)]
+    #[strict_type(lib = TEST_LIB, arbitrary)]
+    pub(super) struct Skipped {
+        pub(super) value: u8,
+        #[strict_type(skip)]
+        pub(super) cache: Option<u16>,
+    }
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_skipped(value in any::<shadowed::Skipped>()) {
+        prop_assert_eq!(value.cache, None);
+    }
+}
@@ -26,11 +26,19 @@ extern crate strict_encoding_derive;
 
 mod common;
 
+use std::collections::BTreeMap;
 use std::convert::Infallible;
+use std::io;
 
+use amplify::confinement::{Confined, TinyBlob};
+use amplify::num::{i1024, i256, i512, u1024, u256, u512};
 use strict_encoding::{
-    StrictDecode, StrictDumb, StrictEncode, StrictSerialize, StrictSum, VariantError,
+    DecodeError, DefineStruct, DefineTuple, DefineUnion, DeserializeError, FieldName, Primitive,
+    StreamWriter, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode, StrictEnum,
+    StrictSerialize, StrictStruct, StrictSum, StrictTuple, StrictUnion, StrictWriter, TypedParent,
+    TypedWrite, VariantError, VariantName, WriteStruct, WriteTuple, WriteUnion,
 };
+use strict_encoding_test::{test_bigint_roundtrip, test_encoding_roundtrip};
 
 const TEST_LIB: &str = "TestLib";
 
@@ -41,6 +49,8 @@ fn wrapper_base() -> common::Result {
     #[strict_type(lib = TEST_LIB)]
     struct ShortLen(u16);
 
+    test_encoding_roundtrip::<_, 2>(&ShortLen(0x0201), vec![0x01, 0x02])?;
+
     Ok(())
 }
 
@@ -51,6 +61,249 @@ fn tuple_base() -> common::Result {
     #[strict_type(lib = TEST_LIB)]
     struct TaggedInfo(u16, u64);
 
+    test_encoding_roundtrip::<_, 10>(&TaggedInfo(0x0201, 3), vec![1, 2, 3, 0, 0, 0, 0, 0, 0, 0])?;
+
+    Ok(())
+}
+
+/// Writer registering the types of the written values, like the builders of
+/// type libraries do, without writing any data.
+struct Registry {
+    types: Vec<String>,
+    raw: StreamWriter<io::Sink>,
+}
+
+/// Writer of the fields of tuples and structures to the [`Registry`].
+struct RegistryFields(Registry);
+
+/// Definer and writer of union variants to the [`Registry`].
+struct RegistryUnion(Registry);
+
+/// Definer and writer of the fields of union variants to the [`Registry`].
+struct RegistryVariant(Registry);
+
+impl Registry {
+    fn register(value: &impl StrictEncode) -> Vec<String> {
+        let registry = Registry {
+            types: vec![],
+            raw: StreamWriter::sink::<{ usize::MAX }>(),
+        };
+        value.strict_encode(registry).unwrap().types
+    }
+
+    fn register_sum<T: StrictSum>(&mut self) {
+        let variants = T::ALL_VARIANTS.iter().map(|(_, name)| *name).collect::<Vec<_>>();
+        self.types.push(format!("{}<{}>", T::strict_name().unwrap(), variants.join(", ")));
+    }
+}
+
+impl TypedParent for Registry {}
+impl TypedParent for RegistryUnion {}
+
+impl TypedWrite for Registry {
+    type TupleWriter = RegistryFields;
+    type StructWriter = RegistryFields;
+    type UnionDefiner = RegistryUnion;
+    type RawWriter = StreamWriter<io::Sink>;
+
+    unsafe fn raw_writer(&mut self) -> &mut Self::RawWriter { &mut self.raw }
+
+    fn write_union<T: StrictUnion>(
+        mut self,
+        inner: impl FnOnce(Self::UnionDefiner) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        self.register_sum::<T>();
+        inner(RegistryUnion(self))
+    }
+    fn write_enum<T: StrictEnum>(mut self, _value: T) -> io::Result<Self>
+    where u8: From<T> {
+        self.register_sum::<T>();
+        Ok(self)
+    }
+    fn write_tuple<T: StrictTuple>(
+        mut self,
+        inner: impl FnOnce(Self::TupleWriter) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        self.types.push(format!("{}({})", T::strict_name().unwrap(), T::FIELD_COUNT));
+        inner(RegistryFields(self))
+    }
+    fn write_struct<T: StrictStruct>(
+        mut self,
+        inner: impl FnOnce(Self::StructWriter) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        self.types.push(format!("{}{{{}}}", T::strict_name().unwrap(), T::ALL_FIELDS.join(", ")));
+        inner(RegistryFields(self))
+    }
+    unsafe fn register_primitive(mut self, prim: Primitive) -> Self {
+        self.types.push(prim.to_string());
+        self
+    }
+}
+
+impl WriteTuple for RegistryFields {
+    type Parent = Registry;
+    fn write_field(self, value: &impl StrictEncode) -> io::Result<Self> {
+        value.strict_encode(self.0).map(RegistryFields)
+    }
+    fn complete(self) -> Registry { self.0 }
+}
+
+impl WriteStruct for RegistryFields {
+    type Parent = Registry;
+    fn write_field(self, _name: FieldName, value: &impl StrictEncode) -> io::Result<Self> {
+        value.strict_encode(self.0).map(RegistryFields)
+    }
+    fn complete(self) -> Registry { self.0 }
+}
+
+// The variants are registered once written, thus the definitions are skipped
+impl DefineUnion for RegistryUnion {
+    type Parent = Registry;
+    type TupleDefiner = RegistryVariant;
+    type StructDefiner = RegistryVariant;
+    type UnionWriter = RegistryUnion;
+
+    fn define_unit(self, _name: VariantName) -> Self { self }
+    fn define_tuple(self, _name: VariantName, inner: impl FnOnce(RegistryVariant) -> Self) -> Self {
+        inner(RegistryVariant(self.0))
+    }
+    fn define_struct(
+        self,
+        _name: VariantName,
+        inner: impl FnOnce(RegistryVariant) -> Self,
+    ) -> Self {
+        inner(RegistryVariant(self.0))
+    }
+    fn complete(self) -> RegistryUnion { self }
+}
+
+impl WriteUnion for RegistryUnion {
+    type Parent = Registry;
+    type TupleWriter = RegistryVariant;
+    type StructWriter = RegistryVariant;
+
+    fn write_unit(self, _name: VariantName) -> io::Result<Self> { Ok(self) }
+    fn write_tuple(
+        self,
+        _name: VariantName,
+        inner: impl FnOnce(RegistryVariant) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        inner(RegistryVariant(self.0))
+    }
+    fn write_struct(
+        self,
+        _name: VariantName,
+        inner: impl FnOnce(RegistryVariant) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        inner(RegistryVariant(self.0))
+    }
+    fn complete(self) -> Registry { self.0 }
+}
+
+impl DefineTuple for RegistryVariant {
+    type Parent = RegistryUnion;
+    fn define_field<T: StrictEncode + StrictDumb>(self) -> Self { self }
+    fn complete(self) -> RegistryUnion { RegistryUnion(self.0) }
+}
+
+impl DefineStruct for RegistryVariant {
+    type Parent = RegistryUnion;
+    fn define_field<T: StrictEncode + StrictDumb>(self, _name: FieldName) -> Self { self }
+    fn complete(self) -> RegistryUnion { RegistryUnion(self.0) }
+}
+
+impl WriteTuple for RegistryVariant {
+    type Parent = RegistryUnion;
+    fn write_field(self, value: &impl StrictEncode) -> io::Result<Self> {
+        value.strict_encode(self.0).map(RegistryVariant)
+    }
+    fn complete(self) -> RegistryUnion { RegistryUnion(self.0) }
+}
+
+impl WriteStruct for RegistryVariant {
+    type Parent = RegistryUnion;
+    fn write_field(self, _name: FieldName, value: &impl StrictEncode) -> io::Result<Self> {
+        value.strict_encode(self.0).map(RegistryVariant)
+    }
+    fn complete(self) -> RegistryUnion { RegistryUnion(self.0) }
+}
+
+#[test]
+fn tuple_unit_field() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Marked(u8, (), u16);
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Outer {
+        marked: Marked,
+        unit: (),
+        tail: u8,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = order)]
+    enum Slot {
+        #[strict_type(dumb)]
+        Empty,
+        Marked(u8, (), u16),
+    }
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    enum Side {
+        #[default]
+        Left = 1,
+        Right = 2,
+    }
+
+    impl StrictSerialize for Marked {}
+    impl StrictDeserialize for Marked {}
+    impl StrictSerialize for Outer {}
+    impl StrictDeserialize for Outer {}
+
+    // The unit field is a part of the tuple, while adding nothing to the data
+    assert_eq!(<Marked as StrictTuple>::FIELD_COUNT, 3);
+    let marked = Marked(1, (), 0x0302);
+    let data = marked.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[1, 2, 3]);
+    assert_eq!(Marked::from_strict_serialized::<16>(data).unwrap(), marked);
+
+    let outer = Outer {
+        marked: Marked(1, (), 0x0302),
+        unit: (),
+        tail: 4,
+    };
+    let data = outer.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[1, 2, 3, 4]);
+    assert_eq!(Outer::from_strict_serialized::<16>(data).unwrap(), outer);
+
+    // The unit field is registered exactly once, in its own position
+    assert_eq!(Registry::register(&marked), ["Marked(3)", "U8", "()", "U16"]);
+    assert_eq!(Registry::register(&outer), [
+        "Outer{marked, unit, tail}",
+        "Marked(3)",
+        "U8",
+        "()",
+        "U16",
+        "()",
+        "U8"
+    ]);
+    assert_eq!(Registry::register(&Slot::Marked(1, (), 0x0302)), [
+        "Slot<empty, marked>",
+        "U8",
+        "()",
+        "U16"
+    ]);
+    assert_eq!(Registry::register(&Slot::Empty), ["Slot<empty, marked>"]);
+    assert_eq!(Registry::register(&Side::Right), ["Side<left, right>"]);
+
     Ok(())
 }
 
@@ -73,6 +326,18 @@ fn tuple_generics() -> common::Result {
         <A as TryFrom<u8>>::Error: From<Infallible>,
         B: StrictDumb + StrictEncode + StrictDecode;
 
+    test_encoding_roundtrip::<_, 3>(&Pair(1u8, 0x0302u16), vec![1, 2, 3])?;
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Len(u8);
+    impl From<String> for Len {
+        fn from(s: String) -> Self { Len(s.len() as u8) }
+    }
+
+    test_encoding_roundtrip::<_, 2>(&WhereConstraint(1u8, Len::from(s!("ab"))), vec![1, 2])?;
+
     Ok(())
 }
 
@@ -98,6 +363,30 @@ fn struct_generics() -> common::Result {
         value: &'a V,
     }
 
+    test_encoding_roundtrip::<_, 3>(
+        &Field {
+            tag: 1,
+            value: 0x0302u16,
+        },
+        vec![1, 2, 3],
+    )?;
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Len(u8);
+    impl From<&str> for Len {
+        fn from(s: &str) -> Self { Len(s.len() as u8) }
+    }
+    impl Default for &'static Len {
+        fn default() -> Self { &Len(0) }
+    }
+    let dumb = ComplexField::<Len>::strict_dumb();
+    assert_eq!(dumb, ComplexField {
+        tag: 0,
+        value: &Len(0),
+    });
+
     Ok(())
 }
 
@@ -117,7 +406,7 @@ fn enum_ord() -> common::Result {
     assert_eq!(Variants::Three as u8, 7);
     assert_eq!(u8::from(Variants::Three), 7);
     assert_eq!(Variants::try_from(6), Ok(Variants::Two));
-    assert_eq!(Variants::try_from(3), Err(VariantError(Some(s!("Variants")), 3)));
+    assert_eq!(Variants::try_from(3), Err(VariantError::typed("Variants", 3)));
 
     Ok(())
 }
@@ -137,7 +426,30 @@ fn enum_repr() -> common::Result {
 
     assert_eq!(u8::from(Cls::Three), 3);
     assert_eq!(Cls::try_from(2), Ok(Cls::Two));
-    assert_eq!(Cls::try_from(4), Err(VariantError(Some(s!("Cls")), 4)));
+    assert_eq!(Cls::try_from(4), Err(VariantError::typed("Cls", 4)));
+
+    Ok(())
+}
+
+#[test]
+fn enum_single_variant() -> common::Result {
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    enum Tag {
+        #[strict_type(dumb)]
+        Only,
+    }
+
+    impl StrictDeserialize for Tag {}
+
+    assert_eq!(Tag::ALL_VARIANTS, &[(0, "only")]);
+    test_encoding_roundtrip::<_, 1>(&Tag::Only, vec![0]).unwrap();
+    for tag in 1..=u8::MAX {
+        let data = Confined::try_from(vec![tag]).unwrap();
+        assert!(Tag::from_strict_serialized::<1>(data).is_err(), "tag {tag} is accepted");
+    }
 
     Ok(())
 }
@@ -171,6 +483,38 @@ fn enum_associated() -> common::Result {
     Ok(())
 }
 
+#[test]
+fn enum_const_tags() -> common::Result {
+    const BASE: u8 = 0x80;
+
+    struct Tag;
+    impl Tag {
+        const SECOND: u8 = BASE + 1;
+    }
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = custom)]
+    enum Assoc {
+        #[strict_type(dumb, tag = BASE)]
+        One,
+        #[strict_type(tag = Tag::SECOND)]
+        Two(u8),
+        #[strict_type(tag = { BASE | 0x10 })]
+        Three,
+        #[strict_type(tag = 5)]
+        Four,
+    }
+
+    impl StrictSerialize for Assoc {}
+
+    assert_eq!(Assoc::ALL_VARIANTS, &[(0x80, "one"), (0x81, "two"), (0x90, "three"), (5, "four")]);
+    assert_eq!(Assoc::Two(7).to_strict_serialized::<256>().unwrap().as_slice(), &[0x81, 7]);
+    assert_eq!(Assoc::Three.to_strict_serialized::<256>().unwrap().as_slice(), &[0x90]);
+
+    Ok(())
+}
+
 #[test]
 fn enum_custom_tags() -> common::Result {
     #[allow(dead_code)]
@@ -213,3 +557,405 @@ fn enum_custom_tags() -> common::Result {
 
     Ok(())
 }
+
+#[test]
+fn enum_custom_tags_mixed() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = custom)]
+    enum Mixed {
+        #[strict_type(tag = 0x10, dumb)]
+        Unit,
+        #[strict_type(tag = 0x03)]
+        Tuple(u8, u16),
+        #[strict_type(tag = 0xFF)]
+        Struct { flag: bool, value: u32 },
+        #[strict_type(tag = 0x00)]
+        Empty(),
+        #[strict_type(tag = 0x01)]
+        Single(u8),
+    }
+
+    impl StrictSerialize for Mixed {}
+    impl StrictDeserialize for Mixed {}
+
+    assert_eq!(Mixed::ALL_VARIANTS, &[
+        (0x10, "unit"),
+        (0x03, "tuple"),
+        (0xFF, "struct"),
+        (0x00, "empty"),
+        (0x01, "single")
+    ]);
+
+    // Tags are written as given, independently of the declaration order
+    test_encoding_roundtrip::<_, 16>(&Mixed::Unit, vec![0x10])?;
+    test_encoding_roundtrip::<_, 16>(&Mixed::Tuple(7, 0x0102), vec![0x03, 7, 0x02, 0x01])?;
+    test_encoding_roundtrip::<_, 16>(
+        &Mixed::Struct {
+            flag: true,
+            value: 0x01020304,
+        },
+        vec![0xFF, 1, 0x04, 0x03, 0x02, 0x01],
+    )?;
+    test_encoding_roundtrip::<_, 16>(&Mixed::Empty(), vec![0x00])?;
+    test_encoding_roundtrip::<_, 16>(&Mixed::Single(0xAB), vec![0x01, 0xAB])?;
+
+    // Declaration order indexes are not valid tags
+    let err =
+        Mixed::from_strict_serialized::<{ u16::MAX as usize }>(small_vec![0x02, 0x00]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported value `2` for union `Mixed` encountered during decode operation"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn enum_unknown_tag() -> common::Result {
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = order, unknown = Unknown)]
+    enum Version {
+        #[strict_type(dumb)]
+        V1,
+        V2,
+        Unknown(u8),
+    }
+
+    impl StrictSerialize for Version {}
+    impl StrictDeserialize for Version {}
+
+    assert_eq!(Version::ALL_VARIANTS, &[(0, "v1"), (1, "v2")]);
+    assert_eq!(Version::Unknown(7).variant_name(), "unknown");
+
+    let data = Version::V2.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[1]);
+    assert_eq!(Version::from_strict_serialized::<16>(data).unwrap(), Version::V2);
+
+    let data = Version::Unknown(7).to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[7]);
+    assert_eq!(Version::from_strict_serialized::<16>(data).unwrap(), Version::Unknown(7));
+
+    assert!(Version::Unknown(1).to_strict_serialized::<16>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn union_unknown_variant() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = order)]
+    enum MessageV2 {
+        #[strict_type(dumb)]
+        Ping,
+        Data(u16),
+        Extra {
+            id: u8,
+            value: u32,
+        },
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = order, unknown = Unknown)]
+    enum Message {
+        #[strict_type(dumb)]
+        Ping,
+        Data(u16),
+        Unknown(u8, Vec<u8>),
+    }
+
+    impl StrictSerialize for MessageV2 {}
+    impl StrictSerialize for Message {}
+    impl StrictDeserialize for Message {}
+
+    let data = Message::Data(0x0201).to_strict_serialized::<16>().unwrap();
+    assert_eq!(Message::from_strict_serialized::<16>(data).unwrap(), Message::Data(0x0201));
+
+    // Variant which was added in a newer version of the type
+    let newer = MessageV2::Extra {
+        id: 5,
+        value: 0x04030201,
+    };
+    let data = newer.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[2, 5, 1, 2, 3, 4]);
+    let message = Message::from_strict_serialized::<16>(data.clone()).unwrap();
+    assert_eq!(message, Message::Unknown(2, vec![5, 1, 2, 3, 4]));
+    assert_eq!(message.to_strict_serialized::<16>().unwrap(), data);
+
+    Ok(())
+}
+
+#[test]
+fn struct_extensible() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct RecordV1 {
+        id: u8,
+        value: u16,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, extensible)]
+    struct Record {
+        id: u8,
+        value: u16,
+        #[strict_type(default = 7)]
+        flags: u8,
+        #[strict_type(default = u16::MAX)]
+        extra: u16,
+    }
+
+    impl StrictSerialize for RecordV1 {}
+    impl StrictSerialize for Record {}
+    impl StrictDeserialize for RecordV1 {}
+    impl StrictDeserialize for Record {}
+
+    // Data written by the older version of the structure
+    let old = RecordV1 {
+        id: 1,
+        value: 0x0302,
+    };
+    let data = old.to_strict_serialized::<16>().unwrap();
+    assert_eq!(data.as_slice(), &[1, 2, 3]);
+    let record = Record::from_strict_serialized::<16>(data.clone()).unwrap();
+    assert_eq!(record, Record {
+        id: 1,
+        value: 0x0302,
+        flags: 7,
+        extra: u16::MAX,
+    });
+
+    // Data written by the recent version of the structure
+    let new = Record {
+        id: 1,
+        value: 0x0302,
+        flags: 4,
+        extra: 0x0605,
+    };
+    test_encoding_roundtrip::<_, 16>(&new, vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+    // Only some of the trailing fields are present
+    let record =
+        Record::from_strict_serialized::<16>(Confined::try_from(vec![1, 2, 3, 4]).unwrap())
+            .unwrap();
+    assert_eq!(record.flags, 4);
+    assert_eq!(record.extra, u16::MAX);
+
+    // Fields truncated in the middle are not treated as absent
+    assert_eq!(
+        Record::from_strict_serialized::<16>(Confined::try_from(vec![1, 2, 3, 4, 5]).unwrap())
+            .unwrap_err(),
+        DeserializeError::Decode(DecodeError::UnexpectedEof)
+    );
+
+    // Missing fields without default values are still an error
+    assert!(Record::from_strict_serialized::<16>(Confined::try_from(vec![1, 2]).unwrap()).is_err());
+    // Structures which are not extensible remain strict
+    assert!(
+        RecordV1::from_strict_serialized::<16>(Confined::try_from(vec![1, 2]).unwrap()).is_err()
+    );
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, extensible)]
+    struct Rec {
+        a: u8,
+        #[strict_type(default = 7)]
+        b: u16,
+    }
+
+    impl StrictDeserialize for Rec {}
+
+    let rec = Rec::from_strict_serialized::<16>(Confined::try_from(vec![1]).unwrap()).unwrap();
+    assert_eq!(rec, Rec { a: 1, b: 7 });
+    assert_eq!(
+        Rec::from_strict_serialized::<16>(Confined::try_from(vec![1, 2]).unwrap()).unwrap_err(),
+        DeserializeError::Decode(DecodeError::UnexpectedEof)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn struct_present_if() -> common::Result {
+    const HAS_VALUE: u8 = 0x01;
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Packet {
+        flags: u8,
+        has_extra: bool,
+        #[strict_type(present_if = { flags & HAS_VALUE != 0 })]
+        value: u16,
+        #[strict_type(present_if = has_extra)]
+        extra: TinyBlob,
+        id: u8,
+    }
+
+    impl StrictSerialize for Packet {}
+    impl StrictDeserialize for Packet {}
+
+    fn check(packet: Packet, data: &[u8]) {
+        assert_eq!(packet.to_strict_serialized::<16>().unwrap().as_slice(), data);
+        assert_eq!(
+            Packet::from_strict_serialized::<16>(Confined::try_from(data.to_vec()).unwrap())
+                .unwrap(),
+            packet
+        );
+    }
+
+    // Present fields
+    check(
+        Packet {
+            flags: HAS_VALUE,
+            has_extra: true,
+            value: 0x0302,
+            extra: TinyBlob::try_from(vec![4]).unwrap(),
+            id: 5,
+        },
+        &[1, 1, 2, 3, 1, 4, 5],
+    );
+    // Absent fields are not written and are decoded into default values
+    check(
+        Packet {
+            flags: 0x02,
+            has_extra: false,
+            value: 0,
+            extra: none!(),
+            id: 5,
+        },
+        &[2, 0, 5],
+    );
+    check(
+        Packet {
+            flags: 0,
+            has_extra: true,
+            value: 0,
+            extra: TinyBlob::try_from(vec![4]).unwrap(),
+            id: 5,
+        },
+        &[0, 1, 1, 4, 5],
+    );
+
+    // Values of absent fields must be the default ones, since they are not
+    // written
+    let packet = Packet {
+        value: 0x0302,
+        id: 5,
+        ..default!()
+    };
+    let err = packet.strict_encode(StrictWriter::in_memory::<16>()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(err.to_string(), "absent field 'value' has a non-default value");
+
+    Ok(())
+}
+
+#[test]
+fn struct_extensions() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Message {
+        id: u8,
+        value: u16,
+        #[strict_type(extensions)]
+        extensions: Confined<BTreeMap<u16, Vec<u8>>, 0, 8>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Envelope {
+        version: u8,
+        message: Message,
+    }
+
+    impl StrictSerialize for Message {}
+    impl StrictDeserialize for Message {}
+
+    assert_eq!(<Message as strict_encoding::StrictStruct>::ALL_FIELDS, &["id", "value"]);
+    const _: () = assert!(<Message as strict_encoding::StrictType>::HAS_EXTENSIONS);
+    const _: () = assert!(<Envelope as strict_encoding::StrictType>::HAS_EXTENSIONS);
+    const _: () = assert!(<Option<Box<Message>> as strict_encoding::StrictType>::HAS_EXTENSIONS);
+
+    let mut msg = Message {
+        id: 1,
+        value: 0x0302,
+        extensions: none!(),
+    };
+    test_encoding_roundtrip::<_, 16>(&msg, vec![1, 2, 3]).unwrap();
+
+    msg.extensions = Confined::try_from(bmap! { 2u16 => vec![0xAA], 1u16 => vec![] }).unwrap();
+    test_encoding_roundtrip::<_, 16>(&msg, vec![1, 2, 3, 1, 0, 0, 0, 2, 0, 1, 0, 0xAA]).unwrap();
+    test_encoding_roundtrip::<_, 16>(
+        &Envelope {
+            version: 4,
+            message: msg.clone(),
+        },
+        vec![4, 1, 2, 3, 1, 0, 0, 0, 2, 0, 1, 0, 0xAA],
+    )
+    .unwrap();
+
+    let decode =
+        |data: Vec<u8>| Message::from_strict_serialized::<16>(Confined::try_from(data).unwrap());
+    // Records must be sorted by their type
+    assert!(decode(vec![1, 2, 3, 2, 0, 1, 0, 0xAA, 1, 0, 0, 0]).is_err());
+    assert!(decode(vec![1, 2, 3, 1, 0, 0, 0, 1, 0, 0, 0]).is_err());
+    // Truncated records are not accepted
+    assert!(decode(vec![1, 2, 3, 2]).is_err());
+    assert!(decode(vec![1, 2, 3, 2, 0, 1, 0]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn recursive_tree() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Node {
+        value: u8,
+        left: Option<Box<Node>>,
+        right: Option<Box<Node>>,
+    }
+
+    let leaf = |value| {
+        Some(Box::new(Node {
+            value,
+            left: None,
+            right: None,
+        }))
+    };
+
+    test_encoding_roundtrip::<_, 16>(&Node::default(), vec![0, 0, 0]).unwrap();
+    // Each subtree takes a single tag byte, followed by the node itself
+    let tree = Node {
+        value: 1,
+        left: Some(Box::new(Node {
+            value: 2,
+            left: leaf(4),
+            right: None,
+        })),
+        right: leaf(3),
+    };
+    test_encoding_roundtrip::<_, 16>(&tree, vec![1, 1, 2, 1, 4, 0, 0, 0, 1, 3, 0, 0]).unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn bigint_roundtrip() {
+    test_bigint_roundtrip::<u256>().unwrap();
+    test_bigint_roundtrip::<i256>().unwrap();
+    test_bigint_roundtrip::<u512>().unwrap();
+    test_bigint_roundtrip::<i512>().unwrap();
+    test_bigint_roundtrip::<u1024>().unwrap();
+    test_bigint_roundtrip::<i1024>().unwrap();
+}
@@ -87,6 +87,61 @@ fn enum_explicit() -> common::Result {
     Ok(())
 }
 
+#[test]
+fn enum_explicit_fields() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb)]
+    #[strict_type(lib = TEST_LIB)]
+    enum Variants {
+        One,
+        #[strict_type(dumb)]
+        Two(u8, Option<u16>),
+        Three {
+            field: u32,
+        },
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb)]
+    #[strict_type(lib = TEST_LIB)]
+    enum Named {
+        One,
+        #[strict_type(dumb)]
+        Two {
+            first: u8,
+            second: Option<u16>,
+        },
+    }
+
+    assert_eq!(Variants::strict_dumb(), Variants::Two(0, None));
+    assert_ne!(Variants::strict_dumb(), Variants::One);
+    assert_ne!(Variants::strict_dumb(), Variants::Three { field: 0 });
+    assert_eq!(Named::strict_dumb(), Named::Two {
+        first: 0,
+        second: None
+    });
+    assert_ne!(Named::strict_dumb(), Named::One);
+
+    Ok(())
+}
+
+#[test]
+fn enum_variant_value() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb)]
+    #[strict_type(lib = TEST_LIB)]
+    enum Variants {
+        One,
+        #[strict_type(dumb = { Variants::Two(1, Some(2)) })]
+        Two(u8, Option<u16>),
+    }
+
+    assert_eq!(Variants::strict_dumb(), Variants::Two(1, Some(2)));
+    assert_ne!(Variants::strict_dumb(), Variants::One);
+
+    Ok(())
+}
+
 #[test]
 fn dumb_wrapper_container() -> common::Result {
     #[derive(Clone, PartialEq, Eq, Debug)]
@@ -172,5 +227,6 @@ fn dumb_ultra_complex() -> common::Result {
         Confined<Vec<T>, 1, { u8::MAX as usize }>,
     );
 
+    assert_eq!(NamedFields::<u8>::strict_dumb(), NamedFields(Confined::with(0)));
     Ok(())
 }
@@ -0,0 +1,291 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate strict_encoding_derive;
+
+use std::collections::HashSet;
+
+const TEST_LIB: &str = "TestLib";
+
+#[allow(dead_code)]
+#[derive(Clone, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, strict_id)]
+struct Point {
+    x: u16,
+    y: u16,
+}
+
+mod reordered {
+    use super::TEST_LIB;
+
+    #[allow(dead_code)]
+    #[derive(Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, strict_id)]
+    pub struct Point {
+        pub y: u16,
+        pub x: u16,
+    }
+}
+
+mod retyped {
+    use super::TEST_LIB;
+
+    #[allow(dead_code)]
+    #[derive(Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, strict_id)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u16,
+    }
+}
+
+mod renamed {
+    use super::TEST_LIB;
+
+    #[allow(dead_code)]
+    #[derive(Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, strict_id)]
+    pub struct Point {
+        #[strict_type(rename = "abscissa")]
+        pub x: u16,
+        pub y: u16,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, rename = "Point", strict_id)]
+    pub struct RustPoint {
+        pub x: u16,
+        pub y: u16,
+    }
+}
+
+mod spelled {
+    use super::TEST_LIB;
+
+    #[allow(dead_code)]
+    #[derive(Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, strict_id)]
+    pub struct Point {
+        pub x: ::core::primitive::u16,
+        pub y: u16,
+    }
+}
+
+mod other_lib {
+    #[allow(dead_code)]
+    #[derive(Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = "OtherLib", strict_id)]
+    pub struct Point {
+        pub x: u16,
+        pub y: u16,
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, strict_id)]
+struct Pair(u16, u16);
+
+#[allow(dead_code)]
+#[derive(Clone, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, strict_id)]
+struct Rev(u16, u8);
+
+#[allow(dead_code)]
+#[derive(Clone, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, strict_id)]
+struct Swapped(u8, u16);
+
+#[derive(Copy, Clone, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8, strict_id)]
+#[repr(u8)]
+enum Color {
+    #[default]
+    Red = 1,
+    Green = 2,
+}
+
+mod retagged {
+    use super::TEST_LIB;
+
+    #[derive(Copy, Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8, strict_id)]
+    #[repr(u8)]
+    pub enum Color {
+        #[default]
+        Red = 1,
+        Green = 3,
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, tags = order, strict_id)]
+enum Shape {
+    #[default]
+    Empty,
+    Circle(u16),
+    Rect {
+        width: u16,
+        height: u16,
+    },
+}
+
+mod reshaped {
+    use super::TEST_LIB;
+
+    #[allow(dead_code)]
+    #[derive(Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, tags = order, strict_id)]
+    pub enum Shape {
+        #[default]
+        Empty,
+        Circle(u32),
+        Rect {
+            width: u16,
+            height: u16,
+        },
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, strict_id)]
+struct Wrapper<T: Default> {
+    inner: T,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, strict_id)]
+struct Line {
+    from: Point,
+    to: Point,
+}
+
+mod relined {
+    use super::{retyped, TEST_LIB};
+
+    #[allow(dead_code)]
+    #[derive(Clone, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, strict_id)]
+    pub struct Line {
+        pub from: retyped::Point,
+        pub to: retyped::Point,
+    }
+}
+
+const POINT_ID: u64 = Point::STRICT_ID;
+
+#[test]
+fn strict_id_const() {
+    assert_eq!(Point::strict_type_id(), POINT_ID);
+    assert_ne!(Wrapper::<u8>::STRICT_ID, Wrapper::<u16>::STRICT_ID);
+    assert_ne!(Wrapper::<u8>::WIRE_FINGERPRINT, Wrapper::<u16>::WIRE_FINGERPRINT);
+    assert_eq!(Wrapper::<Point>::STRICT_ID, Wrapper::<renamed::RustPoint>::STRICT_ID);
+}
+
+#[test]
+fn strict_id_type_spelling() {
+    // The ids come from the field types, not from their paths in the source
+    assert_eq!(spelled::Point::STRICT_ID, Point::STRICT_ID);
+    assert_eq!(spelled::Point::WIRE_FINGERPRINT, Point::WIRE_FINGERPRINT);
+}
+
+#[test]
+fn strict_id_nested() {
+    assert_ne!(relined::Line::STRICT_ID, Line::STRICT_ID);
+    assert_ne!(relined::Line::WIRE_FINGERPRINT, Line::WIRE_FINGERPRINT);
+    // Renaming the fields of the nested type keeps the outer wire format
+    assert_eq!(Wrapper::<renamed::Point>::WIRE_FINGERPRINT, Wrapper::<Point>::WIRE_FINGERPRINT);
+    assert_ne!(Wrapper::<renamed::Point>::STRICT_ID, Wrapper::<Point>::STRICT_ID);
+}
+
+#[test]
+fn strict_id_renamed_rust_type() {
+    assert_eq!(renamed::RustPoint::STRICT_ID, Point::STRICT_ID);
+}
+
+#[test]
+fn strict_id_distinct() {
+    let ids = [
+        Point::STRICT_ID,
+        reordered::Point::STRICT_ID,
+        retyped::Point::STRICT_ID,
+        renamed::Point::STRICT_ID,
+        other_lib::Point::STRICT_ID,
+        Pair::STRICT_ID,
+        Rev::STRICT_ID,
+        Color::STRICT_ID,
+        retagged::Color::STRICT_ID,
+        Shape::STRICT_ID,
+        reshaped::Shape::STRICT_ID,
+        Wrapper::<u8>::STRICT_ID,
+    ];
+    let unique = ids.iter().collect::<HashSet<_>>();
+    assert_eq!(unique.len(), ids.len());
+}
+
+#[test]
+fn wire_fingerprint_renamed() {
+    assert_eq!(renamed::Point::WIRE_FINGERPRINT, Point::WIRE_FINGERPRINT);
+    assert_eq!(renamed::RustPoint::WIRE_FINGERPRINT, Point::WIRE_FINGERPRINT);
+    assert_eq!(other_lib::Point::WIRE_FINGERPRINT, Point::WIRE_FINGERPRINT);
+    // Named and unnamed fields of the same types are encoded in the same way
+    assert_eq!(Pair::WIRE_FINGERPRINT, Point::WIRE_FINGERPRINT);
+    // Swapping fields of the same type doesn't change the wire format
+    assert_eq!(reordered::Point::WIRE_FINGERPRINT, Point::WIRE_FINGERPRINT);
+}
+
+#[test]
+fn wire_fingerprint_distinct() {
+    let fingerprints = [
+        Point::WIRE_FINGERPRINT,
+        retyped::Point::WIRE_FINGERPRINT,
+        Rev::WIRE_FINGERPRINT,
+        Swapped::WIRE_FINGERPRINT,
+        Color::WIRE_FINGERPRINT,
+        retagged::Color::WIRE_FINGERPRINT,
+        Shape::WIRE_FINGERPRINT,
+        reshaped::Shape::WIRE_FINGERPRINT,
+    ];
+    let unique = fingerprints.iter().collect::<HashSet<_>>();
+    assert_eq!(unique.len(), fingerprints.len());
+}
@@ -30,7 +30,7 @@ extern crate strict_encoding_derive;
 mod common;
 
 use strict_encoding::{
-    tn, StrictDeserialize, StrictSerialize, StrictStruct, StrictSum, StrictType,
+    tn, StrictDeserialize, StrictSerialize, StrictStruct, StrictSum, StrictTuple, StrictType,
 };
 
 const TEST_LIB: &str = "TestLib";
@@ -60,6 +60,28 @@ fn rename_type() -> common::Result {
     Ok(())
 }
 
+#[test]
+fn rename_newtype() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "MyName")]
+    struct Foo(u64);
+
+    impl StrictSerialize for Foo {}
+    impl StrictDeserialize for Foo {}
+
+    assert_eq!(Foo::strict_name().unwrap(), tn!("MyName"));
+    assert_eq!(Foo::FIELD_COUNT, 1);
+
+    // Newtypes are encoded in the same way as their inner values
+    let foo = Foo(0x0102030405060708);
+    let data = foo.to_strict_serialized::<8>().unwrap();
+    assert_eq!(data.as_slice(), &0x0102030405060708u64.to_le_bytes());
+    assert_eq!(Foo::from_strict_serialized::<8>(data).unwrap(), foo);
+
+    Ok(())
+}
+
 #[test]
 fn fields() -> common::Result {
     #[derive(Clone, PartialEq, Eq, Debug, Default)]
@@ -79,6 +101,7 @@ fn variants() -> common::Result {
     #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
     #[derive(StrictType)]
     #[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
     enum Enum {
         #[default]
         MustCamelize,
@@ -145,11 +168,30 @@ fn skip_field() -> common::Result {
     Ok(())
 }
 
+#[test]
+fn skip_tuple_field() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Tuple(u8, #[strict_type(skip)] u8, u16);
+    impl StrictSerialize for Tuple {}
+    impl StrictDeserialize for Tuple {}
+
+    assert_eq!(Tuple::FIELD_COUNT, 2);
+
+    let val = Tuple(2, 3, 4);
+    assert_eq!(val.to_strict_serialized::<{ usize::MAX }>().unwrap().as_slice(), &[2, 4, 0]);
+    assert_eq!(Tuple::from_strict_serialized(small_vec![2, 4, 0]).unwrap(), Tuple(2, 0, 4));
+
+    Ok(())
+}
+
 #[test]
 fn rename_variant() -> common::Result {
     #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
     #[derive(StrictType)]
     #[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
     enum Enum {
         #[default]
         MustCamelize,
@@ -162,3 +204,50 @@ fn rename_variant() -> common::Result {
 
     Ok(())
 }
+
+#[test]
+fn field_variant_count() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Struct {
+        first: u8,
+        #[strict_type(skip)]
+        cache: u8,
+        second: u16,
+        third: u32,
+    }
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType)]
+    #[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+    #[repr(u8)]
+    enum Enum {
+        #[default]
+        One,
+        Two,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType)]
+    #[strict_type(lib = TEST_LIB, tags = order, unknown = Unknown)]
+    enum Union {
+        #[strict_type(dumb)]
+        Unit,
+        Tuple(u8),
+        Struct {
+            field: u8,
+        },
+        Unknown(u8, Vec<u8>),
+    }
+
+    const DISPATCH: [u8; Struct::FIELD_COUNT as usize] = [0; 3];
+    assert_eq!(DISPATCH.len(), Struct::ALL_FIELDS.len());
+    assert_eq!(Struct::FIELD_COUNT, 3);
+    assert_eq!(Enum::VARIANT_COUNT, 2);
+    // Variant for unknown tags is not a part of the type definition
+    assert_eq!(Union::VARIANT_COUNT, 3);
+
+    Ok(())
+}
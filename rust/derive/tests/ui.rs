@@ -0,0 +1,49 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/encode_with.rs");
+    t.compile_fail("tests/ui/encode_with_variant.rs");
+    t.compile_fail("tests/ui/decode_with_variant.rs");
+    t.pass("tests/ui/tag_expr.rs");
+    t.compile_fail("tests/ui/tag_const_wide.rs");
+    t.pass("tests/ui/variant_dumb.rs");
+    t.compile_fail("tests/ui/variant_dumb_both.rs");
+    t.compile_fail("tests/ui/repr_missing.rs");
+    t.pass("tests/ui/dumb_skip_default.rs");
+    t.compile_fail("tests/ui/default_not_trailing.rs");
+    t.compile_fail("tests/ui/duplicate_tag.rs");
+    t.compile_fail("tests/ui/duplicate_tag_const.rs");
+    t.compile_fail("tests/ui/tag_literal_wide.rs");
+    t.compile_fail("tests/ui/extensions_not_last.rs");
+    t.compile_fail("tests/ui/extensions_nested.rs");
+    t.compile_fail("tests/ui/extensions_option.rs");
+    t.compile_fail("tests/ui/extensions_list.rs");
+    t.pass("tests/ui/crate_reexport.rs");
+    t.pass("tests/ui/encode_only.rs");
+    t.compile_fail("tests/ui/encode_only_decode.rs");
+    t.compile_fail("tests/ui/present_if_variant.rs");
+    t.pass("tests/ui/repr_dumb_default.rs");
+    t.compile_fail("tests/ui/union_dumb_missing.rs");
+    t.compile_fail("tests/ui/unknown_unread.rs");
+}
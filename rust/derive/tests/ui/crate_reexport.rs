@@ -0,0 +1,63 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+mod deps {
+    pub mod strict {
+        pub use strict_encoding::*;
+    }
+}
+
+use deps::strict as se;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, crate = deps::strict, rename = "Renamed", strict_id)]
+struct Named {
+    #[strict_type(rename = "value")]
+    a: u8,
+    b: Option<u16>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, crate = se)]
+struct Tuple(u8, Named);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8, crate = self::deps::strict)]
+#[repr(u8)]
+enum Flag {
+    #[default]
+    #[strict_type(rename = "off")]
+    Off = 0,
+    On = 1,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = order, crate = crate::deps::strict, rename = "Choice")]
+enum Union {
+    #[strict_type(dumb)]
+    Unit,
+    Tuple(u8, Flag),
+    Struct { tuple: Tuple },
+}
+
+impl se::StrictSerialize for Union {}
+impl se::StrictDeserialize for Union {}
+
+fn main() {
+    use se::{StrictDeserialize, StrictSerialize, StrictType};
+
+    assert_eq!(Named::strict_name().unwrap().to_string(), "Renamed");
+    assert_eq!(Union::strict_name().unwrap().to_string(), "Choice");
+    let _ = Named::STRICT_ID;
+
+    let value = Union::Struct { tuple: Tuple(1, Named { a: 2, b: Some(3) }) };
+    let data = value.to_strict_serialized::<64>().unwrap();
+    assert_eq!(Union::from_strict_serialized::<64>(data).unwrap(), value);
+    assert_eq!(u8::from(Flag::On), 1);
+}
@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+fn from_u8(val: u8) -> Result<u16, strict_encoding::DecodeError> { Ok(val as u16) }
+
+#[derive(Clone, Debug)]
+#[derive(StrictDumb, StrictType, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = order, dumb = Amount::Value(u16::MAX))]
+enum Amount {
+    Value(#[strict_type(decode_with = from_u8)] u16),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, extensible)]
+struct Record {
+    id: u8,
+    #[strict_type(default = 0)]
+    flags: u8,
+    value: u16,
+}
+
+fn main() {}
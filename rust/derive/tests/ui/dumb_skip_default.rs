@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+// Implements `Default`, but not `StrictDumb`
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+struct Cache(Vec<u8>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb)]
+#[strict_type(lib = TEST_LIB)]
+struct Record {
+    id: u32,
+    #[strict_type(skip)]
+    cache: Cache,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb)]
+#[strict_type(lib = TEST_LIB)]
+struct Tuple(u8, #[strict_type(skip)] Cache);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb)]
+#[strict_type(lib = TEST_LIB)]
+enum Message {
+    Ping,
+    #[strict_type(dumb)]
+    Data {
+        id: u16,
+        #[strict_type(skip)]
+        cache: Cache,
+    },
+}
+
+fn main() {
+    use strict_encoding::StrictDumb;
+
+    assert_eq!(Record::strict_dumb(), Record { id: 0, cache: Cache::default() });
+    assert_eq!(Tuple::strict_dumb(), Tuple(0, Cache::default()));
+    assert_eq!(Message::strict_dumb(), Message::Data { id: 0, cache: Cache::default() });
+}
@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+const DATA: u8 = 3;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType)]
+#[strict_type(lib = TEST_LIB, tags = custom)]
+enum Message {
+    #[strict_type(tag = 1, dumb)]
+    Ping,
+    #[strict_type(tag = DATA)]
+    Data(u8),
+    #[strict_type(tag = 3)]
+    Extra(u16),
+}
+
+fn main() {}
@@ -0,0 +1,96 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+use amplify::confinement::{Confined, TinyBlob, U64};
+use strict_encoding::StrictSerialize;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictType, StrictEncode)]
+#[strict_type(lib = TEST_LIB, tags = order, encode_only)]
+enum Msg<'a> {
+    Ping,
+    Data(&'a TinyBlob),
+    Text { text: &'a str, len: u8 },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictType, StrictEncode)]
+#[strict_type(lib = TEST_LIB, encode_only)]
+struct Envelope<'a> {
+    id: u16,
+    msg: Msg<'a>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictType, StrictEncode)]
+#[strict_type(lib = TEST_LIB, encode_only)]
+struct Batch<'a>(u8, &'a Envelope<'a>);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictType, StrictEncode)]
+#[strict_type(lib = TEST_LIB, encode_only)]
+struct Header<'a> {
+    flags: u8,
+    #[strict_type(present_if = { flags & 1 != 0 })]
+    blob: &'a TinyBlob,
+}
+
+impl StrictSerialize for Envelope<'_> {}
+impl StrictSerialize for Batch<'_> {}
+impl StrictSerialize for Header<'_> {}
+
+// The owned counterpart, which is encoded in the same way
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = order)]
+enum OwnedMsg {
+    #[strict_type(dumb)]
+    Ping,
+    Data(TinyBlob),
+    Text { text: Confined<String, 0, U64>, len: u8 },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB)]
+struct OwnedEnvelope {
+    id: u16,
+    msg: OwnedMsg,
+}
+
+impl StrictSerialize for OwnedEnvelope {}
+
+fn main() {
+    let blob = TinyBlob::try_from(vec![1, 2]).unwrap();
+    let envelope = Envelope { id: 0x0102, msg: Msg::Data(&blob) };
+    let owned = OwnedEnvelope { id: 0x0102, msg: OwnedMsg::Data(blob.clone()) };
+    assert_eq!(
+        envelope.to_strict_serialized::<16>().unwrap(),
+        owned.to_strict_serialized::<16>().unwrap()
+    );
+    assert_eq!(envelope.to_strict_serialized::<16>().unwrap().release(), [2, 1, 1, 2, 1, 2]);
+
+    let envelope = Envelope { id: 4, msg: Msg::Text { text: "hi", len: 2 } };
+    let text = Confined::try_from("hi".to_owned()).unwrap();
+    let owned = OwnedEnvelope { id: 4, msg: OwnedMsg::Text { text, len: 2 } };
+    assert_eq!(
+        envelope.to_strict_serialized::<16>().unwrap(),
+        owned.to_strict_serialized::<16>().unwrap()
+    );
+
+    let envelope = Envelope { id: 3, msg: Msg::Ping };
+    assert_eq!(envelope.to_strict_serialized::<16>().unwrap().release(), [3, 0, 0]);
+
+    let batch = Batch(7, &envelope);
+    assert_eq!(batch.to_strict_serialized::<16>().unwrap().release(), [7, 3, 0, 0]);
+
+    let header = Header { flags: 1, blob: &blob };
+    assert_eq!(header.to_strict_serialized::<16>().unwrap().release(), [1, 2, 1, 2]);
+    let header = Header { flags: 2, blob: &blob };
+    assert!(header.to_strict_serialized::<16>().is_err());
+    let empty = TinyBlob::new();
+    let header = Header { flags: 2, blob: &empty };
+    assert_eq!(header.to_strict_serialized::<16>().unwrap().release(), [2]);
+}
@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, encode_only)]
+struct Borrowed<'a> {
+    text: &'a str,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+use strict_encoding::DecodeError;
+
+const TEST_LIB: &str = "TestLib";
+
+fn from_bytes(val: [u8; 4]) -> Result<String, DecodeError> {
+    String::from_utf8(val.to_vec()).map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+}
+
+fn to_array(val: &String) -> [u8; 4] {
+    let mut data = [0u8; 4];
+    data.copy_from_slice(&val.as_bytes()[..4]);
+    data
+}
+
+#[derive(Clone, Default, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB)]
+struct Ticker {
+    #[strict_type(encode_with = to_array, decode_with = from_bytes)]
+    code: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+fn to_u8(val: &u16) -> u8 { *val as u8 }
+
+#[derive(Clone, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = order, dumb = Amount::Value(u16::MAX))]
+enum Amount {
+    Value(#[strict_type(encode_with = to_u8)] u16),
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+use std::collections::BTreeMap;
+
+use amplify::confinement::Confined;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB)]
+struct Message {
+    id: u8,
+    #[strict_type(extensions)]
+    extensions: Confined<BTreeMap<u16, Vec<u8>>, 0, 8>,
+}
+
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB)]
+struct Env {
+    msg: Option<Message>,
+    version: u8,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = order)]
+enum Packet {
+    #[strict_type(dumb)]
+    Empty,
+    Data {
+        flags: u8,
+        #[strict_type(present_if = { flags & 1 != 0 })]
+        value: u16,
+    },
+}
+
+fn main() {}
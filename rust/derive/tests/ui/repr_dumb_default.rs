@@ -0,0 +1,32 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+use strict_encoding::StrictDumb;
+
+const TEST_LIB: &str = "TestLib";
+
+// No `Default` and no variant marked with `#[strict_type(dumb)]`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+#[repr(u8)]
+enum Flag {
+    Off = 0,
+    On = 1,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+#[repr(u8)]
+enum Level {
+    Low = 1,
+    #[strict_type(dumb)]
+    Medium = 2,
+    High = 3,
+}
+
+fn main() {
+    assert_eq!(Flag::strict_dumb(), Flag::Off);
+    assert_eq!(Level::strict_dumb(), Level::Medium);
+}
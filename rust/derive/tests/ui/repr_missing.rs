@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType)]
+#[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8)]
+enum Flag {
+    #[default]
+    Off = 0,
+    On = 1,
+}
+
+fn main() {}
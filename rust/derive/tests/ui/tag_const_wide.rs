@@ -0,0 +1,23 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+struct Tags;
+
+impl Tags {
+    const FIRST: u8 = 0x10;
+    const SECOND: u16 = 0x120;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = custom)]
+enum Message {
+    #[strict_type(dumb, tag = Tags::FIRST)]
+    Ping,
+    #[strict_type(tag = Tags::SECOND)]
+    Pong(u8),
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+use strict_encoding::{StrictSerialize, StrictSum};
+
+const TEST_LIB: &str = "TestLib";
+const BASE_TAG: u8 = 0x10;
+
+struct Tags;
+
+impl Tags {
+    const FIRST: u8 = BASE_TAG;
+    const SECOND: u8 = 0x20;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = custom)]
+enum Message {
+    #[strict_type(dumb, tag = Tags::FIRST)]
+    Ping,
+    #[strict_type(tag = Tags::SECOND)]
+    Pong(u8),
+    #[strict_type(tag = { BASE_TAG + 2 })]
+    Data { len: u16 },
+    #[strict_type(tag = 3)]
+    Close,
+    #[strict_type(tag = Self::RESET)]
+    Reset,
+}
+
+impl Message {
+    const RESET: u8 = 0x30;
+}
+
+impl StrictSerialize for Message {}
+
+fn main() {
+    assert_eq!(Message::ALL_VARIANTS, &[(0x10, "ping"), (0x20, "pong"), (0x12, "data"), (3, "close"), (0x30, "reset")]);
+    assert_eq!(Message::Pong(7).to_strict_serialized::<4>().unwrap().as_slice(), &[0x20, 7]);
+    assert_eq!(Message::Data { len: 1 }.to_strict_serialized::<4>().unwrap().as_slice(), &[
+        0x12, 1, 0
+    ]);
+    assert_eq!(Message::Reset.to_strict_serialized::<4>().unwrap().as_slice(), &[0x30]);
+}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType)]
+#[strict_type(lib = TEST_LIB, tags = custom)]
+enum Message {
+    #[strict_type(tag = 1, dumb)]
+    Ping,
+    #[strict_type(tag = 300)]
+    Data(u8),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+// Unions with fields must still mark their dumb variant
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb)]
+#[strict_type(lib = TEST_LIB)]
+enum Message {
+    Ping,
+    Data(u16),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb, StrictType, StrictDecode)]
+#[strict_type(lib = TEST_LIB, tags = order, unknown = Unknown)]
+enum Message {
+    #[strict_type(dumb)]
+    Ping,
+    Data(u16),
+    Unknown(u8),
+}
+
+fn main() {}
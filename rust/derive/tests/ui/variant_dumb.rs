@@ -0,0 +1,38 @@
+// Caused by an imperfection of rust compiler in parsing proc macro args
+#![allow(unused_braces)]
+
+use strict_encoding::StrictDumb;
+
+const TEST_LIB: &str = "TestLib";
+
+// Doesn't implement `StrictDumb`, so the derived dumb value of a variant can't
+// construct it
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Payload(u8);
+
+impl Payload {
+    const fn empty() -> Self { Payload(0) }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb)]
+#[strict_type(lib = TEST_LIB)]
+enum Message {
+    Ping,
+    #[strict_type(dumb = { Self::Data(Payload::empty(), 1) })]
+    Data(Payload, u16),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb)]
+#[strict_type(lib = TEST_LIB)]
+enum Record {
+    #[strict_type(dumb = { Record::Named { payload: Payload(7) } })]
+    Named { payload: Payload },
+    Other,
+}
+
+fn main() {
+    assert_eq!(Message::strict_dumb(), Message::Data(Payload(0), 1));
+    assert_eq!(Record::strict_dumb(), Record::Named { payload: Payload(7) });
+}
@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate strict_encoding_derive;
+
+const TEST_LIB: &str = "TestLib";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(StrictDumb)]
+#[strict_type(lib = TEST_LIB)]
+enum Message {
+    Ping,
+    #[strict_type(dumb, dumb = { Self::Data(1) })]
+    Data(u16),
+}
+
+fn main() {}
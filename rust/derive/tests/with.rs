@@ -0,0 +1,96 @@
+// Derivation macro library for strict encoding.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate amplify;
+#[macro_use]
+extern crate strict_encoding_derive;
+
+mod common;
+
+use amplify::confinement::U16;
+use strict_encoding::{DecodeError, DeserializeError, StrictDeserialize, StrictSerialize};
+use strict_encoding_test::test_encoding_roundtrip;
+
+const TEST_LIB: &str = "TestLib";
+
+fn encode_compact(val: &u64) -> u32 { *val as u32 }
+
+fn decode_compact(val: u32) -> Result<u64, DecodeError> { Ok(val as u64) }
+
+fn decode_nonzero(val: u8) -> Result<u8, DecodeError> {
+    if val == 0 {
+        return Err(DecodeError::DataIntegrityError(s!("zero value")));
+    }
+    Ok(val)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB)]
+struct Compact {
+    #[strict_type(encode_with = encode_compact, decode_with = decode_compact)]
+    amount: u64,
+    #[strict_type(decode_with = decode_nonzero)]
+    count: u8,
+}
+
+impl StrictSerialize for Compact {}
+impl StrictDeserialize for Compact {}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = TEST_LIB)]
+struct CompactTuple(
+    u8,
+    #[strict_type(encode_with = encode_compact, decode_with = decode_compact)] u64,
+);
+
+#[test]
+fn encode_with_struct() -> common::Result {
+    let val = Compact {
+        amount: 0x0102,
+        count: 3,
+    };
+    test_encoding_roundtrip::<_, 16>(&val, vec![0x02, 0x01, 0, 0, 3])?;
+    Ok(())
+}
+
+#[test]
+fn encode_with_tuple() -> common::Result {
+    let val = CompactTuple(7, 0x0102);
+    test_encoding_roundtrip::<_, 16>(&val, vec![7, 0x02, 0x01, 0, 0])?;
+    Ok(())
+}
+
+#[test]
+fn decode_with_error() {
+    let err = Compact::from_strict_serialized::<U16>(small_vec![0, 0, 0, 0, 0]).unwrap_err();
+    assert_eq!(err, DeserializeError::Decode(DecodeError::DataIntegrityError(s!("zero value"))));
+
+    let val = Compact::from_strict_serialized::<U16>(small_vec![2, 0, 0, 0, 5]).unwrap();
+    assert_eq!(val, Compact {
+        amount: 2,
+        count: 5
+    });
+    let err = Compact::from_strict_serialized::<U16>(small_vec![2, 0, 0, 0, 0]).unwrap_err();
+    assert_eq!(err, DeserializeError::Decode(DecodeError::DataIntegrityError(s!("zero value"))));
+}
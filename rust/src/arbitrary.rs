@@ -0,0 +1,228 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of arbitrary values for property-based testing and fuzzing,
+//! which respect the constraints of strict types (confined lengths, restricted
+//! charsets, enum variants).
+//!
+//! Derive the trait for own types with `#[strict_type(arbitrary)]` attribute,
+//! which also derives [`proptest::arbitrary::Arbitrary`].
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::RangeInclusive;
+
+use amplify::ascii::AsciiString;
+use amplify::confinement::Confined;
+use amplify::num::{u1024, u24, u256, u40, u48, u512, u56};
+use proptest::collection;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::{RString, RestrictedCharSet};
+
+/// Maximal number of items generated for confined collections and strings on
+/// top of their minimal length, keeping the generated values small.
+pub const ARBITRARY_EXTRA_LEN: usize = 16;
+
+/// Strict types which are able to generate arbitrary values satisfying all of
+/// their constraints.
+pub trait StrictArbitrary: Sized + Debug + 'static {
+    /// Constructs strategy generating arbitrary values of the type.
+    fn strict_arbitrary() -> BoxedStrategy<Self>;
+}
+
+/// Number of candidate items drawn per each item of a set or a map, so the
+/// duplicates rarely leave less unique items than required.
+const UNIQUE_OVERSAMPLING: usize = 4;
+
+fn len_range<const MIN_LEN: usize, const MAX_LEN: usize>() -> RangeInclusive<usize> {
+    MIN_LEN..=MAX_LEN.min(MIN_LEN.saturating_add(ARBITRARY_EXTRA_LEN))
+}
+
+/// Generates collections of `len` unique items, which are taken from a larger
+/// number of candidates instead of rejecting the collections which became too
+/// small because of duplicates.
+fn unique_items<T: Debug, C: Debug + Extend<T> + Default>(
+    item: BoxedStrategy<T>,
+    len: usize,
+    count: fn(&C) -> usize,
+) -> impl Strategy<Value = C> {
+    collection::vec(item, len.saturating_mul(UNIQUE_OVERSAMPLING)).prop_map(move |items| {
+        let mut col = C::default();
+        for item in items {
+            if count(&col) >= len {
+                break;
+            }
+            col.extend([item]);
+        }
+        col
+    })
+}
+
+fn charset<C: RestrictedCharSet>() -> Vec<u8> {
+    (0u8..0x80).filter(|byte| C::try_from(*byte).is_ok()).collect()
+}
+
+macro_rules! arbitrary_any {
+    ($($ty:ty),+) => { $(
+        impl StrictArbitrary for $ty {
+            fn strict_arbitrary() -> BoxedStrategy<Self> { any::<$ty>().boxed() }
+        }
+    )+ }
+}
+
+macro_rules! arbitrary_le_bytes {
+    ($($ty:ty => $len:literal),+) => { $(
+        impl StrictArbitrary for $ty {
+            fn strict_arbitrary() -> BoxedStrategy<Self> {
+                any::<[u8; $len]>().prop_map(<$ty>::from_le_bytes).boxed()
+            }
+        }
+    )+ }
+}
+
+arbitrary_any!((), bool, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+arbitrary_le_bytes!(u24 => 3, u40 => 5, u48 => 6, u56 => 7, u256 => 32, u512 => 64, u1024 => 128);
+
+impl<T: StrictArbitrary> StrictArbitrary for Box<T> {
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        T::strict_arbitrary().prop_map(Box::new).boxed()
+    }
+}
+
+impl<T: StrictArbitrary> StrictArbitrary for Option<T> {
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        proptest::option::of(T::strict_arbitrary()).boxed()
+    }
+}
+
+impl<A: StrictArbitrary, B: StrictArbitrary> StrictArbitrary for (A, B) {
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        (A::strict_arbitrary(), B::strict_arbitrary()).boxed()
+    }
+}
+
+impl<A: StrictArbitrary, B: StrictArbitrary, C: StrictArbitrary> StrictArbitrary for (A, B, C) {
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        (A::strict_arbitrary(), B::strict_arbitrary(), C::strict_arbitrary()).boxed()
+    }
+}
+
+impl<T: StrictArbitrary, const LEN: usize> StrictArbitrary for [T; LEN] {
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        proptest::array::uniform::<_, LEN>(T::strict_arbitrary()).boxed()
+    }
+}
+
+impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictArbitrary
+    for Confined<String, MIN_LEN, MAX_LEN>
+{
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        collection::vec(0x20u8..0x7F, len_range::<MIN_LEN, MAX_LEN>())
+            .prop_map(|bytes| {
+                let s = String::from_utf8(bytes).expect("printable ASCII");
+                Confined::try_from(s).expect("length within confinement bounds")
+            })
+            .boxed()
+    }
+}
+
+impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictArbitrary
+    for Confined<AsciiString, MIN_LEN, MAX_LEN>
+{
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        collection::vec(0x20u8..0x7F, len_range::<MIN_LEN, MAX_LEN>())
+            .prop_map(|bytes| {
+                let s = AsciiString::from_ascii(bytes).expect("printable ASCII");
+                Confined::try_from(s).expect("length within confinement bounds")
+            })
+            .boxed()
+    }
+}
+
+impl<C1, C, const MIN: usize, const MAX: usize> StrictArbitrary for RString<C1, C, MIN, MAX>
+where
+    C1: RestrictedCharSet + 'static,
+    C: RestrictedCharSet + 'static,
+{
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        (select(charset::<C1>()), collection::vec(select(charset::<C>()), len_range::<MIN, MAX>()))
+            .prop_map(|(first, mut bytes)| {
+                if let Some(b) = bytes.first_mut() {
+                    *b = first;
+                }
+                Self::try_from(bytes).expect("string matching the charset and length bounds")
+            })
+            .boxed()
+    }
+}
+
+impl<T: StrictArbitrary, const MIN_LEN: usize, const MAX_LEN: usize> StrictArbitrary
+    for Confined<Vec<T>, MIN_LEN, MAX_LEN>
+{
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        collection::vec(T::strict_arbitrary(), len_range::<MIN_LEN, MAX_LEN>())
+            .prop_map(|col| Confined::try_from(col).expect("length within confinement bounds"))
+            .boxed()
+    }
+}
+
+impl<T: StrictArbitrary, const MIN_LEN: usize, const MAX_LEN: usize> StrictArbitrary
+    for Confined<VecDeque<T>, MIN_LEN, MAX_LEN>
+{
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        collection::vec_deque(T::strict_arbitrary(), len_range::<MIN_LEN, MAX_LEN>())
+            .prop_map(|col| Confined::try_from(col).expect("length within confinement bounds"))
+            .boxed()
+    }
+}
+
+impl<T: StrictArbitrary + Ord, const MIN_LEN: usize, const MAX_LEN: usize> StrictArbitrary
+    for Confined<BTreeSet<T>, MIN_LEN, MAX_LEN>
+{
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        len_range::<MIN_LEN, MAX_LEN>()
+            .prop_flat_map(|len| unique_items(T::strict_arbitrary(), len, BTreeSet::len))
+            .prop_filter("not enough unique items", |col| col.len() >= MIN_LEN)
+            .prop_map(|col| Confined::try_from(col).expect("length within confinement bounds"))
+            .boxed()
+    }
+}
+
+impl<K, V, const MIN_LEN: usize, const MAX_LEN: usize> StrictArbitrary
+    for Confined<BTreeMap<K, V>, MIN_LEN, MAX_LEN>
+where
+    K: StrictArbitrary + Ord + Hash,
+    V: StrictArbitrary,
+{
+    fn strict_arbitrary() -> BoxedStrategy<Self> {
+        len_range::<MIN_LEN, MAX_LEN>()
+            .prop_flat_map(|len| {
+                let item = (K::strict_arbitrary(), V::strict_arbitrary()).boxed();
+                unique_items(item, len, BTreeMap::len)
+            })
+            .prop_filter("not enough unique keys", |col| col.len() >= MIN_LEN)
+            .prop_map(|col| Confined::try_from(col).expect("length within confinement bounds"))
+            .boxed()
+    }
+}
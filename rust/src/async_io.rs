@@ -0,0 +1,215 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reading and writing of strictly encoded values over asynchronous
+//! [`tokio`] streams.
+//!
+//! Strict encoding and decoding remain synchronous: the values are encoded
+//! into memory and then written to the stream, while the data read from the
+//! stream are buffered until they contain the whole value.
+
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    DecodeError, StreamReader, StreamWriter, StrictDecode, StrictEncode, StrictReader, StrictWriter,
+};
+
+/// Minimal number of bytes requested from the underlying reader at once.
+const READ_CHUNK: usize = 1024;
+
+/// Writer of strictly encoded values into [`AsyncWrite`] stream.
+#[derive(Debug)]
+pub struct AsyncStrictWriter<W: AsyncWrite + Unpin> {
+    writer: W,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncStrictWriter<W> {
+    pub fn new(writer: W) -> Self { Self { writer } }
+
+    pub fn unbox(self) -> W { self.writer }
+
+    /// Encodes the value, failing if it takes more than `MAX` bytes, and
+    /// writes it to the stream.
+    pub async fn strict_encode_async<const MAX: usize>(
+        &mut self,
+        value: &impl StrictEncode,
+    ) -> io::Result<()> {
+        let data = value.strict_encode(StrictWriter::in_memory::<MAX>())?.unbox().unconfine();
+        self.writer.write_all(&data).await
+    }
+
+    /// Writes the value prefixed with its length, in the same way as
+    /// [`StrictEncode::strict_write_framed`] does.
+    pub async fn strict_write_framed_async<const MAX: usize>(
+        &mut self,
+        value: &impl StrictEncode,
+    ) -> io::Result<()> {
+        let mut frame = StreamWriter::in_memory::<{ usize::MAX }>();
+        value.strict_write_framed::<MAX>(&mut frame)?;
+        self.writer.write_all(&frame.unconfine()).await
+    }
+
+    pub async fn flush(&mut self) -> io::Result<()> { self.writer.flush().await }
+}
+
+/// Reader of strictly encoded values from [`AsyncRead`] stream.
+///
+/// The data read from the stream are kept in a buffer, and the decoding of a
+/// value is retried each time more data arrive, until it succeeds or fails
+/// with an error other than the end of the data (see [`DecodeError::is_eof`]).
+/// Thus, the values which decode until the end of the data (structures with
+/// extensions or default field values and enums with unknown variants carrying
+/// data) must be written as frames and read with [`Self::strict_read_framed_async`], since
+/// otherwise they would be decoded from whatever part of the data is
+/// available.
+#[derive(Debug)]
+pub struct AsyncStrictReader<R: AsyncRead + Unpin> {
+    reader: R,
+    buf: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncStrictReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: vec![],
+        }
+    }
+
+    /// Returns the data read from the stream which are not decoded yet.
+    pub fn buffered(&self) -> &[u8] { &self.buf }
+
+    /// Returns the underlying stream together with the data read from it
+    /// which are not decoded yet.
+    pub fn into_parts(self) -> (R, Vec<u8>) { (self.reader, self.buf) }
+
+    /// Reads and decodes a value taking no more than `MAX` bytes. Fails with
+    /// [`DecodeError::UnexpectedEof`] if the stream ends before the value is
+    /// complete; if the stream ends right at a value boundary,
+    /// [`Self::buffered`] data are empty.
+    pub async fn strict_decode_async<T: StrictDecode, const MAX: usize>(
+        &mut self,
+    ) -> Result<T, DecodeError> {
+        self.decode::<T, MAX>(|reader| T::strict_decode(&mut StrictReader::with(reader))).await
+    }
+
+    /// Reads a value written with [`AsyncStrictWriter::strict_write_framed_async`]
+    /// or [`StrictEncode::strict_write_framed`].
+    pub async fn strict_read_framed_async<T: StrictDecode, const MAX: usize>(
+        &mut self,
+    ) -> Result<T, DecodeError> {
+        self.decode::<T, MAX>(|reader| T::strict_read_framed::<MAX>(reader)).await
+    }
+
+    async fn decode<T, const MAX: usize>(
+        &mut self,
+        decode: impl Fn(&mut StreamReader<io::Cursor<&[u8]>>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        loop {
+            let mut reader = StreamReader::in_memory::<MAX>(self.buf.as_slice());
+            match decode(&mut reader) {
+                Ok(value) => {
+                    let consumed = self.buf.len() - reader.remaining();
+                    self.buf.drain(..consumed);
+                    return Ok(value);
+                }
+                Err(err) if err.is_eof() => {}
+                Err(err) => return Err(err),
+            }
+            // Growing the requested size with the buffer keeps the number of
+            // the decoding attempts logarithmic in the value size
+            self.buf.reserve(self.buf.len().max(READ_CHUNK));
+            if self.reader.read_buf(&mut self.buf).await? == 0 {
+                return Err(DecodeError::UnexpectedEof);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::TinyBlob;
+
+    use super::*;
+    use crate::test::encode;
+
+    type Record = (u8, u16, TinyBlob);
+
+    fn record(id: u8) -> Record {
+        (id, 0x0302, TinyBlob::try_from(vec![id; id as usize * 100]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn roundtrip() {
+        // Small buffer makes the records arrive in parts
+        let (client, server) = tokio::io::duplex(16);
+
+        let write = async move {
+            let mut writer = AsyncStrictWriter::new(client);
+            for id in 0..3 {
+                writer.strict_encode_async::<1024>(&record(id)).await.unwrap();
+                writer.strict_write_framed_async::<1024>(&record(id)).await.unwrap();
+            }
+            writer.flush().await.unwrap();
+        };
+        let read = async move {
+            let mut reader = AsyncStrictReader::new(server);
+            for id in 0..3 {
+                assert_eq!(reader.strict_decode_async::<Record, 1024>().await.unwrap(), record(id));
+                assert_eq!(
+                    reader.strict_read_framed_async::<Record, 1024>().await.unwrap(),
+                    record(id)
+                );
+            }
+            assert_eq!(
+                reader.strict_decode_async::<Record, 1024>().await.unwrap_err(),
+                DecodeError::UnexpectedEof
+            );
+            assert!(reader.buffered().is_empty());
+        };
+        tokio::join!(write, read);
+    }
+
+    #[tokio::test]
+    async fn truncated_and_oversized() {
+        let data = encode(&record(2));
+
+        let mut reader = AsyncStrictReader::new(&data[..data.len() - 1]);
+        assert_eq!(
+            reader.strict_decode_async::<Record, 1024>().await.unwrap_err(),
+            DecodeError::UnexpectedEof
+        );
+        assert_eq!(reader.buffered(), &data[..data.len() - 1]);
+
+        let mut reader = AsyncStrictReader::new(data.as_slice());
+        assert!(matches!(
+            reader.strict_decode_async::<Record, 16>().await.unwrap_err(),
+            DecodeError::Io(_)
+        ));
+
+        let mut writer = AsyncStrictWriter::new(vec![]);
+        assert!(writer.strict_encode_async::<16>(&record(2)).await.is_err());
+        writer.strict_encode_async::<1024>(&record(2)).await.unwrap();
+        assert_eq!(writer.unbox(), data);
+    }
+}
@@ -19,13 +19,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::hash::Hash;
 use std::io;
-use std::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8};
+use std::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, Saturating, Wrapping};
+use std::ops::Bound;
 
 use amplify::ascii::AsciiString;
-use amplify::confinement::Confined;
+use amplify::confinement::{self, Collection, Confined, LargeVec, U32, U64};
 #[cfg(feature = "float")]
 use amplify::num::apfloat::{ieee, Float};
 use amplify::num::{i1024, i256, i512, u1024, u24, u256, u40, u48, u512, u56};
@@ -33,10 +35,10 @@ use amplify::{Array, Wrapper};
 
 use crate::stl::AsciiSym;
 use crate::{
-    DecodeError, DefineUnion, Primitive, RString, ReadRaw, ReadTuple, ReadUnion, RestrictedCharSet,
-    Sizing, StrictDecode, StrictDumb, StrictEncode, StrictProduct, StrictStruct, StrictSum,
-    StrictTuple, StrictType, StrictUnion, TypeName, TypedRead, TypedWrite, WriteRaw, WriteTuple,
-    WriteUnion, LIB_EMBEDDED,
+    non_terminal, ConstHasher, DecodeError, DefineUnion, Primitive, RString, ReadRaw, ReadTuple,
+    ReadUnion, RestrictedCharSet, Sizing, StrictDecode, StrictDumb, StrictEncode, StrictProduct,
+    StrictSerialize, StrictStruct, StrictSum, StrictTuple, StrictType, StrictTypeId, StrictUnion,
+    TypeName, TypedRead, TypedWrite, WriteRaw, WriteTuple, WriteUnion, LIB_EMBEDDED,
 };
 
 pub trait DecodeRawLe: Sized {
@@ -64,29 +66,41 @@ impl StrictEncode for Byte {
 
 macro_rules! encode_num {
     ($ty:ty, $id:ident) => {
+        encode_num!($ty, $id, {}, {});
+    };
+    ($ty:ty, $id:ident, { $($encode:tt)* }, { $($decode:tt)* }) => {
         impl $crate::StrictType for $ty {
             const STRICT_LIB_NAME: &'static str = $crate::LIB_EMBEDDED;
             fn strict_name() -> Option<TypeName> { Some(tn!(stringify!($id))) }
         }
+        impl $crate::StrictTypeId for $ty {
+            const STRICT_ID: u64 = compose_id(stringify!($id), &[]);
+            const WIRE_FINGERPRINT: u64 = compose_id(stringify!($id), &[]);
+        }
         impl $crate::StrictEncode for $ty {
             fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
                 unsafe {
                     writer = writer.register_primitive(Primitive::$id);
-                    writer.raw_writer().write_raw_array(self.to_le_bytes())?;
+                    writer.raw_writer().write_raw_num(self.to_le_bytes())?;
                 }
                 Ok(writer)
             }
+            $($encode)*
         }
         impl $crate::DecodeRawLe for $ty {
             fn decode_raw_le(reader: &mut (impl ReadRaw + ?Sized)) -> Result<Self, DecodeError> {
-                let buf = reader.read_raw_array::<{ Self::BITS as usize / 8 }>()?;
+                let buf = reader.read_raw_num::<{ Self::BITS as usize / 8 }>()?;
                 Ok(Self::from_le_bytes(buf))
             }
         }
         impl $crate::StrictDecode for $ty {
             fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-                Self::decode_raw_le(unsafe { reader.raw_reader() })
+                unsafe {
+                    reader.register_primitive(Primitive::$id);
+                    Self::decode_raw_le(reader.raw_reader())
+                }
             }
+            $($decode)*
         }
     };
 }
@@ -97,19 +111,27 @@ macro_rules! encode_nonzero {
             const STRICT_LIB_NAME: &'static str = $crate::LIB_EMBEDDED;
             fn strict_name() -> Option<TypeName> { Some(tn!(stringify!($id))) }
         }
+        // Non-zero integers may share the primitive with the plain ones, while
+        // they are different types
+        impl $crate::StrictTypeId for $ty {
+            const STRICT_ID: u64 = compose_id(stringify!($ty), &[]);
+            const WIRE_FINGERPRINT: u64 = compose_id(stringify!($ty), &[]);
+        }
         impl $crate::StrictEncode for $ty {
             fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
                 unsafe {
                     writer = writer.register_primitive(Primitive::$id);
-                    writer.raw_writer().write_raw_array(self.get().to_le_bytes())?;
+                    writer.raw_writer().write_raw_num(self.get().to_le_bytes())?;
                 }
                 Ok(writer)
             }
         }
         impl $crate::StrictDecode for $ty {
             fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-                let buf =
-                    unsafe { reader.raw_reader().read_raw_array::<{ Self::BITS as usize / 8 }>()? };
+                let buf = unsafe {
+                    reader.register_primitive(Primitive::$id);
+                    reader.raw_reader().read_raw_num::<{ Self::BITS as usize / 8 }>()?
+                };
                 let v = <$p>::from_le_bytes(buf);
                 Self::new(v).ok_or(DecodeError::ZeroNatural)
             }
@@ -125,13 +147,18 @@ macro_rules! encode_float {
             fn strict_name() -> Option<TypeName> { Some(tn!(stringify!($id))) }
         }
         #[cfg(feature = "float")]
+        impl $crate::StrictTypeId for $ty {
+            const STRICT_ID: u64 = compose_id(stringify!($id), &[]);
+            const WIRE_FINGERPRINT: u64 = compose_id(stringify!($id), &[]);
+        }
+        #[cfg(feature = "float")]
         impl $crate::StrictEncode for $ty {
             fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
-                let mut be = [0u8; $len];
-                be.copy_from_slice(&self.to_bits().to_le_bytes()[..$len]);
+                let mut le = [0u8; $len];
+                le.copy_from_slice(&self.to_bits().to_le_bytes()[..$len]);
                 unsafe {
                     writer = writer.register_primitive(Primitive::$id);
-                    writer.raw_writer().write_raw_array(be)?;
+                    writer.raw_writer().write_raw_num(le)?;
                 }
                 Ok(writer)
             }
@@ -141,7 +168,7 @@ macro_rules! encode_float {
             fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
                 const BYTES: usize = <$ty>::BITS / 8;
                 let mut inner = [0u8; 32];
-                let buf = unsafe { reader.raw_reader().read_raw_array::<BYTES>()? };
+                let buf = unsafe { reader.raw_reader().read_raw_num::<BYTES>()? };
                 inner[..BYTES].copy_from_slice(&buf[..]);
                 let bits = u256::from_le_bytes(inner);
                 Ok(Self::from_bits(bits))
@@ -150,7 +177,39 @@ macro_rules! encode_float {
     };
 }
 
-encode_num!(u8, U8);
+encode_num!(
+    u8,
+    U8,
+    {
+        fn strict_encode_slice<W: TypedWrite, const MAX_LEN: usize>(
+            items: &[Self],
+            writer: W,
+        ) -> io::Result<W> {
+            unsafe { writer.write_bytes::<MAX_LEN>(items) }
+        }
+
+        fn strict_encode_array<W: TypedWrite, const LEN: usize>(
+            array: &[Self; LEN],
+            writer: W,
+        ) -> io::Result<W> {
+            unsafe { writer.write_byte_array(array) }
+        }
+    },
+    {
+        fn strict_decode_vec<const MIN_LEN: usize, const MAX_LEN: usize>(
+            reader: &mut impl TypedRead,
+        ) -> Result<Confined<Vec<Self>, MIN_LEN, MAX_LEN>, DecodeError> {
+            let bytes = unsafe { reader.read_bytes::<MAX_LEN>()? };
+            Confined::try_from(bytes).map_err(DecodeError::from)
+        }
+
+        fn strict_decode_array<const LEN: usize>(
+            reader: &mut impl TypedRead,
+        ) -> Result<[Self; LEN], DecodeError> {
+            unsafe { reader.read_byte_array::<LEN>() }
+        }
+    }
+);
 encode_num!(u16, U16);
 encode_num!(u24, U24);
 encode_num!(u32, U32);
@@ -189,6 +248,7 @@ impl<T> StrictType for Box<T>
 where T: StrictType
 {
     const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+    const HAS_EXTENSIONS: bool = T::HAS_EXTENSIONS;
 }
 impl<T> StrictSum for Box<T>
 where T: StrictSum
@@ -223,10 +283,111 @@ where T: StrictDecode
     }
 }
 
+macro_rules! encode_transparent {
+    ($ty:ident) => {
+        impl<T> StrictType for $ty<T>
+        where T: StrictType
+        {
+            const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+            const HAS_EXTENSIONS: bool = T::HAS_EXTENSIONS;
+            fn strict_name() -> Option<TypeName> { T::strict_name() }
+        }
+        impl<T> StrictEncode for $ty<T>
+        where T: StrictEncode
+        {
+            fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+                self.0.strict_encode(writer)
+            }
+        }
+        impl<T> StrictDecode for $ty<T>
+        where T: StrictDecode
+        {
+            fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+                T::strict_decode(reader).map($ty)
+            }
+        }
+    };
+}
+
+encode_transparent!(Wrapping);
+encode_transparent!(Saturating);
+
+/// Writes the items in the same way as `Confined<Vec<T>, MIN_LEN, MAX_LEN>`,
+/// failing with [`io::ErrorKind::InvalidInput`] if there are more than `MAX_LEN`
+/// of them. Shared by the vectors, boxed and borrowed slices.
+fn encode_list<
+    W: TypedWrite,
+    T: StrictEncode + StrictDumb,
+    const MIN_LEN: usize,
+    const MAX_LEN: usize,
+>(
+    items: &[T],
+    mut writer: W,
+) -> io::Result<W> {
+    if items.len() > MAX_LEN {
+        let err = confinement::Error::Oversize {
+            len: items.len(),
+            max_len: MAX_LEN,
+        };
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, err));
+    }
+    let sizing = Sizing::new(MIN_LEN as u64, MAX_LEN as u64);
+    writer = T::strict_encode_slice::<W, MAX_LEN>(items, writer)?;
+    Ok(unsafe {
+        if T::strict_name() == u8::strict_name() {
+            writer.register_list(&Byte::strict_dumb(), sizing)
+        } else {
+            writer.register_list(&T::strict_dumb(), sizing)
+        }
+    })
+}
+
+/// Maximal length of boxed slices, which are encoded in the same way as
+/// [`amplify::confinement::LargeVec`] (i.e. with a 32-bit length prefix).
+pub const BOXED_SLICE_MAX_LEN: usize = U32;
+
+impl<T: StrictType> StrictType for Box<[T]> {
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    const HAS_EXTENSIONS: bool = non_terminal::<T>();
+    fn strict_name() -> Option<TypeName> { None }
+}
+impl<T: StrictEncode + StrictDumb> StrictEncode for Box<[T]> {
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the slice is longer than
+    /// [`BOXED_SLICE_MAX_LEN`].
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        encode_list::<W, T, 0, BOXED_SLICE_MAX_LEN>(self, writer)
+    }
+}
+impl<T: StrictDecode> StrictDecode for Box<[T]> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let vec = LargeVec::<T>::strict_decode(reader)?;
+        Ok(vec.release().into_boxed_slice())
+    }
+}
+
+/// Borrowed slices are encoded in the same way as `Confined<Vec<T>, 0, U64>`
+/// (i.e. with a 64-bit length prefix). They are encode-only, since decoding
+/// can't produce a borrowed slice; decode the data as
+/// `Confined<Vec<T>, 0, U64>` instead.
+impl<T: StrictType> StrictType for &[T] {
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    const HAS_EXTENSIONS: bool = non_terminal::<T>();
+    fn strict_name() -> Option<TypeName> { None }
+}
+impl<T: StrictEncode + StrictDumb> StrictEncode for &[T] {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        encode_list::<W, T, 0, U64>(self, writer)
+    }
+}
+
 impl<T> StrictType for Option<T>
 where T: StrictType
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    // The value is the last data of the encoding
+    const HAS_EXTENSIONS: bool = T::HAS_EXTENSIONS;
     fn strict_name() -> Option<TypeName> { None }
 }
 impl<T> StrictSum for Option<T>
@@ -264,86 +425,158 @@ impl<T: StrictDecode> StrictDecode for Option<T> {
     }
 }
 
-impl StrictType for () {
+/// Type information for writing and reading [`Bound`]s, which, unlike the
+/// bounds themselves, is a [`StrictUnion`]: `Bound` has no dumb value, and
+/// the dumb value of this type stands for the unbounded variant.
+#[derive(Default)]
+struct AnonymousBound;
+
+const BOUND_VARIANTS: &[(u8, &str)] = &[(0u8, "unbounded"), (1u8, "included"), (2u8, "excluded")];
+
+impl StrictType for AnonymousBound {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
 }
-impl StrictEncode for () {
-    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
-        Ok(unsafe { writer.register_primitive(Primitive::UNIT) })
-    }
-}
-impl StrictDecode for () {
-    fn strict_decode(_reader: &mut impl TypedRead) -> Result<Self, DecodeError> { Ok(()) }
+impl StrictSum for AnonymousBound {
+    const ALL_VARIANTS: &'static [(u8, &'static str)] = BOUND_VARIANTS;
+    fn variant_name(&self) -> &'static str { "unbounded" }
 }
+impl StrictUnion for AnonymousBound {}
 
-impl<A: StrictType, B: StrictType> StrictType for (A, B) {
+impl<T> StrictType for Bound<T>
+where T: StrictType
+{
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
 }
-impl<A: StrictType + Default, B: StrictType + Default> StrictProduct for (A, B) {}
-impl<A: StrictType + Default, B: StrictType + Default> StrictTuple for (A, B) {
-    const FIELD_COUNT: u8 = 2;
+impl<T> StrictSum for Bound<T>
+where T: StrictType
+{
+    const ALL_VARIANTS: &'static [(u8, &'static str)] = BOUND_VARIANTS;
+    fn variant_name(&self) -> &'static str {
+        match self {
+            Bound::Unbounded => "unbounded",
+            Bound::Included(_) => "included",
+            Bound::Excluded(_) => "excluded",
+        }
+    }
 }
-impl<A: StrictEncode + Default, B: StrictEncode + Default> StrictEncode for (A, B) {
+impl<T: StrictEncode + StrictDumb> StrictEncode for Bound<T> {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
-        writer.write_tuple::<Self>(|w| Ok(w.write_field(&self.0)?.write_field(&self.1)?.complete()))
+        writer.write_union::<AnonymousBound>(|u| {
+            let u = u
+                .define_unit(vname!("unbounded"))
+                .define_newtype::<T>(vname!("included"))
+                .define_newtype::<T>(vname!("excluded"))
+                .complete();
+
+            Ok(match self {
+                Bound::Unbounded => u.write_unit(vname!("unbounded")),
+                Bound::Included(val) => u.write_newtype(vname!("included"), val),
+                Bound::Excluded(val) => u.write_newtype(vname!("excluded"), val),
+            }?
+            .complete())
+        })
     }
 }
-impl<A: StrictDecode + Default, B: StrictDecode + Default> StrictDecode for (A, B) {
+impl<T: StrictDecode> StrictDecode for Bound<T> {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-        reader.read_tuple(|r| {
-            let a = r.read_field()?;
-            let b = r.read_field()?;
-            Ok((a, b))
-        })
+        let mut value = None;
+        reader.read_union(|variant_name, u| {
+            value = Some(match variant_name.as_str() {
+                "unbounded" => Bound::Unbounded,
+                "included" => u.read_tuple(|r| r.read_field().map(Bound::Included))?,
+                "excluded" => u.read_tuple(|r| r.read_field().map(Bound::Excluded))?,
+                _ => unreachable!("unknown bound variant"),
+            });
+            Ok(AnonymousBound)
+        })?;
+        Ok(value.expect("bound variant is read"))
     }
 }
 
-impl<A: StrictType, B: StrictType, C: StrictType> StrictType for (A, B, C) {
+impl StrictType for () {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
 }
-impl<A: StrictType + Default, B: StrictType + Default, C: StrictType + Default> StrictProduct
-    for (A, B, C)
-{
-}
-impl<A: StrictType + Default, B: StrictType + Default, C: StrictType + Default> StrictTuple
-    for (A, B, C)
-{
-    const FIELD_COUNT: u8 = 3;
-}
-impl<A: StrictEncode + Default, B: StrictEncode + Default, C: StrictEncode + Default> StrictEncode
-    for (A, B, C)
-{
+impl StrictEncode for () {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
-        writer.write_tuple::<Self>(|w| {
-            Ok(w.write_field(&self.0)?.write_field(&self.1)?.write_field(&self.2)?.complete())
-        })
+        Ok(unsafe { writer.register_primitive(Primitive::UNIT) })
     }
 }
-impl<A: StrictDecode + Default, B: StrictDecode + Default, C: StrictDecode + Default> StrictDecode
-    for (A, B, C)
-{
-    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-        reader.read_tuple(|r| {
-            let a = r.read_field()?;
-            let b = r.read_field()?;
-            let c = r.read_field()?;
-            Ok((a, b, c))
-        })
-    }
+impl StrictDecode for () {
+    fn strict_decode(_reader: &mut impl TypedRead) -> Result<Self, DecodeError> { Ok(()) }
 }
 
-impl<T: StrictType + Copy + StrictDumb, const LEN: usize> StrictType for [T; LEN] {
+/// Type information for writing and reading tuples, which, unlike the tuples
+/// themselves, is a [`StrictTuple`] even when the tuple elements do not
+/// implement `Default`.
+#[derive(Default)]
+struct AnonymousTuple<const FIELD_COUNT: u8>;
+
+impl<const FIELD_COUNT: u8> StrictType for AnonymousTuple<FIELD_COUNT> {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
 }
+impl<const FIELD_COUNT: u8> StrictProduct for AnonymousTuple<FIELD_COUNT> {}
+impl<const FIELD_COUNT: u8> StrictTuple for AnonymousTuple<FIELD_COUNT> {
+    const FIELD_COUNT: u8 = FIELD_COUNT;
+}
+
+macro_rules! impl_tuple {
+    ($count:literal; $($ty:ident $idx:tt),+) => {
+        impl<$($ty: StrictType),+> StrictType for ($($ty),+) {
+            const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+            const HAS_EXTENSIONS: bool = $(non_terminal::<$ty>())|+;
+            fn strict_name() -> Option<TypeName> { None }
+        }
+        // Tuples get dumb values only from the blanket implementation for `Default`
+        // types, since implementing `StrictDumb` for them directly would conflict
+        // with it; thus the bounds don't require `Default` from the items on their own
+        impl<$($ty: StrictType),+> StrictProduct for ($($ty),+) where Self: StrictDumb {}
+        impl<$($ty: StrictType),+> StrictTuple for ($($ty),+) where Self: StrictDumb {
+            const FIELD_COUNT: u8 = $count;
+        }
+        impl<$($ty: StrictEncode),+> StrictEncode for ($($ty),+) {
+            fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+                writer.write_tuple::<AnonymousTuple<$count>>(|w| {
+                    Ok(w$(.write_field(&self.$idx)?)+.complete())
+                })
+            }
+        }
+        impl<$($ty: StrictDecode),+> StrictDecode for ($($ty),+) {
+            fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+                let mut value = None;
+                reader.read_tuple(|r| {
+                    value = Some(($(r.read_field::<$ty>()?),+));
+                    Ok(AnonymousTuple::<$count>)
+                })?;
+                Ok(value.expect("all tuple fields are read"))
+            }
+        }
+    };
+}
+
+impl_tuple!(2; A 0, B 1);
+impl_tuple!(3; A 0, B 1, C 2);
+impl_tuple!(4; A 0, B 1, C 2, D 3);
+impl_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(9; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(10; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(11; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(12; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+impl<T: StrictType, const LEN: usize> StrictType for [T; LEN] {
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    const HAS_EXTENSIONS: bool = non_terminal::<T>();
+    fn strict_name() -> Option<TypeName> { None }
+}
 impl<T: StrictEncode + Copy + StrictDumb, const LEN: usize> StrictEncode for [T; LEN] {
     fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
-        for item in self {
-            writer = item.strict_encode(writer)?;
-        }
+        writer = T::strict_encode_array(self, writer)?;
         Ok(unsafe {
             if T::strict_name() == u8::strict_name() {
                 writer.register_array(&Byte::strict_dumb(), LEN as u16)
@@ -353,13 +586,9 @@ impl<T: StrictEncode + Copy + StrictDumb, const LEN: usize> StrictEncode for [T;
         })
     }
 }
-impl<T: StrictDecode + Copy + StrictDumb, const LEN: usize> StrictDecode for [T; LEN] {
+impl<T: StrictDecode, const LEN: usize> StrictDecode for [T; LEN] {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-        let mut ar = [T::strict_dumb(); LEN];
-        for c in ar.iter_mut() {
-            *c = T::strict_decode(reader)?;
-        }
-        Ok(ar)
+        T::strict_decode_array::<LEN>(reader)
     }
 }
 
@@ -388,10 +617,27 @@ impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictType for Confined<String,
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
     fn strict_name() -> Option<TypeName> { None }
 }
+/// With `unicode-normalization` feature, fails if the string is not in Unicode
+/// normalization form C, so the strings which can't be decoded are not
+/// written.
+fn check_normalized(s: &str) -> io::Result<()> {
+    #[cfg(feature = "unicode-normalization")]
+    if !unicode_normalization::is_nfc(s) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            DecodeError::NotNormalized(s.to_owned()).to_string(),
+        ));
+    }
+    #[cfg(not(feature = "unicode-normalization"))]
+    let _ = s;
+    Ok(())
+}
+
 impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
     for Confined<String, MIN_LEN, MAX_LEN>
 {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        check_normalized(self)?;
         unsafe {
             writer
                 .register_unicode(Sizing::new(MIN_LEN as u64, MAX_LEN as u64))
@@ -399,16 +645,42 @@ impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
         }
     }
 }
+/// With `unicode-normalization` feature, decoding rejects the strings which are
+/// not in Unicode normalization form C with [`DecodeError::NotNormalized`], so
+/// the same text can't be represented by different data. Encoding such strings
+/// fails as well.
 impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
     for Confined<String, MIN_LEN, MAX_LEN>
 {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
         let bytes = unsafe { reader.read_string::<MAX_LEN>()? };
         let s = String::from_utf8(bytes)?;
+        #[cfg(feature = "unicode-normalization")]
+        if !unicode_normalization::is_nfc(&s) {
+            return Err(DecodeError::NotNormalized(s));
+        }
         Confined::try_from(s).map_err(DecodeError::from)
     }
 }
 
+/// String slices are encoded in the same way as `Confined<String, 0, U64>`,
+/// i.e. with a 64-bit length prefix, which can't be exceeded by a slice
+/// length. The encoding is one-way: decode the data into a [`Confined`] string
+/// with the same bounds.
+impl StrictType for &str {
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    fn strict_name() -> Option<TypeName> { None }
+}
+impl StrictEncode for &str {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        check_normalized(self)?;
+        unsafe {
+            writer.register_unicode(Sizing::new(0, U64 as u64)).write_string::<U64>(self.as_bytes())
+        }
+    }
+}
+impl StrictSerialize for &str {}
+
 impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictType
     for Confined<AsciiString, MIN_LEN, MAX_LEN>
 {
@@ -486,34 +758,21 @@ impl<T: StrictType, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
     for Confined<Vec<T>, MIN_LEN, MAX_LEN>
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    const HAS_EXTENSIONS: bool = non_terminal::<T>();
     fn strict_name() -> Option<TypeName> { None }
 }
 impl<T: StrictEncode + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
     for Confined<Vec<T>, MIN_LEN, MAX_LEN>
 {
-    fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
-        let sizing = Sizing::new(MIN_LEN as u64, MAX_LEN as u64);
-        writer = unsafe {
-            writer = writer.write_collection::<Vec<T>, MIN_LEN, MAX_LEN>(self)?;
-            if T::strict_name() == u8::strict_name() {
-                writer.register_list(&Byte::strict_dumb(), sizing)
-            } else {
-                writer.register_list(&T::strict_dumb(), sizing)
-            }
-        };
-        Ok(writer)
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        encode_list::<W, T, MIN_LEN, MAX_LEN>(self, writer)
     }
 }
 impl<T: StrictDecode, const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
     for Confined<Vec<T>, MIN_LEN, MAX_LEN>
 {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-        let len = unsafe { reader.raw_reader().read_raw_len::<MAX_LEN>()? };
-        let mut col = Vec::<T>::with_capacity(len);
-        for _ in 0..len {
-            col.push(StrictDecode::strict_decode(reader)?);
-        }
-        Confined::try_from(col).map_err(DecodeError::from)
+        T::strict_decode_vec::<MIN_LEN, MAX_LEN>(reader)
     }
 }
 
@@ -521,6 +780,7 @@ impl<T: StrictType, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
     for Confined<VecDeque<T>, MIN_LEN, MAX_LEN>
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    const HAS_EXTENSIONS: bool = non_terminal::<T>();
     fn strict_name() -> Option<TypeName> { None }
 }
 impl<T: StrictEncode + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
@@ -543,7 +803,7 @@ impl<T: StrictDecode, const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
     for Confined<VecDeque<T>, MIN_LEN, MAX_LEN>
 {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-        let len = unsafe { reader.raw_reader().read_raw_len::<MAX_LEN>()? };
+        let len = unsafe { reader.read_collection_len::<MAX_LEN>()? };
         let mut col = VecDeque::<T>::with_capacity(len);
         for _ in 0..len {
             col.push_back(StrictDecode::strict_decode(reader)?);
@@ -556,6 +816,7 @@ impl<T: StrictType + Ord, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
     for Confined<BTreeSet<T>, MIN_LEN, MAX_LEN>
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    const HAS_EXTENSIONS: bool = non_terminal::<T>();
     fn strict_name() -> Option<TypeName> { None }
 }
 impl<T: StrictEncode + Ord + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
@@ -574,7 +835,7 @@ impl<T: StrictDecode + Ord, const MIN_LEN: usize, const MAX_LEN: usize> StrictDe
     for Confined<BTreeSet<T>, MIN_LEN, MAX_LEN>
 {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-        let len = unsafe { reader.raw_reader().read_raw_len::<MAX_LEN>()? };
+        let len = unsafe { reader.read_collection_len::<MAX_LEN>()? };
         let mut col = BTreeSet::<T>::new();
         for _ in 0..len {
             let item = StrictDecode::strict_decode(reader)?;
@@ -593,6 +854,7 @@ impl<K: StrictType + Ord + Hash, V: StrictType, const MIN_LEN: usize, const MAX_
     StrictType for Confined<BTreeMap<K, V>, MIN_LEN, MAX_LEN>
 {
     const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    const HAS_EXTENSIONS: bool = non_terminal::<K>() | non_terminal::<V>();
     fn strict_name() -> Option<TypeName> { None }
 }
 impl<
@@ -604,11 +866,7 @@ impl<
 {
     fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
         unsafe {
-            writer.raw_writer().write_raw_len::<MAX_LEN>(self.len())?;
-        }
-        for (k, v) in self {
-            writer = k.strict_encode(writer)?;
-            writer = v.strict_encode(writer)?
+            writer = writer.write_keyed_collection::<BTreeMap<K, V>, MIN_LEN, MAX_LEN>(self)?;
         }
         Ok(unsafe {
             writer.register_map(
@@ -627,7 +885,7 @@ impl<
     > StrictDecode for Confined<BTreeMap<K, V>, MIN_LEN, MAX_LEN>
 {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
-        let len = unsafe { reader.raw_reader().read_raw_len::<MAX_LEN>()? };
+        let len = unsafe { reader.read_keyed_collection_len::<MAX_LEN>()? };
         let mut col = BTreeMap::new();
         for _ in 0..len {
             let key = StrictDecode::strict_decode(reader)?;
@@ -642,3 +900,400 @@ impl<
         Confined::try_from(col).map_err(DecodeError::from)
     }
 }
+
+impl<C: Collection + Clone, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
+    for Cow<'_, Confined<C, MIN_LEN, MAX_LEN>>
+where Confined<C, MIN_LEN, MAX_LEN>: StrictType
+{
+    const STRICT_LIB_NAME: &'static str = Confined::<C, MIN_LEN, MAX_LEN>::STRICT_LIB_NAME;
+    const HAS_EXTENSIONS: bool = Confined::<C, MIN_LEN, MAX_LEN>::HAS_EXTENSIONS;
+    fn strict_name() -> Option<TypeName> { Confined::<C, MIN_LEN, MAX_LEN>::strict_name() }
+}
+impl<C: Collection + Clone, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
+    for Cow<'_, Confined<C, MIN_LEN, MAX_LEN>>
+where Confined<C, MIN_LEN, MAX_LEN>: StrictEncode
+{
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.as_ref().strict_encode(writer)
+    }
+}
+impl<C: Collection + Clone, const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
+    for Cow<'_, Confined<C, MIN_LEN, MAX_LEN>>
+where Confined<C, MIN_LEN, MAX_LEN>: StrictDecode
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        Confined::strict_decode(reader).map(Cow::Owned)
+    }
+}
+
+/// Composes the id of a built-in type from the name of its kind and the ids of
+/// the nested types (or other parameters, like collection bounds).
+pub(crate) const fn compose_id(kind: &str, ids: &[u64]) -> u64 {
+    let mut hasher = ConstHasher::new().write(LIB_EMBEDDED.as_bytes()).write(kind.as_bytes());
+    let mut i = 0;
+    while i < ids.len() {
+        hasher = hasher.write_u64(ids[i]);
+        i += 1;
+    }
+    hasher.finish()
+}
+
+macro_rules! strict_id_transparent {
+    ($ty:ident) => {
+        impl<T: StrictTypeId> StrictTypeId for $ty<T> {
+            const STRICT_ID: u64 = T::STRICT_ID;
+            const WIRE_FINGERPRINT: u64 = T::WIRE_FINGERPRINT;
+        }
+    };
+}
+
+strict_id_transparent!(Box);
+strict_id_transparent!(Wrapping);
+strict_id_transparent!(Saturating);
+
+impl StrictTypeId for Byte {
+    const STRICT_ID: u64 = compose_id("Byte", &[]);
+    const WIRE_FINGERPRINT: u64 = compose_id("Byte", &[]);
+}
+
+impl StrictTypeId for () {
+    const STRICT_ID: u64 = compose_id("()", &[]);
+    const WIRE_FINGERPRINT: u64 = compose_id("()", &[]);
+}
+
+impl<T: StrictTypeId> StrictTypeId for Option<T> {
+    const STRICT_ID: u64 = compose_id("Option", &[T::STRICT_ID]);
+    const WIRE_FINGERPRINT: u64 = compose_id("Option", &[T::WIRE_FINGERPRINT]);
+}
+
+macro_rules! strict_id_tuple {
+    ($($ty:ident),+) => {
+        impl<$($ty: StrictTypeId),+> StrictTypeId for ($($ty),+) {
+            const STRICT_ID: u64 = compose_id("Tuple", &[$($ty::STRICT_ID),+]);
+            const WIRE_FINGERPRINT: u64 = compose_id("Tuple", &[$($ty::WIRE_FINGERPRINT),+]);
+        }
+    };
+}
+
+strict_id_tuple!(A, B);
+strict_id_tuple!(A, B, C);
+strict_id_tuple!(A, B, C, D);
+strict_id_tuple!(A, B, C, D, E);
+strict_id_tuple!(A, B, C, D, E, F);
+strict_id_tuple!(A, B, C, D, E, F, G);
+strict_id_tuple!(A, B, C, D, E, F, G, H);
+strict_id_tuple!(A, B, C, D, E, F, G, H, I);
+strict_id_tuple!(A, B, C, D, E, F, G, H, I, J);
+strict_id_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+strict_id_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+impl<T: StrictTypeId, const LEN: usize> StrictTypeId for [T; LEN] {
+    const STRICT_ID: u64 = compose_id("Array", &[T::STRICT_ID, LEN as u64]);
+    const WIRE_FINGERPRINT: u64 = compose_id("Array", &[T::WIRE_FINGERPRINT, LEN as u64]);
+}
+impl<T: StrictTypeId + StrictDumb + Copy, const LEN: usize, const REVERSE_STR: bool> StrictTypeId
+    for Array<T, LEN, REVERSE_STR>
+{
+    const STRICT_ID: u64 = <[T; LEN]>::STRICT_ID;
+    const WIRE_FINGERPRINT: u64 = <[T; LEN]>::WIRE_FINGERPRINT;
+}
+
+// Boxed and borrowed slices share the ids with the confined vectors they are
+// encoded as.
+impl<T: StrictTypeId> StrictTypeId for Box<[T]> {
+    const STRICT_ID: u64 = LargeVec::<T>::STRICT_ID;
+    const WIRE_FINGERPRINT: u64 = LargeVec::<T>::WIRE_FINGERPRINT;
+}
+impl<T: StrictTypeId> StrictTypeId for &[T] {
+    const STRICT_ID: u64 = Confined::<Vec<T>, 0, U64>::STRICT_ID;
+    const WIRE_FINGERPRINT: u64 = Confined::<Vec<T>, 0, U64>::WIRE_FINGERPRINT;
+}
+impl StrictTypeId for &str {
+    const STRICT_ID: u64 = Confined::<String, 0, U64>::STRICT_ID;
+    const WIRE_FINGERPRINT: u64 = Confined::<String, 0, U64>::WIRE_FINGERPRINT;
+}
+
+impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictTypeId
+    for Confined<String, MIN_LEN, MAX_LEN>
+{
+    const STRICT_ID: u64 = compose_id("String", &[MIN_LEN as u64, MAX_LEN as u64]);
+    const WIRE_FINGERPRINT: u64 = Self::STRICT_ID;
+}
+impl<const MIN_LEN: usize, const MAX_LEN: usize> StrictTypeId
+    for Confined<AsciiString, MIN_LEN, MAX_LEN>
+{
+    const STRICT_ID: u64 = compose_id("AsciiString", &[MIN_LEN as u64, MAX_LEN as u64]);
+    const WIRE_FINGERPRINT: u64 = Self::STRICT_ID;
+}
+
+// `VecDeque` is encoded in the same way as `Vec`
+impl<T: StrictTypeId, const MIN_LEN: usize, const MAX_LEN: usize> StrictTypeId
+    for Confined<Vec<T>, MIN_LEN, MAX_LEN>
+{
+    const STRICT_ID: u64 = compose_id("List", &[T::STRICT_ID, MIN_LEN as u64, MAX_LEN as u64]);
+    const WIRE_FINGERPRINT: u64 =
+        compose_id("List", &[T::WIRE_FINGERPRINT, MIN_LEN as u64, MAX_LEN as u64]);
+}
+impl<T: StrictTypeId, const MIN_LEN: usize, const MAX_LEN: usize> StrictTypeId
+    for Confined<VecDeque<T>, MIN_LEN, MAX_LEN>
+{
+    const STRICT_ID: u64 = Confined::<Vec<T>, MIN_LEN, MAX_LEN>::STRICT_ID;
+    const WIRE_FINGERPRINT: u64 = Confined::<Vec<T>, MIN_LEN, MAX_LEN>::WIRE_FINGERPRINT;
+}
+impl<T: StrictTypeId + Ord, const MIN_LEN: usize, const MAX_LEN: usize> StrictTypeId
+    for Confined<BTreeSet<T>, MIN_LEN, MAX_LEN>
+{
+    const STRICT_ID: u64 = compose_id("Set", &[T::STRICT_ID, MIN_LEN as u64, MAX_LEN as u64]);
+    const WIRE_FINGERPRINT: u64 =
+        compose_id("Set", &[T::WIRE_FINGERPRINT, MIN_LEN as u64, MAX_LEN as u64]);
+}
+impl<K: StrictTypeId + Ord + Hash, V: StrictTypeId, const MIN_LEN: usize, const MAX_LEN: usize>
+    StrictTypeId for Confined<BTreeMap<K, V>, MIN_LEN, MAX_LEN>
+{
+    const STRICT_ID: u64 =
+        compose_id("Map", &[K::STRICT_ID, V::STRICT_ID, MIN_LEN as u64, MAX_LEN as u64]);
+    const WIRE_FINGERPRINT: u64 = compose_id("Map", &[
+        K::WIRE_FINGERPRINT,
+        V::WIRE_FINGERPRINT,
+        MIN_LEN as u64,
+        MAX_LEN as u64,
+    ]);
+}
+impl<C: Collection + Clone, const MIN_LEN: usize, const MAX_LEN: usize> StrictTypeId
+    for Cow<'_, Confined<C, MIN_LEN, MAX_LEN>>
+where Confined<C, MIN_LEN, MAX_LEN>: StrictTypeId
+{
+    const STRICT_ID: u64 = Confined::<C, MIN_LEN, MAX_LEN>::STRICT_ID;
+    const WIRE_FINGERPRINT: u64 = Confined::<C, MIN_LEN, MAX_LEN>::WIRE_FINGERPRINT;
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::panic;
+
+    use amplify::confinement::{SmallVec, TinyBlob, TinyString, U16};
+
+    use super::*;
+    use crate::test::{decode, encode, encoding};
+    use crate::StrictReader;
+
+    thread_local! {
+        static DROPPED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Neither `Copy` nor `StrictDumb`; counts its drops and panics when
+    /// decoded from `0xFF`.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Tracked(String);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) { DROPPED.with(|dropped| dropped.set(dropped.get() + 1)) }
+    }
+
+    impl StrictType for Tracked {
+        const STRICT_LIB_NAME: &'static str = "Test";
+    }
+    impl StrictDecode for Tracked {
+        fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+            let byte = u8::strict_decode(reader)?;
+            assert_ne!(byte, 0xFF, "decoding a poisoned item");
+            Ok(Tracked(byte.to_string()))
+        }
+    }
+
+    #[test]
+    fn tuple_larger_arity() {
+        assert_eq!(<(u8, u16, u8, u8, u8)>::FIELD_COUNT, 5);
+        encoding(&(1u8, 2u16, 3u8, 4u8, 5u8), [1, 2, 0, 3, 4, 5]);
+        encoding(&(1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 0x0C0Bu16), [
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 11, 12,
+        ]);
+        assert_eq!(<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)>::FIELD_COUNT, 12);
+    }
+
+    #[test]
+    fn tuple_non_default() {
+        let one = NonZeroU8::new(1).unwrap();
+        encoding(&(one, 2u16), [1, 2, 0]);
+        encoding(&(3u8, one, 4u8), [3, 1, 4]);
+        encoding(&(one, one, one, 5u8), [1, 1, 1, 5]);
+        assert!(
+            <(u8, NonZeroU8)>::strict_decode(&mut StrictReader::in_memory::<8>([1, 0])).is_err()
+        );
+    }
+
+    #[test]
+    fn boxed_slice() {
+        let data = [3, 0, 0, 0, 1, 0, 2, 0, 0, 3];
+        encoding(&Box::<[u16]>::from([1u16, 2, 0x300]), data);
+        encoding(&LargeVec::try_from(vec![1u16, 2, 0x300]).unwrap(), data);
+
+        encoding(&Box::<[u8]>::from(&b"ab"[..]), [2, 0, 0, 0, b'a', b'b']);
+        encoding(&Box::<[u8]>::default(), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn wrapping_saturating() {
+        encoding(&Wrapping(0x0102030405060708u64), [8, 7, 6, 5, 4, 3, 2, 1]);
+        encoding(&Saturating(-2i16), [0xFE, 0xFF]);
+        encoding(&(Wrapping(1u8), Saturating(2u32)), [1, 2, 0, 0, 0]);
+
+        assert_eq!(Wrapping::<u64>::strict_name(), u64::strict_name());
+        assert_eq!(Saturating::<u8>::strict_name(), u8::strict_name());
+        assert_eq!(Wrapping::<u64>::STRICT_LIB_NAME, LIB_EMBEDDED);
+        assert_eq!(Wrapping::<u16>::strict_dumb(), Wrapping(0));
+    }
+
+    #[test]
+    fn bound() {
+        encoding(&Bound::<u64>::Unbounded, [0]);
+        encoding(&Bound::Included(5u64), [1, 5, 0, 0, 0, 0, 0, 0, 0]);
+        encoding(&Bound::Excluded(u64::MAX), [2, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+        encoding(&(Bound::Included(1u8), Bound::<u8>::Unbounded), [1, 1, 0]);
+
+        assert_eq!(Bound::<u64>::strict_name(), None);
+        assert_eq!(Bound::Excluded(1u8).variant_name(), "excluded");
+        let mut reader = StrictReader::in_memory::<8>([3, 0]);
+        assert_eq!(
+            Bound::<u8>::strict_decode(&mut reader).unwrap_err(),
+            DecodeError::UnionTagNotKnown(s!("__unnamed"), 3)
+        );
+    }
+
+    #[test]
+    fn wrapping_saturating_bigint() {
+        encoding(&Saturating(u256::MAX), [0xFF; 32]);
+        encoding(&Wrapping(u256::MAX), [0xFF; 32]);
+        encoding(&Saturating(i256::MAX), encode(&i256::MAX));
+
+        assert_eq!(Saturating::<u256>::strict_name(), u256::strict_name());
+        assert_eq!(Wrapping::<i256>::strict_name(), i256::strict_name());
+        assert_eq!(Saturating::<u256>::strict_dumb(), Saturating(u256::ZERO));
+    }
+
+    #[test]
+    fn borrowed_slice() {
+        let vec = vec![1u16, 2, 0x300];
+        let data = encode(&vec.as_slice());
+        assert_eq!(data, [3, 0, 0, 0, 0, 0, 0, 0, 1, 0, 2, 0, 0, 3]);
+        assert_eq!(data, encode(&Confined::<Vec<u16>, 0, U64>::try_from(vec).unwrap()));
+
+        let bytes: &[u8] = b"ab";
+        assert_eq!(encode(&bytes), [2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b']);
+        assert_eq!(encode(&<&[u8]>::default()), [0u8; 8]);
+    }
+
+    #[test]
+    fn byte_array() {
+        let mut array = [0u8; 64];
+        array.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+        // Arrays of `Byte` are still written byte by byte
+        let bytes = array.map(Byte::from);
+        assert_eq!(encode(&array), array);
+        assert_eq!(encode(&array), encode(&bytes));
+        encoding(&array, array);
+        encoding(&bytes, array);
+
+        encoding(&[0xABu8; 1], [0xAB]);
+        encoding(&[0u8; 0], []);
+        let mut reader = crate::StrictReader::in_memory::<64>([1u8, 2, 3]);
+        assert!(<[u8; 4]>::strict_decode(&mut reader).is_err());
+    }
+
+    #[test]
+    fn cow_confined() {
+        let vec = SmallVec::try_from(vec![1u16, 2, 0x300]).unwrap();
+        let data = encode(&vec);
+        assert_eq!(encode(&Cow::Borrowed(&vec)), data);
+        assert_eq!(encode(&Cow::<SmallVec<u16>>::Owned(vec.clone())), data);
+
+        let cow: Cow<SmallVec<u16>> = decode(&data);
+        assert!(matches!(cow, Cow::Owned(ref owned) if owned == &vec));
+        encoding(&Cow::Borrowed(&vec), &data);
+
+        let s = TinyString::try_from(s!("cow")).unwrap();
+        encoding(&Cow::Borrowed(&s), [3, b'c', b'o', b'w']);
+        assert_eq!(Cow::<TinyString>::strict_name(), None);
+        assert_eq!(Cow::<TinyString>::STRICT_LIB_NAME, LIB_EMBEDDED);
+    }
+
+    #[test]
+    fn str_slice() {
+        let s = Confined::<String, 0, U64>::try_from(s!("hello")).unwrap();
+        assert_eq!("hello".to_strict_serialized::<16>().unwrap().release(), encode(&s),);
+        assert_eq!(encode(&"hello"), [5, 0, 0, 0, 0, 0, 0, 0, b'h', b'e', b'l', b'l', b'o']);
+        assert_eq!(encode(&""), [0; 8]);
+        let decoded: Confined<String, 0, U64> = decode(encode(&"hello"));
+        assert_eq!(decoded, s);
+    }
+
+    #[test]
+    fn string_normalization() {
+        let composed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+        let string = TinyString::try_from(decomposed.to_owned()).unwrap();
+        // Written by hand, since the encoding of a non-normalized string fails
+        let mut data = vec![decomposed.len() as u8];
+        data.extend(decomposed.as_bytes());
+        let mut reader = StrictReader::in_memory::<16>(data.clone());
+        let res = TinyString::strict_decode(&mut reader);
+        #[cfg(feature = "unicode-normalization")]
+        {
+            assert_eq!(res.unwrap_err(), DecodeError::NotNormalized(decomposed.to_owned()));
+            let writer = || crate::StrictWriter::in_memory::<16>();
+            let err = string.strict_encode(writer()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert!(decomposed.strict_encode(writer()).is_err());
+            assert!(std::path::PathBuf::from(decomposed).strict_encode(writer()).is_err());
+        }
+        #[cfg(not(feature = "unicode-normalization"))]
+        {
+            assert_eq!(res.unwrap().as_str(), decomposed);
+            assert_eq!(encode(&string), data);
+        }
+
+        encoding(&TinyString::try_from(composed.to_owned()).unwrap(), [
+            5, b'c', b'a', b'f', 0xC3, 0xA9,
+        ]);
+    }
+
+    #[test]
+    fn byte_vec() {
+        // Byte vectors are written at once, while deques still go byte by byte
+        for len in [0usize, 1, 255, 256, 0x1234] {
+            let bytes = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let vec = SmallVec::try_from(bytes.clone()).unwrap();
+            let deque = Confined::<VecDeque<u8>, 0, U16>::try_from(VecDeque::from(bytes)).unwrap();
+            assert_eq!(encode(&vec), encode(&deque));
+            encoding(&vec, encode(&deque));
+        }
+        encoding(&TinyBlob::try_from(vec![1, 2, 3]).unwrap(), [3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn array_non_copy() {
+        let decode_array = |data: &[u8]| {
+            <[Tracked; 3]>::strict_decode(&mut StrictReader::in_memory::<8>(data.to_vec()))
+        };
+
+        let array = decode_array(&[1, 2, 3]).unwrap();
+        assert_eq!(array, [Tracked(s!("1")), Tracked(s!("2")), Tracked(s!("3"))]);
+        drop(array);
+
+        DROPPED.with(|dropped| dropped.set(0));
+        assert_eq!(decode_array(&[1, 2]).unwrap_err(), DecodeError::UnexpectedEof);
+        assert_eq!(DROPPED.with(Cell::get), 2);
+    }
+
+    #[test]
+    fn array_panic_safety() {
+        DROPPED.with(|dropped| dropped.set(0));
+        let result = panic::catch_unwind(|| {
+            <[Tracked; 4]>::strict_decode(&mut StrictReader::in_memory::<8>([1, 2, 0xFF, 4]))
+        });
+        assert!(result.is_err());
+        // Both items decoded before the panic are dropped exactly once
+        assert_eq!(DROPPED.with(Cell::get), 2);
+    }
+}
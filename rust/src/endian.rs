@@ -0,0 +1,151 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Big-endian encoding of numbers for interoperability with the formats which
+//! mandate it.
+//!
+//! Strict encoding is canonically little-endian, and data encoded with
+//! [`BigEndian`] are not strict-encoded: they can't be decoded by other strict
+//! encoding readers, and their hashes and commitments differ from the ones of
+//! the canonical encoding. This is purely an escape hatch for interoperability
+//! and must not be mixed with the canonical little-endian encoding.
+
+use std::io;
+
+use crate::{RawBuffer, ReadRaw, WriteRaw};
+
+/// Raw writer or reader adaptor, which writes and reads all numbers
+/// (integers, floats and collection lengths) in big-endian byte order. Other
+/// data, like strings, bytes and byte arrays, are not affected.
+///
+/// `StrictWriter::with(BigEndian::new(StreamWriter::in_memory::<MAX>()))`
+/// constructs a big-endian in-memory writer, and
+/// `StrictReader::with(BigEndian::new(StreamReader::in_memory::<MAX>(data)))`
+/// constructs the matching reader.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct BigEndian<T>(T);
+
+impl<T> BigEndian<T> {
+    pub fn new(inner: T) -> Self { Self(inner) }
+    pub fn as_inner(&self) -> &T { &self.0 }
+    pub fn into_inner(self) -> T { self.0 }
+}
+
+impl<W: WriteRaw> WriteRaw for BigEndian<W> {
+    fn write_raw<const MAX_LEN: usize>(&mut self, bytes: impl AsRef<[u8]>) -> io::Result<()> {
+        self.0.write_raw::<MAX_LEN>(bytes)
+    }
+    fn write_raw_array<const LEN: usize>(&mut self, raw: [u8; LEN]) -> io::Result<()> {
+        self.0.write_raw_array(raw)
+    }
+    fn write_raw_num<const LEN: usize>(&mut self, mut le_bytes: [u8; LEN]) -> io::Result<()> {
+        le_bytes.reverse();
+        self.0.write_raw_array(le_bytes)
+    }
+    fn raw_buffer(&self) -> RawBuffer { self.0.raw_buffer().reverse_nums() }
+}
+
+impl<R: ReadRaw> ReadRaw for BigEndian<R> {
+    fn read_raw<const MAX_LEN: usize>(&mut self, len: usize) -> io::Result<Vec<u8>> {
+        self.0.read_raw::<MAX_LEN>(len)
+    }
+    fn read_raw_array<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]> {
+        self.0.read_raw_array::<LEN>()
+    }
+    fn read_raw_into(&mut self, buf: &mut Vec<u8>, len: usize) -> io::Result<()> {
+        self.0.read_raw_into(buf, len)
+    }
+    fn read_raw_num<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]> {
+        let mut bytes = self.0.read_raw_array::<LEN>()?;
+        bytes.reverse();
+        Ok(bytes)
+    }
+    fn read_raw_to_end(&mut self) -> io::Result<Vec<u8>> { self.0.read_raw_to_end() }
+    fn raw_count(&self) -> Option<usize> { self.0.raw_count() }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::{SmallVec, TinyString};
+
+    use super::*;
+    use crate::test::encode;
+    use crate::{
+        StreamReader, StreamWriter, StrictDecode, StrictDumb, StrictEncode, StrictReader,
+        StrictType, StrictWriter,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "Test", tags = order, dumb = Payload::Empty, crate = crate)]
+    enum Payload {
+        Empty,
+        Amount(i64),
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "Test", crate = crate)]
+    struct Record {
+        id: u16,
+        name: TinyString,
+        hash: [u8; 2],
+        values: SmallVec<u32>,
+        payload: Option<Payload>,
+    }
+
+    fn record() -> Record {
+        Record {
+            id: 0x0102,
+            name: tiny_s!("ab"),
+            hash: [0xAA, 0xBB],
+            values: small_vec![0x03040506],
+            payload: Some(Payload::Amount(-2)),
+        }
+    }
+
+    #[test]
+    fn big_endian() {
+        let rec = record();
+        #[rustfmt::skip]
+        assert_eq!(encode(&rec), [
+            0x02, 0x01,
+            2, b'a', b'b',
+            0xAA, 0xBB,
+            1, 0, 0x06, 0x05, 0x04, 0x03,
+            1, 1, 0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ]);
+
+        let writer = StrictWriter::with(BigEndian::new(StreamWriter::in_memory::<64>()));
+        let data = rec.strict_encode(writer).unwrap().unbox().into_inner().unconfine();
+        #[rustfmt::skip]
+        assert_eq!(data, [
+            0x01, 0x02,
+            2, b'a', b'b',
+            0xAA, 0xBB,
+            0, 1, 0x03, 0x04, 0x05, 0x06,
+            1, 1, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE,
+        ]);
+
+        let mut reader = StrictReader::with(BigEndian::new(StreamReader::in_memory::<64>(&data)));
+        assert_eq!(Record::strict_decode(&mut reader).unwrap(), rec);
+    }
+}
@@ -28,9 +28,15 @@ use amplify::{confinement, IoError};
 #[display(doc_comments)]
 pub enum DecodeError {
     #[display(inner)]
-    #[from(io::Error)]
     Io(IoError),
 
+    /// transient I/O error, the operation may be retried once more data are
+    /// available. Details: {0}
+    Retryable(IoError),
+
+    /// data end before the value is completely read.
+    UnexpectedEof,
+
     /// confinement requirements are not satisfied. Specifically, {0}
     #[from]
     Confinement(confinement::Error),
@@ -72,6 +78,46 @@ pub enum DecodeError {
 
     /// data integrity problem during strict decoding operation.\nDetails: {0}
     DataIntegrityError(String),
+
+    /// unused high bits of a bit-width integer are not zero
+    NonZeroPadding,
+
+    /// value {0:#x} is not a valid unicode scalar value
+    InvalidCharCode(u32),
+
+    /// nesting of tuples, structures and unions exceeds the depth limit of {0}
+    RecursionLimit(usize),
+
+    /// {0} bytes are requested while only {1} bytes remain in the input
+    InsufficientData(usize, usize),
+
+    /// string '{0}' is not in Unicode normalization form C (NFC).
+    ///
+    /// Returned only with `unicode-normalization` feature.
+    NotNormalized(String),
+}
+
+impl DecodeError {
+    /// Whether the error is caused by the data ending before the value is
+    /// completely read. This is reported as [`DecodeError::UnexpectedEof`],
+    /// or as [`DecodeError::InsufficientData`] by the zero-copy reads of the
+    /// in-memory readers, which check the length against the remaining data
+    /// in advance.
+    pub fn is_eof(&self) -> bool {
+        matches!(self, DecodeError::UnexpectedEof | DecodeError::InsufficientData(..))
+    }
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => {
+                DecodeError::Retryable(err.into())
+            }
+            io::ErrorKind::UnexpectedEof => DecodeError::UnexpectedEof,
+            _ => DecodeError::Io(err.into()),
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
@@ -96,4 +142,9 @@ pub enum DeserializeError {
 
     /// data are not entirely consumed during strict deserialize operation
     DataNotEntirelyConsumed,
+
+    /// invalid hex-encoded data: {0}
+    #[cfg(feature = "hex")]
+    #[from]
+    InvalidHex(amplify::hex::Error),
 }
@@ -0,0 +1,177 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Binary heaps, which are encoded as lists of their elements in ascending
+//! order.
+
+use std::collections::BinaryHeap;
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+use amplify::confinement::{Collection, Confined};
+
+use crate::{
+    Byte, DecodeError, Sizing, StrictDecode, StrictDumb, StrictEncode, StrictType, TypeName,
+    TypedRead, TypedWrite, LIB_EMBEDDED,
+};
+
+/// Confined binary heap, see [`Heap`].
+pub type ConfinedHeap<T, const MIN_LEN: usize, const MAX_LEN: usize> =
+    Confined<Heap<T>, MIN_LEN, MAX_LEN>;
+
+/// Binary heap, which can be used with collection confinement.
+///
+/// The elements of `Confined<Heap<T>, MIN_LEN, MAX_LEN>` are encoded as a
+/// list in ascending order, so the encoding is canonical regardless of the
+/// internal heap layout. The decoding fails with
+/// [`DecodeError::BrokenSetOrder`] if the elements are not sorted. Only the
+/// elements of the heap are preserved, but not its internal order.
+#[derive(Clone, Debug)]
+pub struct Heap<T>(BinaryHeap<T>);
+
+impl<T: Ord> Default for Heap<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T: Ord> Heap<T> {
+    pub fn new() -> Self { Self(BinaryHeap::new()) }
+}
+
+impl<T> Heap<T> {
+    pub fn into_inner(self) -> BinaryHeap<T> { self.0 }
+}
+
+impl<T> From<BinaryHeap<T>> for Heap<T> {
+    fn from(inner: BinaryHeap<T>) -> Self { Self(inner) }
+}
+
+impl<T> Deref for Heap<T> {
+    type Target = BinaryHeap<T>;
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T> DerefMut for Heap<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+impl<T: Ord> FromIterator<T> for Heap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self { Self(BinaryHeap::from_iter(iter)) }
+}
+
+impl<T: Ord> Extend<T> for Heap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) { self.0.extend(iter) }
+}
+
+impl<T> IntoIterator for Heap<T> {
+    type Item = T;
+    type IntoIter = std::collections::binary_heap::IntoIter<T>;
+
+    /// Iterates the elements in arbitrary order.
+    fn into_iter(self) -> Self::IntoIter { self.0.into_iter() }
+}
+
+impl<T: Ord> Collection for Heap<T> {
+    type Item = T;
+
+    fn with_capacity(capacity: usize) -> Self { Self(BinaryHeap::with_capacity(capacity)) }
+
+    fn len(&self) -> usize { self.0.len() }
+
+    fn push(&mut self, elem: Self::Item) { self.0.push(elem) }
+
+    fn clear(&mut self) { self.0.clear() }
+}
+
+impl<T: StrictType + Ord, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
+    for Confined<Heap<T>, MIN_LEN, MAX_LEN>
+{
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    fn strict_name() -> Option<TypeName> { None }
+}
+impl<T: StrictEncode + Ord + StrictDumb, const MIN_LEN: usize, const MAX_LEN: usize> StrictEncode
+    for Confined<Heap<T>, MIN_LEN, MAX_LEN>
+{
+    fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort();
+        let sizing = Sizing::new(MIN_LEN as u64, MAX_LEN as u64);
+        writer = unsafe {
+            writer = writer.write_slice::<&T, MAX_LEN>(&items)?;
+            if T::strict_name() == u8::strict_name() {
+                writer.register_list(&Byte::strict_dumb(), sizing)
+            } else {
+                writer.register_list(&T::strict_dumb(), sizing)
+            }
+        };
+        Ok(writer)
+    }
+}
+impl<T: StrictDecode + Ord, const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
+    for Confined<Heap<T>, MIN_LEN, MAX_LEN>
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let len = unsafe { reader.read_collection_len::<MAX_LEN>()? };
+        let mut items = Vec::<T>::with_capacity(len);
+        for _ in 0..len {
+            let item = StrictDecode::strict_decode(reader)?;
+            if matches!(items.last(), Some(last) if last > &item) {
+                return Err(DecodeError::BrokenSetOrder);
+            }
+            items.push(item);
+        }
+        Confined::try_from(Heap(BinaryHeap::from(items))).map_err(DecodeError::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{StrictReader, StrictWriter};
+
+    fn encode<T: StrictEncode>(val: &T) -> Vec<u8> {
+        val.strict_encode(StrictWriter::in_memory::<16>()).unwrap().unbox().unconfine()
+    }
+
+    #[test]
+    fn heap_roundtrip() {
+        let heap = ConfinedHeap::<u16, 0, 8>::try_from_iter([3u16, 0x100, 1, 3]).unwrap();
+        let list = Confined::<Vec<u16>, 0, 8>::try_from(vec![1, 3, 3, 0x100]).unwrap();
+        let data = encode(&heap);
+        assert_eq!(data, [4, 1, 0, 3, 0, 3, 0, 0, 1]);
+        assert_eq!(data, encode(&list));
+
+        let mut reader = StrictReader::in_memory::<16>(data);
+        let decoded = ConfinedHeap::<u16, 0, 8>::strict_decode(&mut reader).unwrap();
+        assert_eq!(decoded.peek(), Some(&0x100));
+        assert_eq!(decoded.release().into_inner().into_sorted_vec(), list.release());
+
+        assert_eq!(encode(&ConfinedHeap::<u8, 0, 4>::default()), [0]);
+    }
+
+    #[test]
+    fn heap_unsorted() {
+        let mut reader = StrictReader::in_memory::<8>([2u8, 5, 4]);
+        assert_eq!(
+            ConfinedHeap::<u8, 0, 4>::strict_decode(&mut reader).unwrap_err(),
+            DecodeError::BrokenSetOrder
+        );
+    }
+}
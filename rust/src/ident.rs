@@ -21,10 +21,10 @@
 
 use std::str::FromStr;
 
-use amplify::Wrapper;
+use amplify::{confinement, Wrapper};
 
 use crate::stl::{AlphaCapsLodash, AlphaLodash, AlphaNumLodash, AlphaSmallLodash};
-use crate::{RString, STRICT_TYPES_LIB};
+use crate::{InvalidRString, RString, STRICT_TYPES_LIB};
 
 pub const IDENT_MAX_LEN: usize = 100;
 
@@ -65,6 +65,13 @@ macro_rules! impl_ident_type {
         }
 
         impl $ty {
+            /// Constructs the name from a string, checking it against the
+            /// charset and the maximal length of the name. Unlike
+            /// constructing from a string literal, never panics.
+            pub fn try_new(s: &str) -> Result<Self, $crate::InvalidIdent> {
+                Self::from_str(s).map_err($crate::InvalidIdent::from)
+            }
+
             /// Returns string reference.
             #[inline]
             pub fn as_str(&self) -> &str { self.0.as_str() }
@@ -72,6 +79,52 @@ macro_rules! impl_ident_type {
     };
 }
 
+/// Errors of identifier and name validation.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum InvalidIdent {
+    /// identifier must contain at least one character.
+    Empty,
+
+    /// identifier of {len} characters is shorter than the minimal length of
+    /// {min_len} characters.
+    TooShort { len: usize, min_len: usize },
+
+    /// identifier of {len} characters exceeds the maximal length of
+    /// {max_len} characters.
+    TooLong { len: usize, max_len: usize },
+
+    /// identifier '{0}' must not start with character '{1}'.
+    DisallowedFirst(String, char),
+
+    /// identifier '{0}' contains invalid character '{1}' at position {2}.
+    InvalidChar(String, char, usize),
+
+    /// identifier contains non-ASCII character(s).
+    NonAsciiChar,
+
+    /// identifier violates confinement: {0}.
+    Confinement(confinement::Error),
+}
+
+impl From<InvalidRString> for InvalidIdent {
+    fn from(err: InvalidRString) -> Self {
+        match err {
+            InvalidRString::Empty => InvalidIdent::Empty,
+            InvalidRString::DisallowedFirst(s, ch) => InvalidIdent::DisallowedFirst(s, ch),
+            InvalidRString::InvalidChar(s, ch, pos) => InvalidIdent::InvalidChar(s, ch, pos),
+            InvalidRString::Confinement(confinement::Error::Undersize { len, min_len }) => {
+                InvalidIdent::TooShort { len, min_len }
+            }
+            InvalidRString::Confinement(confinement::Error::Oversize { len, max_len }) => {
+                InvalidIdent::TooLong { len, max_len }
+            }
+            InvalidRString::NonAsciiChar => InvalidIdent::NonAsciiChar,
+            InvalidRString::Confinement(err) => InvalidIdent::Confinement(err),
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! impl_ident_subtype {
     ($ty:ty) => {
@@ -156,3 +209,44 @@ pub struct LibName(RString<AlphaCapsLodash, AlphaNumLodash, 1, IDENT_MAX_LEN>);
 impl_ident_type!(LibName);
 impl_ident_subtype!(LibName);
 impl_strict_newtype!(LibName, STRICT_TYPES_LIB);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn try_new() {
+        assert_eq!(Ident::try_new("some_Name1").unwrap(), Ident::from("some_Name1"));
+        assert_eq!(TypeName::try_new("Name").unwrap(), tn!("Name"));
+        assert_eq!(FieldName::try_new("name").unwrap(), fname!("name"));
+        assert_eq!(LibName::try_new("Lib").unwrap(), libname!("Lib"));
+        assert_eq!(VariantName::try_new("some").unwrap(), vname!("some"));
+
+        assert_eq!(Ident::try_new(""), Err(InvalidIdent::Empty));
+        assert_eq!(
+            Ident::try_new(&"a".repeat(IDENT_MAX_LEN + 1)),
+            Err(InvalidIdent::TooLong {
+                len: IDENT_MAX_LEN + 1,
+                max_len: IDENT_MAX_LEN
+            })
+        );
+        assert!(Ident::try_new(&"a".repeat(IDENT_MAX_LEN)).is_ok());
+        assert_eq!(Ident::try_new("1a"), Err(InvalidIdent::DisallowedFirst(s!("1a"), '1')));
+        assert_eq!(TypeName::try_new("name"), Err(InvalidIdent::DisallowedFirst(s!("name"), 'n')));
+        assert_eq!(Ident::try_new("ab-c"), Err(InvalidIdent::InvalidChar(s!("ab-c"), '-', 2)));
+        assert_eq!(FieldName::try_new("nämé"), Err(InvalidIdent::InvalidChar(s!("nämé"), 'ä', 1)));
+        assert_eq!(
+            Ident::try_new("ab-c").unwrap_err().to_string(),
+            "identifier 'ab-c' contains invalid character '-' at position 2."
+        );
+
+        // Errors of restricted strings beyond the identifier validation are mapped without
+        // panicking
+        assert_eq!(InvalidIdent::from(InvalidRString::NonAsciiChar), InvalidIdent::NonAsciiChar);
+        let err = confinement::Error::OutOfBoundary { index: 3, len: 2 };
+        assert_eq!(
+            InvalidIdent::from(InvalidRString::Confinement(err)),
+            InvalidIdent::Confinement(err)
+        );
+    }
+}
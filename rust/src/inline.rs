@@ -0,0 +1,177 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Confined collections stored inline, without heap allocations, which are
+//! intended for small collections frequently decoded on hot paths.
+
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+use amplify::confinement::{self, Collection, Confined};
+use arrayvec::ArrayVec;
+
+use crate::{
+    Byte, DecodeError, Sizing, StrictDecode, StrictDumb, StrictEncode, StrictType, TypeName,
+    TypedRead, TypedWrite, LIB_EMBEDDED,
+};
+
+/// Vector with a fixed capacity `CAP`, keeping items inline (on the stack),
+/// which can be used with collection confinement as an alternative for `Vec`.
+///
+/// `Confined<InlineVec<T, CAP>, MIN_LEN, MAX_LEN>` has exactly the same strict
+/// encoding as `Confined<Vec<T>, MIN_LEN, MAX_LEN>`, so both types are
+/// interchangeable. `MAX_LEN` must not exceed `CAP`; otherwise decoding of
+/// data with more than `CAP` items fails.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct InlineVec<T, const CAP: usize>(ArrayVec<T, CAP>);
+
+impl<T, const CAP: usize> InlineVec<T, CAP> {
+    pub fn new() -> Self { Self(ArrayVec::new()) }
+
+    pub fn into_inner(self) -> ArrayVec<T, CAP> { self.0 }
+}
+
+impl<T, const CAP: usize> From<ArrayVec<T, CAP>> for InlineVec<T, CAP> {
+    fn from(inner: ArrayVec<T, CAP>) -> Self { Self(inner) }
+}
+
+impl<T, const CAP: usize> Deref for InlineVec<T, CAP> {
+    type Target = ArrayVec<T, CAP>;
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T, const CAP: usize> DerefMut for InlineVec<T, CAP> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+impl<T, const CAP: usize> FromIterator<T> for InlineVec<T, CAP> {
+    /// # Panics
+    ///
+    /// If the iterator yields more than `CAP` items.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self { Self(ArrayVec::from_iter(iter)) }
+}
+
+impl<T, const CAP: usize> Extend<T> for InlineVec<T, CAP> {
+    /// # Panics
+    ///
+    /// If the vector capacity is exceeded.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) { self.0.extend(iter) }
+}
+
+impl<T, const CAP: usize> IntoIterator for InlineVec<T, CAP> {
+    type Item = T;
+    type IntoIter = arrayvec::IntoIter<T, CAP>;
+
+    fn into_iter(self) -> Self::IntoIter { self.0.into_iter() }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a InlineVec<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.0.iter() }
+}
+
+impl<T, const CAP: usize> Collection for InlineVec<T, CAP> {
+    type Item = T;
+
+    /// Capacity is fixed, so the argument is ignored.
+    fn with_capacity(_capacity: usize) -> Self { Self::new() }
+
+    fn len(&self) -> usize { self.0.len() }
+
+    /// # Panics
+    ///
+    /// If the vector capacity is exceeded.
+    fn push(&mut self, elem: Self::Item) { self.0.push(elem) }
+
+    fn clear(&mut self) { self.0.clear() }
+}
+
+impl<T: StrictType, const CAP: usize, const MIN_LEN: usize, const MAX_LEN: usize> StrictType
+    for Confined<InlineVec<T, CAP>, MIN_LEN, MAX_LEN>
+{
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    fn strict_name() -> Option<TypeName> { None }
+}
+impl<
+        T: StrictEncode + StrictDumb,
+        const CAP: usize,
+        const MIN_LEN: usize,
+        const MAX_LEN: usize,
+    > StrictEncode for Confined<InlineVec<T, CAP>, MIN_LEN, MAX_LEN>
+{
+    fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
+        let sizing = Sizing::new(MIN_LEN as u64, MAX_LEN as u64);
+        writer = unsafe {
+            writer = writer.write_collection::<InlineVec<T, CAP>, MIN_LEN, MAX_LEN>(self)?;
+            if T::strict_name() == u8::strict_name() {
+                writer.register_list(&Byte::strict_dumb(), sizing)
+            } else {
+                writer.register_list(&T::strict_dumb(), sizing)
+            }
+        };
+        Ok(writer)
+    }
+}
+impl<T: StrictDecode, const CAP: usize, const MIN_LEN: usize, const MAX_LEN: usize> StrictDecode
+    for Confined<InlineVec<T, CAP>, MIN_LEN, MAX_LEN>
+{
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let len = unsafe { reader.read_collection_len::<MAX_LEN>()? };
+        if len > CAP {
+            return Err(confinement::Error::Oversize { len, max_len: CAP }.into());
+        }
+        let mut col = InlineVec::<T, CAP>::new();
+        for _ in 0..len {
+            col.push(StrictDecode::strict_decode(reader)?);
+        }
+        Confined::try_from(col).map_err(DecodeError::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encoding;
+    use crate::StrictReader;
+
+    #[test]
+    fn inline_vec_wire_format() {
+        let inline = Confined::<InlineVec<u16, 8>, 0, 8>::try_from_iter([1u16, 2, 0x300]).unwrap();
+        let heap = Confined::<Vec<u16>, 0, 8>::try_from_iter([1u16, 2, 0x300]).unwrap();
+        let data = [3, 1, 0, 2, 0, 0, 3];
+        encoding(&inline, data);
+        encoding(&heap, data);
+
+        encoding(&Confined::<InlineVec<u8, 4>, 0, 4>::try_from_iter([7u8]).unwrap(), [1, 7]);
+        encoding(&Confined::<InlineVec<u8, 4>, 0, 4>::default(), [0]);
+    }
+
+    #[test]
+    fn inline_vec_oversize() {
+        let mut reader = StrictReader::in_memory::<8>([5u8, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            Confined::<InlineVec<u8, 4>, 0, 255>::strict_decode(&mut reader).unwrap_err(),
+            DecodeError::Confinement(confinement::Error::Oversize { len: 5, max_len: 4 })
+        );
+    }
+}
@@ -0,0 +1,1397 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Canonical JSON projection of strict types, used as an interop format with
+//! systems which are unable to process strict binary encoding.
+//!
+//! The projection is produced by walking the same [`TypedWrite`] calls which
+//! are used for the binary encoding, and it is read back with [`TypedRead`],
+//! so no additional serialization code is required for the strict types. It
+//! is not a replacement for the binary format: the JSON projection has no
+//! determinism guarantees beyond being produced by this library.
+//!
+//! The values are mapped as follows:
+//! - integers up to 64 bits (including bytes) are JSON numbers; other primitives (floats, large
+//!   integers) are lower-case hex strings of their little-endian binary representation;
+//! - unicode, ASCII and restricted strings are JSON strings;
+//! - byte lists and byte arrays are lower-case hex strings;
+//! - other lists, sets and fixed-size arrays are JSON arrays;
+//! - maps are JSON arrays of key-value pairs, each being a two-item array;
+//! - structures are JSON objects with keys matching strict field names;
+//! - tuples are JSON arrays of their fields, while newtypes (single-field tuples) are represented
+//!   by their inner value;
+//! - enums and union unit variants are JSON strings with the variant name;
+//! - other union variants are JSON objects with a single key matching the variant name. The value
+//!   is an array of fields for tuple variants and an object for struct variants;
+//! - a value which doesn't write anything (like the unit type) is `null`, and a value written as a
+//!   sequence of several items outside of any collection, structure or tuple (like a custom
+//!   encoding writing several primitives in a row) is an object with a single `$seq` key holding
+//!   the array of the items in their writing order, i.e. `{"$seq": [item, ...]}`.
+//! - union variants which are not known to the type (see `unknown` attribute of the strict encoding
+//!   derive macros) are objects with a single `$unknown` key holding a two-item array of the raw
+//!   tag number and the hex string of the raw variant data, i.e. `{"$unknown": [tag, "data"]}`.
+
+use std::collections::VecDeque;
+use std::io;
+
+use amplify::confinement::{Collection, Confined, KeyedCollection};
+use amplify::hex::{FromHex, ToHex};
+use serde_json::{Map, Value};
+
+use crate::traits::check_unknown_tag;
+use crate::{
+    Byte, DecodeError, DefineStruct, DefineTuple, DefineUnion, FieldName, NumCls, Primitive,
+    ReadRaw, ReadStruct, ReadTuple, ReadUnion, SerializeError, Sizing, StrictDecode, StrictDumb,
+    StrictEncode, StrictEnum, StrictStruct, StrictSum, StrictTuple, StrictType, StrictUnion,
+    TypedParent, TypedRead, TypedWrite, VariantName, WriteRaw, WriteStruct, WriteTuple, WriteUnion,
+    LIB_EMBEDDED,
+};
+
+/// JSON object key used for values which are written as a sequence of items.
+pub const JSON_SEQ_KEY: &str = "$seq";
+
+/// JSON object key used for union variants which are not known to the type.
+pub const JSON_UNKNOWN_KEY: &str = "$unknown";
+
+/// Strict types which can be converted to and from the canonical JSON
+/// projection.
+pub trait StrictJson: StrictEncode + StrictDecode {
+    /// Produces canonical JSON projection of the value.
+    fn to_strict_json(&self) -> Result<Value, SerializeError> { strict_to_serde_value(self) }
+
+    /// Reconstructs a value from its canonical JSON projection, requiring the
+    /// JSON data to be consumed completely.
+    fn from_strict_json(json: Value) -> Result<Self, DecodeError> { strict_from_serde_value(json) }
+}
+
+/// Produces canonical JSON projection of any strict-encodable value, without
+/// requiring the type to implement [`StrictJson`].
+pub fn strict_to_serde_value(value: &impl StrictEncode) -> Result<Value, SerializeError> {
+    let writer = value.strict_encode(JsonWriter::new())?;
+    Ok(writer.into_value())
+}
+
+/// Reconstructs a value from its canonical JSON projection, requiring the JSON
+/// data to be consumed completely. Counterpart of [`strict_to_serde_value`].
+pub fn strict_from_serde_value<T: StrictDecode>(json: Value) -> Result<T, DecodeError> {
+    let mut reader = JsonReader::with(json);
+    let me = T::strict_decode(&mut reader)?;
+    reader.finish()?;
+    Ok(me)
+}
+
+/// Adaptor implementing serde `Serialize` and `Deserialize` for any strict
+/// type via its canonical JSON projection, allowing to embed strict types into
+/// serde-based data structures.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct StrictSerde<T>(pub T);
+
+impl<T> From<T> for StrictSerde<T> {
+    fn from(value: T) -> Self { StrictSerde(value) }
+}
+
+#[cfg(feature = "serde")]
+mod _serde {
+    use serde_crate::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    impl<T: StrictEncode> Serialize for StrictSerde<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            strict_to_serde_value(&self.0)
+                .map_err(<S::Error as ser::Error>::custom)?
+                .serialize(serializer)
+        }
+    }
+
+    impl<'de, T: StrictDecode> Deserialize<'de> for StrictSerde<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let json = Value::deserialize(deserializer)?;
+            strict_from_serde_value(json).map(StrictSerde).map_err(<D::Error as de::Error>::custom)
+        }
+    }
+}
+
+fn is_byte<T: StrictType>(_: &T) -> bool {
+    T::STRICT_LIB_NAME == LIB_EMBEDDED && T::strict_name() == Byte::strict_name()
+}
+
+fn num_cls(prim: Primitive) -> NumCls {
+    if prim == Primitive::BYTE {
+        NumCls::Unsigned
+    } else {
+        prim.info().ty
+    }
+}
+
+fn number(prim: Primitive, bytes: &[u8]) -> Option<Value> {
+    let len = bytes.len();
+    if len == 0 || len > 8 || prim.byte_size() as usize != len {
+        return None;
+    }
+    let mut buf = [0u8; 8];
+    buf[..len].copy_from_slice(bytes);
+    match num_cls(prim) {
+        NumCls::Unsigned | NumCls::NonZero => Some(u64::from_le_bytes(buf).into()),
+        NumCls::Signed => {
+            let shift = 64 - len * 8;
+            Some(((i64::from_le_bytes(buf) << shift) >> shift).into())
+        }
+        NumCls::Float => None,
+    }
+}
+
+fn invalid_data(msg: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+fn invalid_input(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+enum Item {
+    Value(Value),
+    Raw(Vec<u8>),
+}
+
+impl Item {
+    fn into_value(self) -> Value {
+        match self {
+            Item::Value(value) => value,
+            Item::Raw(bytes) => Value::String(bytes.to_hex()),
+        }
+    }
+}
+
+/// Raw writer collecting items of the JSON projection.
+#[derive(Default)]
+pub struct JsonItems {
+    items: Vec<Item>,
+    prim: Option<Primitive>,
+}
+
+impl JsonItems {
+    fn push(&mut self, value: Value) {
+        self.prim = None;
+        self.items.push(Item::Value(value));
+    }
+
+    fn push_raw(&mut self, bytes: &[u8]) {
+        match self.items.last_mut() {
+            Some(Item::Raw(raw)) => raw.extend_from_slice(bytes),
+            _ => self.items.push(Item::Raw(bytes.to_vec())),
+        }
+    }
+}
+
+impl WriteRaw for JsonItems {
+    fn write_raw<const MAX_LEN: usize>(&mut self, bytes: impl AsRef<[u8]>) -> io::Result<()> {
+        let bytes = bytes.as_ref();
+        match self.prim.take().and_then(|prim| number(prim, bytes)) {
+            Some(num) => self.items.push(Item::Value(num)),
+            None => self.push_raw(bytes),
+        }
+        Ok(())
+    }
+}
+
+/// Writer producing canonical JSON projection of strict types.
+#[derive(Default)]
+pub struct JsonWriter {
+    items: JsonItems,
+    /// Error of a union written without a variant, which can be returned only
+    /// once the union writing completes.
+    error: Option<io::Error>,
+}
+
+impl JsonWriter {
+    pub fn new() -> Self { Self::default() }
+
+    /// Completes the writing, returning the produced JSON value.
+    pub fn into_value(self) -> Value {
+        let mut items = self.items.items;
+        match items.len() {
+            0 => Value::Null,
+            1 => items.remove(0).into_value(),
+            _ => {
+                let seq = items.into_iter().map(Item::into_value).collect();
+                let mut map = Map::new();
+                map.insert(JSON_SEQ_KEY.to_owned(), Value::Array(seq));
+                Value::Object(map)
+            }
+        }
+    }
+}
+
+impl TypedParent for JsonWriter {}
+impl TypedParent for JsonUnion {}
+
+/// Parent of a [`JsonFields`] writer, which absorbs the completed value.
+pub trait JsonParent: TypedParent {
+    fn absorb(self, value: Value) -> Self;
+}
+
+impl JsonParent for JsonWriter {
+    fn absorb(mut self, value: Value) -> Self {
+        self.items.push(value);
+        self
+    }
+}
+
+impl JsonParent for JsonUnion {
+    fn absorb(mut self, value: Value) -> Self {
+        match self.variant.take() {
+            Some(name) => {
+                let mut map = Map::new();
+                map.insert(name.to_string(), value);
+                self.value = Some(Value::Object(map));
+            }
+            None => {
+                self.parent.error =
+                    Some(invalid_input("union variant fields written without variant"))
+            }
+        }
+        self
+    }
+}
+
+impl TypedWrite for JsonWriter {
+    type TupleWriter = JsonFields<Self>;
+    type StructWriter = JsonFields<Self>;
+    type UnionDefiner = JsonUnion;
+    type RawWriter = JsonItems;
+
+    unsafe fn raw_writer(&mut self) -> &mut Self::RawWriter { &mut self.items }
+
+    fn write_union<T: StrictUnion>(
+        self,
+        inner: impl FnOnce(Self::UnionDefiner) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        let mut writer = inner(JsonUnion {
+            parent: self,
+            variant: None,
+            value: None,
+        })?;
+        match writer.error.take() {
+            Some(err) => Err(err),
+            None => Ok(writer),
+        }
+    }
+
+    fn write_union_unknown<T: StrictUnion>(mut self, tag: u8, data: &[u8]) -> io::Result<Self> {
+        check_unknown_tag::<T>(tag)?;
+        let mut map = Map::new();
+        map.insert(
+            JSON_UNKNOWN_KEY.to_owned(),
+            Value::Array(vec![Value::from(tag), Value::String(data.to_hex())]),
+        );
+        self.items.push(Value::Object(map));
+        Ok(self)
+    }
+
+    fn write_enum<T: StrictEnum>(mut self, value: T) -> io::Result<Self>
+    where u8: From<T> {
+        self.items.push(Value::String(value.variant_name().to_owned()));
+        Ok(self)
+    }
+
+    fn write_tuple<T: StrictTuple>(
+        self,
+        inner: impl FnOnce(Self::TupleWriter) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        inner(JsonFields::tuple(self, T::FIELD_COUNT == 1))
+    }
+
+    fn write_struct<T: StrictStruct>(
+        self,
+        inner: impl FnOnce(Self::StructWriter) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        inner(JsonFields::strukt(self))
+    }
+
+    unsafe fn register_primitive(mut self, prim: Primitive) -> Self {
+        self.items.prim = Some(prim);
+        self
+    }
+
+    unsafe fn register_array(mut self, ty: &impl StrictEncode, len: u16) -> Self {
+        let items = &mut self.items.items;
+        let len = len as usize;
+        if !is_byte(ty) || len == 0 || items.len() < len {
+            return self;
+        }
+        let bytes = items[items.len() - len..]
+            .iter()
+            .map(|item| match item {
+                Item::Value(Value::Number(num)) => num.as_u64().and_then(|n| u8::try_from(n).ok()),
+                _ => None,
+            })
+            .collect::<Option<Vec<u8>>>();
+        if let Some(bytes) = bytes {
+            items.truncate(items.len() - len);
+            self.items.push_raw(&bytes);
+        }
+        self
+    }
+
+    unsafe fn register_list(mut self, ty: &impl StrictEncode, _sizing: Sizing) -> Self {
+        if !is_byte(ty) {
+            return self;
+        }
+        if let Some(Item::Value(value @ Value::Array(_))) = self.items.items.last_mut() {
+            let bytes = value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|item| item.as_u64().and_then(|n| u8::try_from(n).ok()))
+                .collect::<Option<Vec<u8>>>();
+            if let Some(bytes) = bytes {
+                *value = Value::String(bytes.to_hex());
+            }
+        }
+        self
+    }
+
+    unsafe fn write_string<const MAX_LEN: usize>(
+        mut self,
+        bytes: impl AsRef<[u8]>,
+    ) -> io::Result<Self> {
+        let s = String::from_utf8(bytes.as_ref().to_vec()).map_err(invalid_data)?;
+        self.items.push(Value::String(s));
+        Ok(self)
+    }
+
+    unsafe fn write_collection<C: Collection, const MIN_LEN: usize, const MAX_LEN: usize>(
+        mut self,
+        col: &Confined<C, MIN_LEN, MAX_LEN>,
+    ) -> io::Result<Self>
+    where
+        for<'a> &'a C: IntoIterator,
+        for<'a> <&'a C as IntoIterator>::Item: StrictEncode,
+    {
+        let mut items = Vec::with_capacity(col.len());
+        for item in col {
+            items.push(item.strict_encode(JsonWriter::new())?.into_value());
+        }
+        self.items.push(Value::Array(items));
+        Ok(self)
+    }
+
+    unsafe fn write_slice<T: StrictEncode, const MAX_LEN: usize>(
+        mut self,
+        items: &[T],
+    ) -> io::Result<Self> {
+        let mut values = Vec::with_capacity(items.len());
+        for item in items {
+            values.push(item.strict_encode(JsonWriter::new())?.into_value());
+        }
+        self.items.push(Value::Array(values));
+        Ok(self)
+    }
+
+    unsafe fn write_keyed_collection<
+        C: KeyedCollection,
+        const MIN_LEN: usize,
+        const MAX_LEN: usize,
+    >(
+        mut self,
+        map: &Confined<C, MIN_LEN, MAX_LEN>,
+    ) -> io::Result<Self>
+    where
+        for<'a> &'a C: IntoIterator<Item = (&'a C::Key, &'a C::Value)>,
+        C::Key: StrictEncode,
+        C::Value: StrictEncode,
+    {
+        let mut items = Vec::with_capacity(map.len());
+        for (k, v) in map {
+            let k = k.strict_encode(JsonWriter::new())?.into_value();
+            let v = v.strict_encode(JsonWriter::new())?.into_value();
+            items.push(Value::Array(vec![k, v]));
+        }
+        self.items.push(Value::Array(items));
+        Ok(self)
+    }
+}
+
+enum FieldsInner {
+    Tuple(Vec<Value>),
+    Newtype(Option<Value>),
+    Struct(Map<String, Value>),
+}
+
+/// Writer of tuple and structure fields, which are also used by union
+/// variants.
+pub struct JsonFields<P: JsonParent> {
+    parent: P,
+    inner: FieldsInner,
+}
+
+impl<P: JsonParent> JsonFields<P> {
+    fn tuple(parent: P, newtype: bool) -> Self {
+        let inner =
+            if newtype { FieldsInner::Newtype(None) } else { FieldsInner::Tuple(Vec::new()) };
+        JsonFields { parent, inner }
+    }
+
+    fn strukt(parent: P) -> Self {
+        JsonFields {
+            parent,
+            inner: FieldsInner::Struct(Map::new()),
+        }
+    }
+
+    fn complete_value(self) -> P {
+        let value = match self.inner {
+            FieldsInner::Tuple(fields) => Value::Array(fields),
+            FieldsInner::Newtype(field) => field.unwrap_or(Value::Null),
+            FieldsInner::Struct(fields) => Value::Object(fields),
+        };
+        self.parent.absorb(value)
+    }
+}
+
+impl<P: JsonParent> WriteTuple for JsonFields<P> {
+    type Parent = P;
+
+    fn write_field(mut self, value: &impl StrictEncode) -> io::Result<Self> {
+        let value = value.strict_encode(JsonWriter::new())?.into_value();
+        match &mut self.inner {
+            FieldsInner::Tuple(fields) => fields.push(value),
+            FieldsInner::Newtype(field @ None) => *field = Some(value),
+            FieldsInner::Newtype(Some(_)) => {
+                return Err(invalid_input("newtype must have exactly one field"))
+            }
+            FieldsInner::Struct(_) => {
+                return Err(invalid_input("unnamed field written to a structure"))
+            }
+        }
+        Ok(self)
+    }
+
+    fn complete(self) -> P { self.complete_value() }
+}
+
+impl<P: JsonParent> WriteStruct for JsonFields<P> {
+    type Parent = P;
+
+    fn write_field(mut self, name: FieldName, value: &impl StrictEncode) -> io::Result<Self> {
+        let value = value.strict_encode(JsonWriter::new())?.into_value();
+        match &mut self.inner {
+            FieldsInner::Struct(fields) => {
+                fields.insert(name.to_string(), value);
+            }
+            _ => return Err(invalid_input("named field written to a tuple")),
+        }
+        Ok(self)
+    }
+
+    fn complete(self) -> P { self.complete_value() }
+}
+
+/// Definer of union variant fields, which are not reflected in the JSON
+/// projection.
+pub struct JsonDefiner(JsonUnion);
+
+impl DefineTuple for JsonDefiner {
+    type Parent = JsonUnion;
+    fn define_field<T: StrictEncode + StrictDumb>(self) -> Self { self }
+    fn complete(self) -> JsonUnion { self.0 }
+}
+
+impl DefineStruct for JsonDefiner {
+    type Parent = JsonUnion;
+    fn define_field<T: StrictEncode + StrictDumb>(self, _name: FieldName) -> Self { self }
+    fn complete(self) -> JsonUnion { self.0 }
+}
+
+/// Writer of union variants to the JSON projection.
+pub struct JsonUnion {
+    parent: JsonWriter,
+    variant: Option<VariantName>,
+    value: Option<Value>,
+}
+
+impl DefineUnion for JsonUnion {
+    type Parent = JsonWriter;
+    type TupleDefiner = JsonDefiner;
+    type StructDefiner = JsonDefiner;
+    type UnionWriter = JsonUnion;
+
+    fn define_unit(self, _name: VariantName) -> Self { self }
+    fn define_tuple(
+        self,
+        _name: VariantName,
+        inner: impl FnOnce(Self::TupleDefiner) -> Self,
+    ) -> Self {
+        inner(JsonDefiner(self))
+    }
+    fn define_struct(
+        self,
+        _name: VariantName,
+        inner: impl FnOnce(Self::StructDefiner) -> Self,
+    ) -> Self {
+        inner(JsonDefiner(self))
+    }
+
+    fn complete(self) -> Self::UnionWriter { self }
+}
+
+impl WriteUnion for JsonUnion {
+    type Parent = JsonWriter;
+    type TupleWriter = JsonFields<Self>;
+    type StructWriter = JsonFields<Self>;
+
+    fn write_unit(mut self, name: VariantName) -> io::Result<Self> {
+        self.value = Some(Value::String(name.to_string()));
+        Ok(self)
+    }
+    fn write_tuple(
+        mut self,
+        name: VariantName,
+        inner: impl FnOnce(Self::TupleWriter) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        self.variant = Some(name);
+        inner(JsonFields::tuple(self, false))
+    }
+    fn write_struct(
+        mut self,
+        name: VariantName,
+        inner: impl FnOnce(Self::StructWriter) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        self.variant = Some(name);
+        inner(JsonFields::strukt(self))
+    }
+
+    fn complete(self) -> JsonWriter {
+        let value = self.value.unwrap_or(Value::Null);
+        self.parent.absorb(value)
+    }
+}
+
+fn unexpected(expected: &str, found: &Value) -> DecodeError {
+    DecodeError::DataIntegrityError(format!("expected {expected} in JSON data, found `{found}`"))
+}
+
+enum Node {
+    Value(Value),
+    Bytes(VecDeque<u8>),
+}
+
+/// Reader of strict types from their canonical JSON projection.
+pub struct JsonReader {
+    queue: VecDeque<Node>,
+    prim: Option<Primitive>,
+}
+
+impl JsonReader {
+    pub fn with(json: Value) -> Self {
+        JsonReader {
+            queue: VecDeque::from([Node::Value(json)]),
+            prim: None,
+        }
+    }
+
+    /// Checks that all the JSON data were consumed by the reader.
+    pub fn finish(mut self) -> Result<(), DecodeError> {
+        self.normalize();
+        match self.queue.pop_front() {
+            None => Ok(()),
+            Some(Node::Bytes(bytes)) => Err(DecodeError::DataIntegrityError(format!(
+                "{} unconsumed bytes in JSON data",
+                bytes.len()
+            ))),
+            Some(Node::Value(value)) => Err(unexpected("no more data", &value)),
+        }
+    }
+
+    /// Removes empty values and unfolds item sequences at the front of the
+    /// queue.
+    fn normalize(&mut self) {
+        loop {
+            match self.queue.front_mut() {
+                Some(Node::Value(Value::Null)) => {}
+                Some(Node::Bytes(bytes)) if bytes.is_empty() => {}
+                Some(Node::Value(Value::Object(map)))
+                    if map.len() == 1 && map.get(JSON_SEQ_KEY).is_some_and(Value::is_array) =>
+                {
+                    let Some(Value::Array(items)) = map.remove(JSON_SEQ_KEY) else {
+                        unreachable!()
+                    };
+                    self.queue.pop_front();
+                    for item in items.into_iter().rev() {
+                        self.queue.push_front(Node::Value(item));
+                    }
+                    continue;
+                }
+                _ => return,
+            }
+            self.queue.pop_front();
+        }
+    }
+
+    fn next_value(&mut self, expected: &str) -> Result<Value, DecodeError> {
+        self.normalize();
+        match self.queue.pop_front() {
+            Some(Node::Value(value)) => Ok(value),
+            Some(Node::Bytes(_)) => Err(DecodeError::DataIntegrityError(format!(
+                "expected {expected} in JSON data, found bytes"
+            ))),
+            None => Err(DecodeError::DataIntegrityError(format!(
+                "expected {expected} in JSON data, found nothing"
+            ))),
+        }
+    }
+
+    fn front_bytes(&mut self) -> io::Result<Option<&mut VecDeque<u8>>> {
+        self.normalize();
+        if let Some(Node::Value(Value::String(s))) = self.queue.front() {
+            let bytes = Vec::<u8>::from_hex(s).map_err(invalid_data)?;
+            self.queue[0] = Node::Bytes(bytes.into());
+        }
+        Ok(match self.queue.front_mut() {
+            Some(Node::Bytes(bytes)) => Some(bytes),
+            _ => None,
+        })
+    }
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        // Numbers are range-checked against the primitive being decoded; the
+        // numbers read without it (like collection lengths) may be either
+        // unsigned or negative signed values
+        let signed = match self.prim.take().map(num_cls) {
+            Some(NumCls::Unsigned | NumCls::NonZero) => Some(false),
+            Some(NumCls::Signed) => Some(true),
+            Some(NumCls::Float) | None => None,
+        };
+        let mut pos = 0;
+        while pos < buf.len() {
+            if let Some(bytes) = self.front_bytes()? {
+                let len = bytes.len().min(buf.len() - pos);
+                for (dst, src) in buf[pos..pos + len].iter_mut().zip(bytes.drain(..len)) {
+                    *dst = src;
+                }
+                pos += len;
+                continue;
+            }
+            match self.queue.pop_front() {
+                Some(Node::Value(Value::Number(num))) if pos == 0 && buf.len() <= 8 => {
+                    let len = buf.len();
+                    let bits = len * 8;
+                    let le = match (signed, num.as_u64(), num.as_i64()) {
+                        (Some(false) | None, Some(n), _) if len == 8 || n >> bits == 0 => {
+                            n.to_le_bytes()
+                        }
+                        (Some(true), _, Some(n))
+                            if len == 8 || matches!(n >> (bits - 1), 0 | -1) =>
+                        {
+                            n.to_le_bytes()
+                        }
+                        (None, None, Some(n)) if len == 8 || n >> (bits - 1) == -1 => {
+                            n.to_le_bytes()
+                        }
+                        (Some(true), ..) => {
+                            return Err(invalid_data(format!(
+                                "number {num} doesn't fit {len}-byte signed integer"
+                            )))
+                        }
+                        _ => {
+                            return Err(invalid_data(format!(
+                                "number {num} doesn't fit {len}-byte unsigned integer"
+                            )))
+                        }
+                    };
+                    buf.copy_from_slice(&le[..len]);
+                    pos = len;
+                }
+                Some(Node::Value(value)) => {
+                    return Err(invalid_data(unexpected("number or hex string", &value)))
+                }
+                Some(Node::Bytes(_)) => unreachable!("bytes were checked before"),
+                None => return Err(io::ErrorKind::UnexpectedEof.into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ReadRaw for JsonReader {
+    fn read_raw<const MAX_LEN: usize>(&mut self, len: usize) -> io::Result<Vec<u8>> {
+        let mut buf = vec![0u8; len];
+        self.read_bytes(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_raw_array<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]> {
+        let mut buf = [0u8; LEN];
+        self.read_bytes(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_raw_into(&mut self, buf: &mut Vec<u8>, len: usize) -> io::Result<()> {
+        let start = buf.len();
+        buf.resize(start + len, 0);
+        self.read_bytes(&mut buf[start..])
+    }
+}
+
+impl TypedRead for JsonReader {
+    type TupleReader<'parent>
+        = JsonFieldsReader
+    where Self: 'parent;
+    type StructReader<'parent>
+        = JsonFieldsReader
+    where Self: 'parent;
+    type UnionReader = JsonVariantReader;
+    type RawReader = Self;
+
+    unsafe fn raw_reader(&mut self) -> &mut Self::RawReader { self }
+
+    unsafe fn register_primitive(&mut self, prim: Primitive) { self.prim = Some(prim); }
+
+    fn read_union<T: StrictUnion>(
+        &mut self,
+        inner: impl FnOnce(VariantName, &mut Self::UnionReader) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        let name = T::strict_name().unwrap_or_else(|| tn!("__unnamed"));
+        let (variant, payload) = match self.next_value("union variant")? {
+            Value::String(variant) => (variant, None),
+            Value::Object(map) if map.len() == 1 => {
+                let (variant, payload) = map.into_iter().next().expect("single item");
+                (variant, Some(payload))
+            }
+            value => return Err(unexpected("union variant", &value)),
+        };
+        let variant_name = T::ALL_VARIANTS
+            .iter()
+            .find(|(_, n)| *n == variant)
+            .map(|(_, n)| VariantName::from(*n))
+            .ok_or_else(|| {
+                DecodeError::DataIntegrityError(format!(
+                    "unknown variant `{variant}` of union `{name}`"
+                ))
+            })?;
+        let mut reader = JsonVariantReader { payload };
+        let res = inner(variant_name, &mut reader)?;
+        if let Some(payload) = reader.payload {
+            return Err(unexpected("no data for the variant", &payload));
+        }
+        Ok(res)
+    }
+
+    fn read_union_lenient<T: StrictUnion>(
+        &mut self,
+        inner: impl FnOnce(VariantName, &mut Self::UnionReader) -> Result<T, DecodeError>,
+        unknown: impl FnOnce(u8, &mut Self::RawReader) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        self.normalize();
+        let Some(Node::Value(Value::Object(map))) = self.queue.front_mut() else {
+            return self.read_union(inner);
+        };
+        if map.len() != 1 || !map.contains_key(JSON_UNKNOWN_KEY) {
+            return self.read_union(inner);
+        }
+        let value = map.remove(JSON_UNKNOWN_KEY).expect("checked above");
+        self.queue.pop_front();
+        let (tag, data) = match &value {
+            Value::Array(items) if items.len() == 2 => match (&items[0], &items[1]) {
+                (Value::Number(tag), Value::String(data)) => (
+                    tag.as_u64().and_then(|tag| u8::try_from(tag).ok()),
+                    Vec::<u8>::from_hex(data).ok(),
+                ),
+                _ => (None, None),
+            },
+            _ => (None, None),
+        };
+        let (Some(tag), Some(data)) = (tag, data) else {
+            return Err(unexpected("tag and hex data of unknown variant", &value));
+        };
+        if let Some(variant) = T::variant_name_by_tag(tag) {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "tag {tag} of unknown variant is used by variant `{variant}`"
+            )));
+        }
+        // The variant data are read by a separate reader, so they can't be
+        // confused with the data which follow the union
+        let mut reader = JsonReader {
+            queue: VecDeque::from([Node::Bytes(data.into())]),
+            prim: None,
+        };
+        let res = unknown(tag, &mut reader)?;
+        reader.finish()?;
+        Ok(res)
+    }
+
+    fn read_enum<T: StrictEnum>(&mut self) -> Result<T, DecodeError>
+    where u8: From<T> {
+        let name = T::strict_name().unwrap_or_else(|| tn!("__unnamed"));
+        let variant = match self.next_value("enum variant")? {
+            Value::String(variant) => variant,
+            value => return Err(unexpected("enum variant", &value)),
+        };
+        T::ALL_VARIANTS
+            .iter()
+            .find(|(_, n)| *n == variant)
+            .and_then(|(tag, _)| T::try_from(*tag).ok())
+            .ok_or_else(|| {
+                DecodeError::DataIntegrityError(format!(
+                    "unknown variant `{variant}` of enum `{name}`"
+                ))
+            })
+    }
+
+    fn read_tuple<'parent, 'me, T: StrictTuple>(
+        &'me mut self,
+        inner: impl FnOnce(&mut Self::TupleReader<'parent>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        Self: 'parent,
+        'me: 'parent,
+    {
+        let fields = match self.next_value("tuple")? {
+            value if T::FIELD_COUNT == 1 => vec![value],
+            Value::Array(fields) => fields,
+            value => return Err(unexpected("tuple", &value)),
+        };
+        JsonFieldsReader::Tuple(fields.into()).read_with(inner)
+    }
+
+    fn read_struct<'parent, 'me, T: StrictStruct>(
+        &'me mut self,
+        inner: impl FnOnce(&mut Self::StructReader<'parent>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        Self: 'parent,
+        'me: 'parent,
+    {
+        match self.next_value("structure")? {
+            Value::Object(fields) => JsonFieldsReader::Struct(fields).read_with(inner),
+            value => Err(unexpected("structure", &value)),
+        }
+    }
+
+    unsafe fn read_string<const MAX_LEN: usize>(&mut self) -> Result<Vec<u8>, DecodeError> {
+        match self.next_value("string")? {
+            Value::String(s) => Ok(s.into_bytes()),
+            value => Err(unexpected("string", &value)),
+        }
+    }
+
+    unsafe fn read_collection_len<const MAX_LEN: usize>(&mut self) -> Result<usize, DecodeError> {
+        match self.next_value("collection")? {
+            Value::Array(items) => {
+                let len = items.len();
+                for item in items.into_iter().rev() {
+                    self.queue.push_front(Node::Value(item));
+                }
+                Ok(len)
+            }
+            Value::String(s) => {
+                let bytes = Vec::<u8>::from_hex(&s).map_err(invalid_data)?;
+                let len = bytes.len();
+                self.queue.push_front(Node::Bytes(bytes.into()));
+                Ok(len)
+            }
+            value => Err(unexpected("collection", &value)),
+        }
+    }
+
+    unsafe fn read_keyed_collection_len<const MAX_LEN: usize>(
+        &mut self,
+    ) -> Result<usize, DecodeError> {
+        let items = match self.next_value("map")? {
+            Value::Array(items) => items,
+            value => return Err(unexpected("map", &value)),
+        };
+        let len = items.len();
+        for item in items.into_iter().rev() {
+            match item {
+                Value::Array(pair) if pair.len() == 2 => {
+                    for value in pair.into_iter().rev() {
+                        self.queue.push_front(Node::Value(value));
+                    }
+                }
+                value => return Err(unexpected("key-value pair", &value)),
+            }
+        }
+        Ok(len)
+    }
+}
+
+/// Reader of tuple and structure fields from the JSON projection.
+pub enum JsonFieldsReader {
+    Tuple(VecDeque<Value>),
+    Struct(Map<String, Value>),
+}
+
+impl JsonFieldsReader {
+    fn read_with<T>(
+        mut self,
+        inner: impl FnOnce(&mut Self) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        let res = inner(&mut self)?;
+        match self {
+            JsonFieldsReader::Tuple(fields) if !fields.is_empty() => Err(
+                DecodeError::DataIntegrityError(format!("{} unknown tuple fields", fields.len())),
+            ),
+            JsonFieldsReader::Struct(fields) if !fields.is_empty() => {
+                let names = fields.keys().cloned().collect::<Vec<_>>().join(", ");
+                Err(DecodeError::DataIntegrityError(format!("unknown structure fields {names}")))
+            }
+            _ => Ok(res),
+        }
+    }
+}
+
+fn read_slot<T: StrictDecode>(value: Value) -> Result<T, DecodeError> {
+    let mut reader = JsonReader::with(value);
+    let me = T::strict_decode(&mut reader)?;
+    reader.finish()?;
+    Ok(me)
+}
+
+impl ReadTuple for JsonFieldsReader {
+    fn read_field<T: StrictDecode>(&mut self) -> Result<T, DecodeError> {
+        match self {
+            JsonFieldsReader::Tuple(fields) => {
+                let value = fields.pop_front().ok_or_else(|| {
+                    DecodeError::DataIntegrityError(s!("missing tuple field in JSON data"))
+                })?;
+                read_slot(value)
+            }
+            JsonFieldsReader::Struct(_) => Err(DecodeError::DataIntegrityError(s!(
+                "unnamed field requested from a JSON object"
+            ))),
+        }
+    }
+}
+
+impl ReadStruct for JsonFieldsReader {
+    fn read_field<T: StrictDecode>(&mut self, field: FieldName) -> Result<T, DecodeError> {
+        match self {
+            JsonFieldsReader::Struct(fields) => {
+                let value = fields.remove(field.as_str()).ok_or_else(|| {
+                    DecodeError::DataIntegrityError(format!("missing field `{field}` in JSON data"))
+                })?;
+                read_slot(value)
+            }
+            JsonFieldsReader::Tuple(_) => Err(DecodeError::DataIntegrityError(format!(
+                "named field `{field}` requested from a JSON array"
+            ))),
+        }
+    }
+
+    fn read_field_or_else<T: StrictDecode>(
+        &mut self,
+        field: FieldName,
+        default: impl FnOnce() -> T,
+    ) -> Result<T, DecodeError> {
+        match self {
+            JsonFieldsReader::Struct(fields) if !fields.contains_key(field.as_str()) => {
+                Ok(default())
+            }
+            _ => ReadStruct::read_field(self, field),
+        }
+    }
+}
+
+/// Reader of union variant data from the JSON projection.
+pub struct JsonVariantReader {
+    payload: Option<Value>,
+}
+
+impl ReadUnion for JsonVariantReader {
+    type TupleReader<'parent>
+        = JsonFieldsReader
+    where Self: 'parent;
+    type StructReader<'parent>
+        = JsonFieldsReader
+    where Self: 'parent;
+
+    fn read_tuple<'parent, 'me, T: StrictSum>(
+        &'me mut self,
+        inner: impl FnOnce(&mut Self::TupleReader<'parent>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        Self: 'parent,
+        'me: 'parent,
+    {
+        match self.payload.take() {
+            Some(Value::Array(fields)) => JsonFieldsReader::Tuple(fields.into()).read_with(inner),
+            Some(value) => Err(unexpected("tuple variant fields", &value)),
+            None => Err(DecodeError::DataIntegrityError(s!("missing tuple variant fields"))),
+        }
+    }
+
+    fn read_struct<'parent, 'me, T: StrictSum>(
+        &'me mut self,
+        inner: impl FnOnce(&mut Self::StructReader<'parent>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError>
+    where
+        Self: 'parent,
+        'me: 'parent,
+    {
+        match self.payload.take() {
+            Some(Value::Object(fields)) => JsonFieldsReader::Struct(fields).read_with(inner),
+            Some(value) => Err(unexpected("struct variant fields", &value)),
+            None => Err(DecodeError::DataIntegrityError(s!("missing struct variant fields"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use amplify::confinement::{SmallVec, TinyOrdMap, TinyString};
+    use serde_json::json;
+
+    use super::*;
+
+    const TEST_LIB: &str = "TestLib";
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = repr, into_u8, try_from_u8, crate = crate)]
+    #[repr(u8)]
+    enum Kind {
+        #[default]
+        Plain = 1,
+        Signed = 2,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = order, dumb = Payload::Empty, crate = crate)]
+    enum Payload {
+        Empty,
+        Amount(u64, i16),
+        Data(SmallVec<u8>),
+        Nested { kind: Kind, id: [u8; 4] },
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, crate = crate)]
+    struct Name(TinyString);
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, crate = crate)]
+    struct Record {
+        name: Name,
+        kind: Kind,
+        hash: [u8; 4],
+        payloads: SmallVec<Payload>,
+        meta: TinyOrdMap<u8, Option<Name>>,
+        unit: (),
+    }
+
+    impl StrictJson for Record {}
+
+    fn record() -> Record {
+        Record {
+            name: Name(tiny_s!("alice")),
+            kind: Kind::Signed,
+            hash: [0xde, 0xad, 0xbe, 0xef],
+            payloads: small_vec![
+                Payload::Empty,
+                Payload::Amount(u64::MAX, -2),
+                Payload::Data(small_vec![1, 2, 0xff]),
+                Payload::Nested {
+                    kind: Kind::Plain,
+                    id: [0, 1, 2, 3]
+                },
+            ],
+            meta: TinyOrdMap::from_checked(BTreeMap::from([
+                (1, None),
+                (7, Some(Name(tiny_s!("x")))),
+            ])),
+            unit: (),
+        }
+    }
+
+    #[test]
+    fn json_projection() {
+        let json = record().to_strict_json().unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "name": "alice",
+                "kind": "signed",
+                "hash": "deadbeef",
+                "payloads": [
+                    "empty",
+                    { "amount": [u64::MAX, -2] },
+                    { "data": ["0102ff"] },
+                    { "nested": { "kind": "plain", "id": "00010203" } },
+                ],
+                "meta": [[1, "none"], [7, { "some": ["x"] }]],
+                "unit": null,
+            })
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let rec = record();
+        let json = rec.to_strict_json().unwrap();
+        assert_eq!(Record::from_strict_json(json).unwrap(), rec);
+    }
+
+    #[test]
+    fn json_invalid() {
+        let mut json = record().to_strict_json().unwrap();
+        json["kind"] = json!("unknown");
+        assert!(matches!(Record::from_strict_json(json), Err(DecodeError::DataIntegrityError(_))));
+
+        let mut json = record().to_strict_json().unwrap();
+        json["extra"] = json!(1);
+        assert!(matches!(Record::from_strict_json(json), Err(DecodeError::DataIntegrityError(_))));
+
+        let mut json = record().to_strict_json().unwrap();
+        json["hash"] = json!("dead");
+        assert!(Record::from_strict_json(json).is_err());
+    }
+
+    #[test]
+    fn json_extensible() {
+        #[derive(Clone, PartialEq, Eq, Debug, Default)]
+        #[derive(StrictType, StrictEncode, StrictDecode)]
+        #[strict_type(lib = TEST_LIB, extensible, crate = crate)]
+        struct Versioned {
+            id: u8,
+            #[strict_type(default = 5)]
+            flags: u8,
+        }
+
+        impl StrictJson for Versioned {}
+
+        let rec = Versioned::from_strict_json(json!({ "id": 1 })).unwrap();
+        assert_eq!(rec, Versioned { id: 1, flags: 5 });
+        let rec = Versioned::from_strict_json(json!({ "id": 1, "flags": 2 })).unwrap();
+        assert_eq!(rec, Versioned { id: 1, flags: 2 });
+        assert!(Versioned::from_strict_json(json!({ "flags": 2 })).is_err());
+    }
+
+    #[test]
+    fn json_unknown_variant() {
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+        #[strict_type(lib = TEST_LIB, tags = order, unknown = Unknown, crate = crate)]
+        enum Message {
+            #[strict_type(dumb)]
+            Ping,
+            Data(u16),
+            Unknown(u8, Vec<u8>),
+        }
+
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+        #[strict_type(lib = TEST_LIB, crate = crate)]
+        struct Envelope {
+            msg: Message,
+            tail: u8,
+        }
+
+        impl StrictJson for Envelope {}
+
+        // Unlike the binary encoding, the data of unknown variants are
+        // delimited in JSON, so the fields following them are decodable
+        let env = Envelope {
+            msg: Message::Unknown(2, vec![5, 1]),
+            tail: 7,
+        };
+        let json = env.to_strict_json().unwrap();
+        assert_eq!(json, json!({ "msg": { "$unknown": [2, "0501"] }, "tail": 7 }));
+        assert_eq!(Envelope::from_strict_json(json).unwrap(), env);
+
+        let json = json!({ "msg": { "data": [3] }, "tail": 7 });
+        assert_eq!(Envelope::from_strict_json(json).unwrap().msg, Message::Data(3));
+
+        // Tags of known variants can't be used by unknown ones
+        let json = json!({ "msg": { "$unknown": [1, "0300"] }, "tail": 7 });
+        assert!(matches!(
+            Envelope::from_strict_json(json),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+        assert!(Envelope {
+            msg: Message::Unknown(1, vec![3, 0]),
+            tail: 7
+        }
+        .to_strict_json()
+        .is_err());
+    }
+
+    #[test]
+    fn json_writer_misuse() {
+        fn is_invalid_input(res: io::Result<JsonWriter>) -> bool {
+            matches!(res, Err(err) if err.kind() == io::ErrorKind::InvalidInput)
+        }
+
+        assert!(is_invalid_input(JsonWriter::new().write_tuple::<Name>(|writer| {
+            let writer = WriteTuple::write_field(writer, &1u8)?;
+            Ok(WriteTuple::complete(WriteTuple::write_field(writer, &2u8)?))
+        })));
+        assert!(is_invalid_input(JsonWriter::new().write_struct::<Record>(|writer| {
+            Ok(WriteTuple::complete(WriteTuple::write_field(writer, &1u8)?))
+        })));
+        assert!(is_invalid_input(JsonWriter::new().write_tuple::<Name>(|writer| {
+            Ok(WriteStruct::complete(WriteStruct::write_field(writer, fname!("name"), &1u8)?))
+        })));
+        assert!(is_invalid_input(JsonWriter::new().write_union::<Payload>(|definer| {
+            let writer = DefineUnion::complete(definer).absorb(Value::Null);
+            Ok(WriteUnion::complete(writer))
+        })));
+    }
+
+    #[test]
+    fn json_encode_only() {
+        use amplify::confinement::{Confined, TinyBlob, U64};
+
+        #[derive(StrictType, StrictEncode)]
+        #[strict_type(lib = TEST_LIB, tags = order, encode_only, crate = crate)]
+        enum Msg<'a> {
+            Ping,
+            Data(&'a TinyBlob),
+            Text { text: &'a str, len: u8 },
+        }
+
+        #[derive(StrictType, StrictEncode)]
+        #[strict_type(lib = TEST_LIB, encode_only, crate = crate)]
+        struct Env<'a> {
+            id: u8,
+            blob: &'a TinyBlob,
+            msg: Msg<'a>,
+        }
+
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+        #[strict_type(lib = TEST_LIB, tags = order, crate = crate)]
+        enum OwnedMsg {
+            #[strict_type(dumb)]
+            Ping,
+            Data(TinyBlob),
+            Text {
+                text: Confined<String, 0, U64>,
+                len: u8,
+            },
+        }
+
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+        #[strict_type(lib = TEST_LIB, crate = crate)]
+        struct OwnedEnv {
+            id: u8,
+            blob: TinyBlob,
+            msg: OwnedMsg,
+        }
+
+        impl StrictJson for OwnedEnv {}
+
+        let blob = TinyBlob::try_from(vec![1, 2]).unwrap();
+        let text = Confined::try_from(s!("hi")).unwrap();
+        for (msg, owned) in [
+            (Msg::Ping, OwnedMsg::Ping),
+            (Msg::Data(&blob), OwnedMsg::Data(blob.clone())),
+            (Msg::Text { text: "hi", len: 2 }, OwnedMsg::Text { text, len: 2 }),
+        ] {
+            let env = Env {
+                id: 3,
+                blob: &blob,
+                msg,
+            };
+            let owned = OwnedEnv {
+                id: 3,
+                blob: blob.clone(),
+                msg: owned,
+            };
+            assert_eq!(strict_to_serde_value(&env).unwrap(), owned.to_strict_json().unwrap());
+        }
+
+        let env = Env {
+            id: 3,
+            blob: &blob,
+            msg: Msg::Data(&blob),
+        };
+        assert_eq!(
+            strict_to_serde_value(&env).unwrap(),
+            json!({ "id": 3, "blob": "0102", "msg": { "data": ["0102"] } })
+        );
+    }
+
+    #[test]
+    fn number_ranges() {
+        assert_eq!(strict_from_serde_value::<i8>(json!(-56)).unwrap(), -56);
+        assert_eq!(strict_from_serde_value::<i8>(json!(127)).unwrap(), 127);
+        assert_eq!(strict_from_serde_value::<u8>(json!(200)).unwrap(), 200);
+        assert_eq!(strict_from_serde_value::<i64>(json!(i64::MIN)).unwrap(), i64::MIN);
+        assert_eq!(strict_from_serde_value::<u64>(json!(u64::MAX)).unwrap(), u64::MAX);
+
+        // Values must fit the primitive with its signedness
+        assert!(strict_from_serde_value::<i8>(json!(200)).is_err());
+        assert!(strict_from_serde_value::<i8>(json!(128)).is_err());
+        assert!(strict_from_serde_value::<i8>(json!(-129)).is_err());
+        assert!(strict_from_serde_value::<u8>(json!(-1)).is_err());
+        assert!(strict_from_serde_value::<u16>(json!(-1)).is_err());
+        assert!(strict_from_serde_value::<i64>(json!(u64::MAX)).is_err());
+        assert!(strict_from_serde_value::<u64>(json!(-1)).is_err());
+    }
+
+    #[test]
+    fn slices() {
+        let boxed: Box<[u16]> = Box::new([1, 2, 3]);
+        let json = strict_to_serde_value(&boxed).unwrap();
+        assert_eq!(json, json!([1, 2, 3]));
+        assert_eq!(strict_from_serde_value::<Box<[u16]>>(json.clone()).unwrap(), boxed);
+        assert_eq!(strict_to_serde_value(&&boxed[..]).unwrap(), json);
+
+        let bytes: Box<[u8]> = Box::new([1, 2, 0xff]);
+        let json = strict_to_serde_value(&bytes).unwrap();
+        assert_eq!(json, json!("0102ff"));
+        assert_eq!(strict_from_serde_value::<Box<[u8]>>(json.clone()).unwrap(), bytes);
+        assert_eq!(strict_to_serde_value(&&bytes[..]).unwrap(), json);
+    }
+
+    #[test]
+    fn serde_value() {
+        let rec = record();
+        let json = strict_to_serde_value(&rec).unwrap();
+        assert_eq!(json, rec.to_strict_json().unwrap());
+        assert_eq!(strict_from_serde_value::<Record>(json).unwrap(), rec);
+        assert_eq!(
+            strict_to_serde_value(&Payload::Amount(5, -1)).unwrap(),
+            json!({ "amount": [5, -1] })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_bridge() {
+        let rec = StrictSerde(record());
+        let json = serde_json::to_value(&rec).unwrap();
+        assert_eq!(json, rec.0.to_strict_json().unwrap());
+        let s = serde_json::to_string(&rec).unwrap();
+        assert_eq!(serde_json::from_str::<StrictSerde<Record>>(&s).unwrap(), rec);
+
+        let err = serde_json::from_value::<StrictSerde<Kind>>(json!("unknown")).unwrap_err();
+        assert!(err.to_string().contains("unknown"));
+    }
+}
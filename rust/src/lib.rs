@@ -34,6 +34,8 @@
 pub use derive::{StrictDecode, StrictDumb, StrictEncode, StrictType};
 #[cfg(not(feature = "derive"))]
 use derive::{StrictDecode, StrictDumb, StrictEncode, StrictType};
+#[cfg(feature = "proptest")]
+pub use proptest;
 #[cfg(feature = "derive")]
 pub use strict_encoding_derive as derive;
 #[cfg(not(feature = "derive"))]
@@ -57,21 +59,44 @@ mod writer;
 mod util;
 mod primitives;
 mod embedded;
+mod endian;
+mod heap;
+mod net;
+#[cfg(feature = "arrayvec")]
+mod inline;
+#[cfg(feature = "tokio")]
+mod async_io;
 pub mod stl;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[cfg(feature = "json")]
+pub mod json;
 #[cfg(test)]
 pub(crate) mod test;
 
-pub use embedded::{Byte, DecodeRawLe};
+#[cfg(feature = "tokio")]
+pub use async_io::{AsyncStrictReader, AsyncStrictWriter};
+pub use embedded::{Byte, DecodeRawLe, BOXED_SLICE_MAX_LEN};
+pub use endian::BigEndian;
 pub use error::{DecodeError, DeserializeError, SerializeError};
-pub use ident::{FieldName, Ident, LibName, TypeName, VariantName, IDENT_MAX_LEN};
-pub use primitives::{NumCls, NumInfo, NumSize, Primitive};
-pub use reader::{ConfinedReader, StreamReader, StrictReader};
-pub use stl::{Bool, InvalidRString, RString, RestrictedCharSet, U1, U2, U3, U4, U5, U6, U7};
+pub use heap::{ConfinedHeap, Heap};
+pub use ident::{FieldName, Ident, InvalidIdent, LibName, TypeName, VariantName, IDENT_MAX_LEN};
+#[cfg(feature = "arrayvec")]
+pub use inline::InlineVec;
+pub use primitives::{InvalidPrimitive, NumCls, NumInfo, NumSize, Primitive};
+pub use reader::{
+    strict_decode_exact, strict_decode_iter, ConfinedReader, DecodeIter, StreamReader,
+    StrictReader, DEFAULT_DEPTH_LIMIT,
+};
+pub use stl::{
+    Bits, Bool, InvalidRString, RString, RestrictedCharSet, Unit, U1, U2, U3, U4, U5, U6, U7,
+};
 pub use traits::*;
 pub use types::*;
-pub use util::{Sizing, Variant};
+pub use util::{ConstHasher, Sizing, SizingParseError, Variant, VariantKey};
 pub use writer::{
-    SplitParent, StreamWriter, StrictParent, StrictWriter, StructWriter, UnionWriter,
+    Crc32Writer, RawBuffer, SplitParent, StreamWriter, StrictParent, StrictWriter, StructWriter,
+    UnionWriter,
 };
 
 #[deprecated(since = "2.2.0", note = "use LIB_EMBEDDED")]
@@ -98,6 +98,170 @@ macro_rules! impl_strict_struct {
     };
 }
 
+/// Implements [`StrictEncode`](crate::StrictEncode) for a structure by
+/// delegating to one of its fields, which is encoded instead of the whole
+/// structure; the rest of the fields are not encoded. The type must implement
+/// [`StrictType`](crate::StrictType).
+///
+/// Unlike [`impl_strict_newtype!`], the type is not required to be a wrapper,
+/// and it may be generic, in which case the generic parameters go after
+/// `impl` and the bounds into the trailing `where` clause:
+///
+/// ```
+/// # use std::marker::PhantomData;
+/// # use strict_encoding::{strict_encode_delegate, StrictEncode, StrictSerialize, StrictType};
+/// struct Cached {
+///     value: u16,
+///     hits: usize,
+/// }
+/// impl StrictType for Cached {
+///     const STRICT_LIB_NAME: &'static str = "Example";
+/// }
+/// impl StrictSerialize for Cached {}
+/// strict_encode_delegate!(Cached => self.value);
+///
+/// struct Tagged<T> {
+///     inner: T,
+///     _phantom: PhantomData<T>,
+/// }
+/// impl<T> StrictType for Tagged<T> {
+///     const STRICT_LIB_NAME: &'static str = "Example";
+/// }
+/// strict_encode_delegate!(impl<T> Tagged<T> => self.inner where T: StrictEncode);
+///
+/// let cached = Cached {
+///     value: 0x0201,
+///     hits: 7,
+/// };
+/// assert_eq!(cached.to_strict_serialized::<2>().unwrap().as_slice(), &[1, 2]);
+/// ```
+///
+/// See [`strict_decode_delegate!`](crate::strict_decode_delegate!) for the decoding counterpart.
+#[macro_export]
+macro_rules! strict_encode_delegate {
+    (impl<$($param:ident),*> $ty:ty => self.$field:ident where $($bounds:tt)*) => {
+        impl<$($param),*> $crate::StrictEncode for $ty where $($bounds)* {
+            fn strict_encode<W: $crate::TypedWrite>(&self, writer: W) -> ::std::io::Result<W> {
+                $crate::StrictEncode::strict_encode(&self.$field, writer)
+            }
+        }
+    };
+    ($ty:ty => self.$field:ident) => {
+        $crate::strict_encode_delegate!(impl<> $ty => self.$field where);
+    };
+}
+
+/// Implements [`StrictDecode`](crate::StrictDecode) for a structure by
+/// decoding one of its fields, written by [`strict_encode_delegate!`]. The
+/// rest of the fields take their values from the `Default` implementation for
+/// the type. The type must implement [`StrictType`](crate::StrictType).
+///
+/// Generic types are supported in the same way as in
+/// [`strict_encode_delegate!`]:
+///
+/// ```
+/// # use std::marker::PhantomData;
+/// # use amplify::confinement::Confined;
+/// # use strict_encoding::{strict_decode_delegate, StrictDecode, StrictDeserialize, StrictType};
+/// #[derive(Default)]
+/// struct Cached {
+///     value: u16,
+///     hits: usize,
+/// }
+/// impl StrictType for Cached {
+///     const STRICT_LIB_NAME: &'static str = "Example";
+/// }
+/// impl StrictDeserialize for Cached {}
+/// strict_decode_delegate!(Cached => value);
+///
+/// #[derive(Default)]
+/// struct Tagged<T> {
+///     inner: T,
+///     _phantom: PhantomData<T>,
+/// }
+/// impl<T> StrictType for Tagged<T> {
+///     const STRICT_LIB_NAME: &'static str = "Example";
+/// }
+/// strict_decode_delegate!(impl<T> Tagged<T> => inner where T: StrictDecode + Default);
+///
+/// let data = Confined::try_from(vec![1, 2]).unwrap();
+/// let cached = Cached::from_strict_serialized::<2>(data).unwrap();
+/// assert_eq!(cached.value, 0x0201);
+/// assert_eq!(cached.hits, 0);
+/// ```
+#[macro_export]
+macro_rules! strict_decode_delegate {
+    (impl<$($param:ident),*> $ty:ty => $field:ident where $($bounds:tt)*) => {
+        impl<$($param),*> $crate::StrictDecode for $ty where $($bounds)* {
+            fn strict_decode(
+                reader: &mut impl $crate::TypedRead,
+            ) -> Result<Self, $crate::DecodeError> {
+                Ok(Self {
+                    $field: $crate::StrictDecode::strict_decode(reader)?,
+                    ..::core::default::Default::default()
+                })
+            }
+        }
+    };
+    ($ty:ty => $field:ident) => {
+        $crate::strict_decode_delegate!(impl<> $ty => $field where);
+    };
+}
+
+/// Implements [`StrictDumb`](crate::StrictDumb), [`StrictType`](crate::StrictType),
+/// [`StrictEncode`](crate::StrictEncode) and [`StrictDecode`](crate::StrictDecode)
+/// for a bit flags type in the library `$lib`, encoding it as the underlying
+/// integer `$int`. The type keeps its own strict name.
+///
+/// The type must provide `bits(&self) -> $int` and `from_bits_retain($int)`
+/// methods, like the types generated by `bitflags` crate do, and must not
+/// implement `Default` (the dumb value has no flags set). Unknown bits are
+/// neither rejected nor cleared: they are written and read back as they are,
+/// so the validation of the decoded flags is up to the caller.
+///
+/// ```
+/// # use strict_encoding::{strict_encode_bitflags, StrictSerialize};
+/// #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// struct Permissions(u32);
+///
+/// impl Permissions {
+///     const READ: Self = Self(0x01);
+///
+///     fn bits(&self) -> u32 { self.0 }
+///     fn from_bits_retain(bits: u32) -> Self { Self(bits) }
+/// }
+///
+/// const LIB_NAME: &str = "Example";
+/// strict_encode_bitflags!(Permissions, u32, LIB_NAME);
+/// impl StrictSerialize for Permissions {}
+///
+/// let data = Permissions::READ.to_strict_serialized::<4>().unwrap();
+/// assert_eq!(data.as_slice(), &[1, 0, 0, 0]);
+/// ```
+#[macro_export]
+macro_rules! strict_encode_bitflags {
+    ($ty:ty, $int:ty, $lib:expr) => {
+        impl $crate::StrictDumb for $ty {
+            fn strict_dumb() -> Self { Self::from_bits_retain(0) }
+        }
+        impl $crate::StrictType for $ty {
+            const STRICT_LIB_NAME: &'static str = $lib;
+        }
+        impl $crate::StrictEncode for $ty {
+            fn strict_encode<W: $crate::TypedWrite>(&self, writer: W) -> ::std::io::Result<W> {
+                $crate::StrictEncode::strict_encode(&self.bits(), writer)
+            }
+        }
+        impl $crate::StrictDecode for $ty {
+            fn strict_decode(
+                reader: &mut impl $crate::TypedRead,
+            ) -> Result<Self, $crate::DecodeError> {
+                <$int as $crate::StrictDecode>::strict_decode(reader).map(Self::from_bits_retain)
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! ident {
     ($name:literal) => {
@@ -156,3 +320,76 @@ macro_rules! libname {
         $crate::LibName::try_from($name).expect("hardcoded parameter is not a valid library name")
     };
 }
+
+#[cfg(test)]
+mod test {
+    use std::marker::PhantomData;
+
+    use crate::test::{decode, encoding};
+    use crate::{StrictDumb, StrictType};
+
+    #[derive(Clone, Eq, PartialEq, Debug, Default)]
+    struct Cached {
+        value: u16,
+        hits: usize,
+    }
+    impl StrictType for Cached {
+        const STRICT_LIB_NAME: &'static str = "Test";
+    }
+    strict_encode_delegate!(Cached => self.value);
+    strict_decode_delegate!(Cached => value);
+
+    #[derive(Clone, Eq, PartialEq, Debug, Default)]
+    struct Tagged<T> {
+        inner: T,
+        _phantom: PhantomData<T>,
+    }
+    impl<T> StrictType for Tagged<T> {
+        const STRICT_LIB_NAME: &'static str = "Test";
+    }
+    strict_encode_delegate!(impl<T> Tagged<T> => self.inner where T: crate::StrictEncode);
+    strict_decode_delegate!(impl<T> Tagged<T> => inner where T: crate::StrictDecode + Default);
+
+    #[test]
+    fn delegate() {
+        encoding(
+            &Cached {
+                value: 0x0201,
+                hits: 0,
+            },
+            [1, 2],
+        );
+        encoding(
+            &Tagged {
+                inner: 0x04030201u32,
+                _phantom: PhantomData,
+            },
+            [1, 2, 3, 4],
+        );
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+    struct Permissions(u32);
+    impl Permissions {
+        const READ: Self = Self(0x01);
+        const WRITE: Self = Self(0x02);
+        const ADMIN: Self = Self(0x8000_0000);
+
+        fn bits(&self) -> u32 { self.0 }
+        fn from_bits_retain(bits: u32) -> Self { Self(bits) }
+    }
+    strict_encode_bitflags!(Permissions, u32, "Test");
+
+    #[test]
+    fn bitflags() {
+        assert_eq!(Permissions::strict_name(), Some(tn!("Permissions")));
+        assert_eq!(Permissions::STRICT_LIB_NAME, "Test");
+        assert_eq!(Permissions::strict_dumb(), Permissions(0));
+
+        encoding(&Permissions::READ, [1, 0, 0, 0]);
+        encoding(&Permissions(Permissions::READ.0 | Permissions::WRITE.0), [3, 0, 0, 0]);
+        encoding(&Permissions::ADMIN, [0, 0, 0, 0x80]);
+        // Unknown bits are kept as they are
+        assert_eq!(decode::<Permissions>([0x10, 0, 0, 0]), Permissions(0x10));
+    }
+}
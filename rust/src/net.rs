@@ -0,0 +1,78 @@
+// Strict encoding library for deterministic binary serialization.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding of IP addresses.
+//!
+//! The implementations are provided for `core::net` address types, which are
+//! re-exported by `std::net`. Addresses are encoded as byte arrays of their
+//! octets in the network byte order.
+//!
+//! Since the address types don't implement [`Default`], they can't have
+//! [`crate::StrictDumb`] implementation, thus fields of these types require
+//! an explicit dumb value in the derived types, and [`core::net::IpAddr`] is
+//! not supported.
+
+use core::net::{Ipv4Addr, Ipv6Addr};
+use std::io;
+
+use crate::{
+    DecodeError, StrictDecode, StrictEncode, StrictType, TypedRead, TypedWrite, LIB_NAME_STD,
+};
+
+macro_rules! encode_ip {
+    ($ty:ty, $len:literal) => {
+        impl StrictType for $ty {
+            const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+        }
+        impl StrictEncode for $ty {
+            fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+                self.octets().strict_encode(writer)
+            }
+        }
+        impl StrictDecode for $ty {
+            fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+                <[u8; $len]>::strict_decode(reader).map(<$ty>::from)
+            }
+        }
+    };
+}
+
+encode_ip!(Ipv4Addr, 4);
+encode_ip!(Ipv6Addr, 16);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::encoding;
+
+    #[test]
+    fn ip_addr() {
+        encoding(&Ipv4Addr::new(192, 168, 0, 1), [192, 168, 0, 1]);
+        encoding(&Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1), [
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+        ]);
+        // `std::net` types are the same as `core::net` ones
+        encoding(&std::net::Ipv4Addr::LOCALHOST, [127, 0, 0, 1]);
+
+        assert_eq!(Ipv4Addr::strict_name().unwrap(), tn!("Ipv4Addr"));
+        assert_eq!(Ipv6Addr::strict_name().unwrap(), tn!("Ipv6Addr"));
+    }
+}
@@ -26,6 +26,11 @@ use amplify::Wrapper;
 
 use crate::STRICT_TYPES_LIB;
 
+/// Error parsing [`Primitive`] from a reserved code.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display("primitive type code {0:#04x} is reserved")]
+pub struct InvalidPrimitive(pub u8);
+
 #[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
 pub struct Primitive(u8);
@@ -135,6 +140,64 @@ impl Primitive {
     pub const FLOAT_RESERVED_53: Primitive = Primitive(0xFE);
     pub const FLOAT_RESERVED_54: Primitive = Primitive(0xFF);
 
+    /// Reserved float codes, in ascending order.
+    pub const FLOAT_RESERVED: [Primitive; 54] = [
+        Primitive::FLOAT_RESERVED_1,
+        Primitive::FLOAT_RESERVED_2,
+        Primitive::FLOAT_RESERVED_3,
+        Primitive::FLOAT_RESERVED_4,
+        Primitive::FLOAT_RESERVED_5,
+        Primitive::FLOAT_RESERVED_6,
+        Primitive::FLOAT_RESERVED_7,
+        Primitive::FLOAT_RESERVED_8,
+        Primitive::FLOAT_RESERVED_9,
+        Primitive::FLOAT_RESERVED_10,
+        Primitive::FLOAT_RESERVED_11,
+        Primitive::FLOAT_RESERVED_12,
+        Primitive::FLOAT_RESERVED_13,
+        Primitive::FLOAT_RESERVED_14,
+        Primitive::FLOAT_RESERVED_15,
+        Primitive::FLOAT_RESERVED_16,
+        Primitive::FLOAT_RESERVED_17,
+        Primitive::FLOAT_RESERVED_18,
+        Primitive::FLOAT_RESERVED_19,
+        Primitive::FLOAT_RESERVED_20,
+        Primitive::FLOAT_RESERVED_21,
+        Primitive::FLOAT_RESERVED_22,
+        Primitive::FLOAT_RESERVED_23,
+        Primitive::FLOAT_RESERVED_24,
+        Primitive::FLOAT_RESERVED_25,
+        Primitive::FLOAT_RESERVED_26,
+        Primitive::FLOAT_RESERVED_27,
+        Primitive::FLOAT_RESERVED_28,
+        Primitive::FLOAT_RESERVED_29,
+        Primitive::FLOAT_RESERVED_30,
+        Primitive::FLOAT_RESERVED_31,
+        Primitive::FLOAT_RESERVED_32,
+        Primitive::FLOAT_RESERVED_33,
+        Primitive::FLOAT_RESERVED_34,
+        Primitive::FLOAT_RESERVED_35,
+        Primitive::FLOAT_RESERVED_36,
+        Primitive::FLOAT_RESERVED_37,
+        Primitive::FLOAT_RESERVED_38,
+        Primitive::FLOAT_RESERVED_39,
+        Primitive::FLOAT_RESERVED_40,
+        Primitive::FLOAT_RESERVED_41,
+        Primitive::FLOAT_RESERVED_42,
+        Primitive::FLOAT_RESERVED_43,
+        Primitive::FLOAT_RESERVED_44,
+        Primitive::FLOAT_RESERVED_45,
+        Primitive::FLOAT_RESERVED_46,
+        Primitive::FLOAT_RESERVED_47,
+        Primitive::FLOAT_RESERVED_48,
+        Primitive::FLOAT_RESERVED_49,
+        Primitive::FLOAT_RESERVED_50,
+        Primitive::FLOAT_RESERVED_51,
+        Primitive::FLOAT_RESERVED_52,
+        Primitive::FLOAT_RESERVED_53,
+        Primitive::FLOAT_RESERVED_54,
+    ];
+
     pub const fn unsigned(bytes: u16) -> Self {
         Primitive(
             NumInfo {
@@ -175,7 +238,20 @@ impl Primitive {
         )
     }
 
+    /// Constructs primitive from a trusted code, which is not checked to be
+    /// [`Self::RESERVED`] or one of [`Self::FLOAT_RESERVED`]; use
+    /// [`Self::try_from_code`] for the codes coming from untrusted data.
     pub const fn from_code(code: u8) -> Self { Primitive(code) }
+
+    /// Constructs primitive from a code, failing on [`Self::RESERVED`] and
+    /// [`Self::FLOAT_RESERVED`] codes.
+    pub fn try_from_code(code: u8) -> Result<Self, InvalidPrimitive> {
+        let prim = Primitive(code);
+        if prim == Self::RESERVED || Self::FLOAT_RESERVED.contains(&prim) {
+            return Err(InvalidPrimitive(code));
+        }
+        Ok(prim)
+    }
     pub const fn into_code(self) -> u8 { self.0 }
 
     pub const fn info(self) -> NumInfo { NumInfo::from_code(self.0) }
@@ -306,7 +382,32 @@ impl NumCls {
 
 #[cfg(test)]
 mod test {
-    use crate::Primitive;
+    use crate::{InvalidPrimitive, Primitive};
+
+    #[test]
+    fn try_from_code() {
+        let rejected = (0..=u8::MAX)
+            .filter(|code| Primitive::try_from_code(*code).is_err())
+            .collect::<Vec<_>>();
+        let mut reserved = vec![0x80];
+        reserved.extend(Primitive::FLOAT_RESERVED.map(Primitive::into_code));
+        assert_eq!(rejected, reserved);
+        assert_eq!(rejected.len(), 55);
+
+        assert_eq!(Primitive::try_from_code(0x80), Err(InvalidPrimitive(0x80)));
+        assert_eq!(Primitive::try_from_code(0xC1), Err(InvalidPrimitive(0xC1)));
+        assert_eq!(Primitive::try_from_code(0xFF), Err(InvalidPrimitive(0xFF)));
+        assert_eq!(InvalidPrimitive(0xC1).to_string(), "primitive type code 0xc1 is reserved");
+
+        for prim in
+            [Primitive::UNIT, Primitive::BYTE, Primitive::F16B, Primitive::U8, Primitive::F16]
+        {
+            assert_eq!(Primitive::try_from_code(prim.into_code()), Ok(prim));
+        }
+        for code in (0..=u8::MAX).filter(|code| !reserved.contains(code)) {
+            let _ = Primitive::try_from_code(code).unwrap().to_string();
+        }
+    }
 
     #[test]
     fn unsigned_byte_size() {
@@ -20,10 +20,14 @@
 // limitations under the License.
 
 use std::io;
+use std::marker::PhantomData;
+
+use amplify::confinement;
 
 use crate::{
-    DecodeError, FieldName, ReadRaw, ReadStruct, ReadTuple, ReadUnion, StrictDecode, StrictEnum,
-    StrictStruct, StrictSum, StrictTuple, StrictUnion, TypedRead, VariantName,
+    DecodeError, DeserializeError, FieldName, ReadRaw, ReadStruct, ReadTuple, ReadUnion,
+    StrictDecode, StrictEnum, StrictStruct, StrictSum, StrictTuple, StrictUnion, TypedRead,
+    VariantName,
 };
 
 // TODO: Move to amplify crate
@@ -86,14 +90,41 @@ impl<R: io::Read> io::Read for ConfinedReader<R> {
     }
 }
 
+/// Raw reader over [`io::Read`] implementation, which fails once more than the
+/// given number of bytes are read.
+///
+/// Transient I/O errors are reported as [`DecodeError::Retryable`] only if the
+/// reader hasn't consumed any data before the error, since the consumed bytes
+/// are not kept and a retry would start in the middle of the value. Thus, when
+/// each value of a stream may need to be retried, the reader should be
+/// recreated for each of them, like `StrictReader::from_io::<MAX>(&mut stream)`.
 #[derive(Clone, Debug)]
 pub struct StreamReader<R: io::Read>(ConfinedReader<R>);
 
 impl<R: io::Read> StreamReader<R> {
     pub fn new<const MAX: usize>(inner: R) -> Self { Self(ConfinedReader::with(MAX, inner)) }
+
+    /// Constructs reader which fails once more than `limit` bytes are read,
+    /// or more than `MAX` bytes, if it is smaller than the `limit`.
+    pub fn with_limit<const MAX: usize>(inner: R, limit: usize) -> Self {
+        Self(ConfinedReader::with(MAX.min(limit), inner))
+    }
+
+    /// Returns the maximal number of bytes which can be read.
+    pub fn limit(&self) -> usize { self.0.limit }
     pub fn unconfine(self) -> R { self.0.unconfine() }
 }
 
+impl<R: io::Read> StreamReader<io::BufReader<R>> {
+    /// Constructs reader over `inner` wrapped into [`io::BufReader`], which
+    /// should be used for the readers which are slow on small reads, like
+    /// files or sockets. The data read ahead by the buffer don't count towards
+    /// the `MAX` limit until they are actually decoded.
+    pub fn buffered<const MAX: usize>(inner: R) -> Self {
+        Self::new::<MAX>(io::BufReader::new(inner))
+    }
+}
+
 impl<T: AsRef<[u8]>> StreamReader<io::Cursor<T>> {
     pub fn cursor<const MAX: usize>(inner: T) -> Self {
         Self(ConfinedReader::with(MAX, io::Cursor::new(inner)))
@@ -104,45 +135,361 @@ impl<R: io::Read> ReadRaw for StreamReader<R> {
     fn read_raw<const MAX_LEN: usize>(&mut self, len: usize) -> io::Result<Vec<u8>> {
         use io::Read;
         let mut buf = vec![0u8; len];
-        self.0.read_exact(&mut buf)?;
+        self.0.read_exact(&mut buf).map_err(|err| self.transient_err(err))?;
         Ok(buf)
     }
 
     fn read_raw_array<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]> {
         use io::Read;
         let mut buf = [0u8; LEN];
-        self.0.read_exact(&mut buf)?;
+        self.0.read_exact(&mut buf).map_err(|err| self.transient_err(err))?;
         Ok(buf)
     }
+
+    fn read_raw_into(&mut self, buf: &mut Vec<u8>, len: usize) -> io::Result<()> {
+        use io::Read;
+        let start = buf.len();
+        buf.resize(start + len, 0);
+        self.0.read_exact(&mut buf[start..]).map_err(|err| self.transient_err(err))
+    }
+
+    fn read_raw_to_end(&mut self) -> io::Result<Vec<u8>> {
+        use io::Read;
+        let mut buf = vec![];
+        self.0.read_to_end(&mut buf).map_err(|err| self.transient_err(err))?;
+        Ok(buf)
+    }
+
+    fn raw_count(&self) -> Option<usize> { Some(self.0.count) }
+}
+
+impl<R: io::Read> StreamReader<R> {
+    /// Transient errors (see [`DecodeError::Retryable`]) may be retried only
+    /// if no data were consumed yet: the bytes read before the error are lost,
+    /// so the retry would decode the value from its middle. Thus, such errors
+    /// are made non-transient once some data are read.
+    fn transient_err(&self, err: io::Error) -> io::Error {
+        match err.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted if self.0.count > 0 => {
+                io::Error::other(format!(
+                    "transient I/O error after {} bytes were read: {err}",
+                    self.0.count
+                ))
+            }
+            _ => err,
+        }
+    }
 }
 
 impl<T: AsRef<[u8]>> StreamReader<io::Cursor<T>> {
     pub fn in_memory<const MAX: usize>(data: T) -> Self { Self::new::<MAX>(io::Cursor::new(data)) }
     pub fn into_cursor(self) -> io::Cursor<T> { self.0.unconfine() }
+
+    /// Moves the cursor back to the beginning of the data and resets the count
+    /// of the bytes read, preserving the read limit.
+    pub fn rewind(&mut self) {
+        self.0.reader.set_position(0);
+        self.0.count = 0;
+    }
+
+    /// Replaces the data with a new one, resetting the count of the bytes read
+    /// and preserving the read limit.
+    pub fn reset(&mut self, data: T) {
+        self.0.reader = io::Cursor::new(data);
+        self.0.count = 0;
+    }
+
+    /// Reads `len` bytes, returning a slice borrowed from the underlying data
+    /// without copying or allocating them.
+    ///
+    /// Unlike [`ReadRaw::read_raw`], which allocates the buffer before reading
+    /// the data, the length is checked against the remaining data first, and
+    /// a length exceeding it is reported with
+    /// [`DecodeError::InsufficientData`]. Exceeding the read limit of the
+    /// reader produces the same I/O error as [`ReadRaw::read_raw`] does. In
+    /// both cases the reader position is not changed.
+    pub fn read_raw_slice(&mut self, len: usize) -> Result<&[u8], DecodeError> {
+        let pos = self.advance(len)?;
+        Ok(&self.0.reader.get_ref().as_ref()[pos..pos + len])
+    }
+
+    /// Reads `len` bytes like [`ReadRaw::read_raw`] does, but checks the length
+    /// against the remaining data before allocating the buffer. See
+    /// [`Self::read_raw_slice`] for the details.
+    pub fn read_raw_checked(&mut self, len: usize) -> Result<Vec<u8>, DecodeError> {
+        self.read_raw_slice(len).map(<[u8]>::to_vec)
+    }
+
+    /// Returns the number of bytes remaining in the data after the cursor.
+    pub fn remaining(&self) -> usize {
+        let data_len = self.0.reader.get_ref().as_ref().len();
+        data_len - (self.0.reader.position() as usize).min(data_len)
+    }
+
+    /// Moves the cursor `len` bytes forward, returning the position before the
+    /// move.
+    fn advance(&mut self, len: usize) -> Result<usize, DecodeError> {
+        // The cursor may be positioned past the end of the data by its owner
+        let data_len = self.0.reader.get_ref().as_ref().len();
+        let pos = (self.0.reader.position() as usize).min(data_len);
+        let remaining = data_len - pos;
+        if len > remaining {
+            return Err(DecodeError::InsufficientData(len, remaining));
+        }
+        match self.0.count.checked_add(len) {
+            None => return Err(io::Error::from(io::ErrorKind::OutOfMemory).into()),
+            Some(count) if count > self.0.limit => {
+                return Err(io::Error::from(io::ErrorKind::InvalidInput).into())
+            }
+            Some(count) => self.0.count = count,
+        }
+        self.0.reader.set_position((pos + len) as u64);
+        Ok(pos)
+    }
+}
+
+impl<'a> StreamReader<io::Cursor<&'a [u8]>> {
+    /// Reads `len` bytes like [`Self::read_raw_slice`] does, but returns a
+    /// slice borrowed for the lifetime of the underlying data rather than of
+    /// the reader, so it outlives the reader and further reads from it.
+    pub fn read_raw_ref(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let pos = self.advance(len)?;
+        let data: &'a [u8] = self.0.reader.get_ref();
+        Ok(&data[pos..pos + len])
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl StreamReader<io::Cursor<bytes::Bytes>> {
+    /// Reads `len` bytes like [`Self::read_raw_slice`] does, but returns them
+    /// as [`bytes::Bytes`] sharing the underlying buffer, so they are neither
+    /// copied nor borrowed from the reader.
+    pub fn read_raw_bytes(&mut self, len: usize) -> Result<bytes::Bytes, DecodeError> {
+        let pos = self.advance(len)?;
+        Ok(self.0.reader.get_ref().slice(pos..pos + len))
+    }
 }
 
 impl StreamReader<ReadCounter> {
     pub fn counter<const MAX: usize>() -> Self { Self::new::<MAX>(ReadCounter::default()) }
 }
 
-#[derive(Clone, Debug, From)]
-pub struct StrictReader<R: ReadRaw>(R);
+/// Default limit for the nesting depth of tuples, structures and unions
+/// decoded by [`StrictReader`].
+pub const DEFAULT_DEPTH_LIMIT: usize = 256;
+
+/// Maximal number of bytes read at once by [`StrictReader`] for byte vectors.
+const READ_CHUNK_LEN: usize = 0x10000;
+
+#[derive(Clone, Debug)]
+pub struct StrictReader<R: ReadRaw> {
+    reader: R,
+    depth: usize,
+    max_depth: usize,
+    depth_limit: usize,
+}
+
+impl<R: ReadRaw> From<R> for StrictReader<R> {
+    fn from(reader: R) -> Self { Self::with(reader) }
+}
+
+impl<R: io::Read> StrictReader<StreamReader<R>> {
+    /// Constructs reader over an arbitrary [`io::Read`] implementation, which
+    /// fails once more than `MAX` bytes are read.
+    ///
+    /// This is a shorthand for `StrictReader::with(StreamReader::new::<MAX>(reader))`.
+    ///
+    /// ```
+    /// # use strict_encoding::{StrictDecode, StrictReader};
+    /// let data: &[u8] = &[0xCD, 0xAB];
+    /// let mut reader = StrictReader::from_io::<2>(data);
+    /// assert_eq!(u16::strict_decode(&mut reader).unwrap(), 0xABCD);
+    ///
+    /// assert!(u16::strict_decode(&mut StrictReader::from_io::<1>(data)).is_err());
+    /// ```
+    pub fn from_io<const MAX: usize>(reader: R) -> Self {
+        Self::with(StreamReader::new::<MAX>(reader))
+    }
+
+    /// Constructs reader over an arbitrary [`io::Read`] implementation, which
+    /// fails once more than `limit` bytes are read, even if the `MAX` is
+    /// larger. This allows to cap the size of untrusted input at runtime
+    /// independently of the maximal size of the decoded type.
+    ///
+    /// ```
+    /// # use amplify::confinement::{LargeBlob, U32};
+    /// # use strict_encoding::{StrictDecode, StrictReader};
+    /// let data: &[u8] = &[3, 0, 0, 0, 1, 2, 3];
+    /// let mut reader = StrictReader::with_limit::<U32>(data, 7);
+    /// assert_eq!(LargeBlob::strict_decode(&mut reader).unwrap().as_slice(), &[1, 2, 3]);
+    ///
+    /// assert!(LargeBlob::strict_decode(&mut StrictReader::with_limit::<U32>(data, 6)).is_err());
+    /// ```
+    pub fn with_limit<const MAX: usize>(reader: R, limit: usize) -> Self {
+        Self::with(StreamReader::with_limit::<MAX>(reader, limit))
+    }
+}
+
+impl<'a> StrictReader<StreamReader<&'a mut dyn io::Read>> {
+    /// Constructs reader over an [`io::Read`] trait object, which fails once
+    /// more than `MAX` bytes are read.
+    ///
+    /// All the readers constructed in this way share the same type, so the
+    /// decoding code is instantiated once for all of them instead of once per
+    /// each concrete reader type. The price is a dynamic dispatch on every
+    /// read from the underlying reader, which, for unbuffered readers, happens
+    /// for each primitive value; thus the generic [`StrictReader::from_io`]
+    /// remains preferable for hot paths.
+    ///
+    /// ```
+    /// # use std::io;
+    /// # use strict_encoding::{StrictDecode, StrictReader};
+    /// fn read_u16(reader: &mut dyn io::Read) -> u16 {
+    ///     u16::strict_decode(&mut StrictReader::from_dyn::<2>(reader)).unwrap()
+    /// }
+    ///
+    /// let mut data: &[u8] = &[0xCD, 0xAB];
+    /// assert_eq!(read_u16(&mut data), 0xABCD);
+    /// ```
+    pub fn from_dyn<const MAX: usize>(reader: &'a mut dyn io::Read) -> Self {
+        Self::from_io::<MAX>(reader)
+    }
+}
 
 impl<T: AsRef<[u8]>> StrictReader<StreamReader<io::Cursor<T>>> {
     pub fn in_memory<const MAX: usize>(data: T) -> Self {
-        Self(StreamReader::in_memory::<MAX>(data))
+        Self::with(StreamReader::in_memory::<MAX>(data))
+    }
+    pub fn into_cursor(self) -> io::Cursor<T> { self.reader.into_cursor() }
+
+    /// Returns the number of bytes remaining in the data, which are not read
+    /// yet.
+    pub fn remaining(&self) -> usize { self.reader.remaining() }
+
+    /// Moves the reader back to the beginning of the data, allowing to decode
+    /// it once again within the same read limit.
+    pub fn rewind(&mut self) {
+        self.reader.rewind();
+        self.max_depth = 0;
+    }
+
+    /// Replaces the data with a new one, allowing to reuse the reader with its
+    /// read limit for decoding a next record.
+    pub fn reset(&mut self, data: T) {
+        self.reader.reset(data);
+        self.max_depth = 0;
+    }
+}
+
+impl<'a> StrictReader<StreamReader<io::Cursor<&'a [u8]>>> {
+    /// Reads a byte collection with length in `MIN_LEN..=MAX_LEN` range,
+    /// encoded in the same way as `Confined<Vec<u8>, MIN_LEN, MAX_LEN>`,
+    /// without copying the bytes.
+    ///
+    /// This is available only for readers over in-memory byte slices: the
+    /// returned slice borrows the data which were given to the reader and not
+    /// the reader itself, thus it remains valid after the reader is dropped,
+    /// but the data must outlive all borrowed slices.
+    pub fn read_bytes_ref<const MIN_LEN: usize, const MAX_LEN: usize>(
+        &mut self,
+    ) -> Result<&'a [u8], DecodeError> {
+        let len = self.read_bytes_len::<MIN_LEN, MAX_LEN>()?;
+        self.reader.read_raw_ref(len)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl StrictReader<StreamReader<io::Cursor<bytes::Bytes>>> {
+    /// Reads a byte collection with length in `MIN_LEN..=MAX_LEN` range,
+    /// encoded in the same way as `Confined<Vec<u8>, MIN_LEN, MAX_LEN>`,
+    /// without copying the bytes.
+    ///
+    /// The returned [`bytes::Bytes`] share the buffer given to the reader,
+    /// which is kept alive for as long as any of them exists.
+    pub fn read_bytes_shared<const MIN_LEN: usize, const MAX_LEN: usize>(
+        &mut self,
+    ) -> Result<bytes::Bytes, DecodeError> {
+        let len = self.read_bytes_len::<MIN_LEN, MAX_LEN>()?;
+        self.reader.read_raw_bytes(len)
     }
-    pub fn into_cursor(self) -> io::Cursor<T> { self.0.into_cursor() }
 }
 
 impl StrictReader<StreamReader<ReadCounter>> {
-    pub fn counter<const MAX: usize>() -> Self { Self(StreamReader::counter::<MAX>()) }
+    pub fn counter<const MAX: usize>() -> Self { Self::with(StreamReader::counter::<MAX>()) }
 }
 
 impl<R: ReadRaw> StrictReader<R> {
-    pub fn with(reader: R) -> Self { Self(reader) }
+    pub fn with(reader: R) -> Self { Self::with_depth_limit(reader, DEFAULT_DEPTH_LIMIT) }
+
+    /// Constructs reader which fails with [`DecodeError::RecursionLimit`] once
+    /// the nesting depth of tuples, structures and unions exceeds `depth_limit`.
+    pub fn with_depth_limit(reader: R, depth_limit: usize) -> Self {
+        Self {
+            reader,
+            depth: 0,
+            max_depth: 0,
+            depth_limit,
+        }
+    }
+
+    /// Returns the limit for the nesting depth of decoded data.
+    pub fn depth_limit(&self) -> usize { self.depth_limit }
+
+    /// Returns the maximum nesting depth of tuples, structures and unions
+    /// reached by the reader since its construction.
+    pub fn max_depth_reached(&self) -> usize { self.max_depth }
 
-    pub fn unbox(self) -> R { self.0 }
+    pub fn unbox(self) -> R { self.reader }
+
+    /// Skips `len` bytes of the data, like padding or reserved bytes, without
+    /// decoding them. The skipped bytes count towards the read limit of the
+    /// reader.
+    ///
+    /// Fails with the same errors as reading the bytes would, including
+    /// [`DecodeError::UnexpectedEof`] if less than `len` bytes remain.
+    pub fn skip_raw(&mut self, len: usize) -> Result<(), DecodeError> {
+        let mut left = len;
+        while left > 0 {
+            let chunk = left.min(READ_CHUNK_LEN);
+            self.reader.read_raw::<READ_CHUNK_LEN>(chunk)?;
+            left -= chunk;
+        }
+        Ok(())
+    }
+
+    fn enter(&mut self) -> Result<(), DecodeError> {
+        if self.depth >= self.depth_limit {
+            return Err(DecodeError::RecursionLimit(self.depth_limit));
+        }
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+        Ok(())
+    }
+
+    fn leave(&mut self) { self.depth -= 1; }
+
+    /// Reads the length of a byte collection and checks it against the
+    /// `MIN_LEN..=MAX_LEN` range.
+    fn read_bytes_len<const MIN_LEN: usize, const MAX_LEN: usize>(
+        &mut self,
+    ) -> Result<usize, DecodeError> {
+        let len = self.reader.read_raw_len::<MAX_LEN>()?;
+        if len < MIN_LEN {
+            return Err(confinement::Error::Undersize {
+                len,
+                min_len: MIN_LEN,
+            }
+            .into());
+        }
+        if len > MAX_LEN {
+            return Err(confinement::Error::Oversize {
+                len,
+                max_len: MAX_LEN,
+            }
+            .into());
+        }
+        Ok(len)
+    }
 }
 
 impl<R: ReadRaw> TypedRead for StrictReader<R> {
@@ -155,7 +502,7 @@ impl<R: ReadRaw> TypedRead for StrictReader<R> {
     type UnionReader = Self;
     type RawReader = R;
 
-    unsafe fn raw_reader(&mut self) -> &mut Self::RawReader { &mut self.0 }
+    unsafe fn raw_reader(&mut self) -> &mut Self::RawReader { &mut self.reader }
 
     fn read_union<T: StrictUnion>(
         &mut self,
@@ -165,7 +512,25 @@ impl<R: ReadRaw> TypedRead for StrictReader<R> {
         let tag = u8::strict_decode(self)?;
         let variant_name = T::variant_name_by_tag(tag)
             .ok_or(DecodeError::UnionTagNotKnown(name.to_string(), tag))?;
-        inner(variant_name, self)
+        self.enter()?;
+        let res = inner(variant_name, self);
+        self.leave();
+        res
+    }
+
+    fn read_union_lenient<T: StrictUnion>(
+        &mut self,
+        inner: impl FnOnce(VariantName, &mut Self::UnionReader) -> Result<T, DecodeError>,
+        unknown: impl FnOnce(u8, &mut Self::RawReader) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        let tag = u8::strict_decode(self)?;
+        let Some(variant_name) = T::variant_name_by_tag(tag) else {
+            return unknown(tag, &mut self.reader);
+        };
+        self.enter()?;
+        let res = inner(variant_name, self);
+        self.leave();
+        res
     }
 
     fn read_enum<T: StrictEnum>(&mut self) -> Result<T, DecodeError>
@@ -184,11 +549,14 @@ impl<R: ReadRaw> TypedRead for StrictReader<R> {
         'me: 'parent,
     {
         let name = T::strict_name().unwrap_or_else(|| tn!("__unnamed"));
+        self.enter()?;
         let mut reader = TupleReader {
             read_fields: 0,
             parent: self,
         };
-        let res = inner(&mut reader)?;
+        let res = inner(&mut reader);
+        reader.parent.leave();
+        let res = res?;
         assert_ne!(reader.read_fields, 0, "you forget to read fields for a tuple {}", name);
         assert_eq!(
             reader.read_fields,
@@ -208,11 +576,14 @@ impl<R: ReadRaw> TypedRead for StrictReader<R> {
         'me: 'parent,
     {
         let name = T::strict_name().unwrap_or_else(|| tn!("__unnamed"));
+        self.enter()?;
         let mut reader = StructReader {
             named_fields: empty!(),
             parent: self,
         };
-        let res = inner(&mut reader)?;
+        let res = inner(&mut reader);
+        reader.parent.leave();
+        let res = res?;
         assert!(!reader.named_fields.is_empty(), "you forget to read fields for a tuple {}", name);
 
         for field in T::ALL_FIELDS {
@@ -226,6 +597,107 @@ impl<R: ReadRaw> TypedRead for StrictReader<R> {
         assert!(reader.named_fields.is_empty(), "excessive fields are read for {}", name);
         Ok(res)
     }
+
+    unsafe fn read_bytes<const MAX_LEN: usize>(&mut self) -> Result<Vec<u8>, DecodeError> {
+        let len = self.reader.read_raw_len::<MAX_LEN>()?;
+        // The length is not trusted, so the memory is allocated only for the data actually read
+        let mut bytes = Vec::with_capacity(len.min(READ_CHUNK_LEN));
+        while bytes.len() < len {
+            let chunk = (len - bytes.len()).min(READ_CHUNK_LEN);
+            self.reader.read_raw_into(&mut bytes, chunk)?;
+        }
+        Ok(bytes)
+    }
+
+    unsafe fn read_byte_array<const LEN: usize>(&mut self) -> Result<[u8; LEN], DecodeError> {
+        self.reader.read_raw_array::<LEN>().map_err(DecodeError::from)
+    }
+}
+
+/// Iterator decoding a stream of concatenated records of the same type,
+/// constructed with [`strict_decode_iter`].
+#[derive(Debug)]
+pub struct DecodeIter<R: io::Read, T: StrictDecode> {
+    reader: StrictReader<StreamReader<R>>,
+    finished: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<R: io::Read, T: StrictDecode> DecodeIter<R, T> {
+    /// Returns the number of bytes consumed from the stream.
+    pub fn consumed(&self) -> usize { self.reader.reader.0.count() }
+
+    pub fn unbox(self) -> StrictReader<StreamReader<R>> { self.reader }
+}
+
+impl<R: io::Read, T: StrictDecode> Iterator for DecodeIter<R, T> {
+    type Item = Result<T, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let start = self.consumed();
+        match T::strict_decode(&mut self.reader) {
+            Ok(record) => Some(Ok(record)),
+            Err(err) if err.is_eof() && self.consumed() == start => {
+                self.finished = true;
+                None
+            }
+            Err(err) => {
+                self.finished = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Decodes a stream of concatenated records of type `T`, reading no more than
+/// `MAX` bytes in total.
+///
+/// The iterator ends once the stream is exhausted right at a record boundary.
+/// If the stream ends in the middle of a record (or any other decoding error
+/// happens), the iterator yields the error and ends after it. Thus, a
+/// truncated stream always results in an end-of-data error (see
+/// [`DecodeError::is_eof`]) as the last item.
+///
+/// The reader type is the last generic parameter, so it can be inferred, like
+/// in `strict_decode_iter::<T, MAX, _>(reader)`.
+pub fn strict_decode_iter<T: StrictDecode, const MAX: usize, R: io::Read>(
+    reader: R,
+) -> DecodeIter<R, T> {
+    DecodeIter {
+        reader: StrictReader::with(StreamReader::new::<MAX>(reader)),
+        finished: false,
+        _phantom: PhantomData,
+    }
+}
+
+/// Decodes a single value of type `T` from the reader, reading no more than
+/// `MAX` bytes, and checks that the reader has no data after it. This
+/// generalizes [`StrictDeserialize::from_strict_serialized`] to arbitrary
+/// readers.
+///
+/// # Errors
+///
+/// Fails with [`DeserializeError::DataNotEntirelyConsumed`] if the reader has
+/// more data after the decoded value; one byte of those data is consumed then.
+///
+/// [`StrictDeserialize::from_strict_serialized`]: crate::StrictDeserialize::from_strict_serialized
+pub fn strict_decode_exact<T: StrictDecode, const MAX: usize>(
+    reader: impl io::Read,
+) -> Result<T, DeserializeError> {
+    let mut reader = StrictReader::from_io::<MAX>(reader);
+    let me = T::strict_decode(&mut reader)?;
+    let mut rest = reader.unbox().unconfine();
+    loop {
+        match rest.read(&mut [0u8]) {
+            Ok(0) => return Ok(me),
+            Ok(_) => return Err(DeserializeError::DataNotEntirelyConsumed),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -252,6 +724,34 @@ impl<'parent, R: ReadRaw> ReadStruct for StructReader<'parent, R> {
         self.named_fields.push(field);
         T::strict_decode(self.parent)
     }
+
+    /// Tells an absent field from a truncated one by the number of bytes read,
+    /// if the underlying reader counts them with [`ReadRaw::raw_count`];
+    /// otherwise any end of data is taken for an absent field.
+    fn read_field_or_else<T: StrictDecode>(
+        &mut self,
+        field: FieldName,
+        default: impl FnOnce() -> T,
+    ) -> Result<T, DecodeError> {
+        let start = self.parent.reader.raw_count();
+        match self.read_field(field) {
+            Err(err) if err.is_eof() && self.parent.reader.raw_count() == start => Ok(default()),
+            res => res,
+        }
+    }
+
+    fn read_field_if<T: StrictDecode + Default>(
+        &mut self,
+        field: FieldName,
+        present: bool,
+    ) -> Result<T, DecodeError> {
+        if present {
+            self.read_field(field)
+        } else {
+            self.named_fields.push(field);
+            Ok(T::default())
+        }
+    }
 }
 
 impl<R: ReadRaw> ReadUnion for StrictReader<R> {
@@ -292,3 +792,435 @@ impl<R: ReadRaw> ReadUnion for StrictReader<R> {
         inner(&mut reader)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::VecDeque;
+
+    use amplify::confinement::{Confined, LargeBlob, MediumBlob, TinyBlob, U24, U32};
+
+    use super::*;
+    use crate::test::encode;
+    use crate::{StrictEncode, StrictType, StrictWriter};
+
+    #[test]
+    fn remaining() {
+        let data = encode(&(1u8, 2u16, TinyBlob::try_from(vec![3, 4]).unwrap()));
+        let mut reader = StrictReader::in_memory::<16>(data);
+        assert_eq!(reader.remaining(), 6);
+        u8::strict_decode(&mut reader).unwrap();
+        assert_eq!(reader.remaining(), 5);
+        u16::strict_decode(&mut reader).unwrap();
+        assert_eq!(reader.remaining(), 3);
+        TinyBlob::strict_decode(&mut reader).unwrap();
+        assert_eq!(reader.remaining(), 0);
+
+        reader.rewind();
+        assert_eq!(reader.remaining(), 6);
+    }
+
+    #[test]
+    fn byte_vec() {
+        // Longer than a single chunk read at once
+        let bytes = (0..0x12345).map(|i| i as u8).collect::<Vec<_>>();
+        let blob = MediumBlob::try_from(bytes.clone()).unwrap();
+        let data =
+            blob.strict_encode(StrictWriter::in_memory::<U24>()).unwrap().unbox().unconfine();
+        let mut reader = StrictReader::in_memory::<U24>(data.as_slice());
+        assert_eq!(MediumBlob::strict_decode(&mut reader).unwrap(), blob);
+        assert_eq!(reader.remaining(), 0);
+
+        // Byte-by-byte decoding of deques reads the same data
+        let mut reader = StrictReader::in_memory::<U24>(data.as_slice());
+        let deque = Confined::<VecDeque<u8>, 0, U24>::strict_decode(&mut reader).unwrap();
+        assert_eq!(deque.into_iter().collect::<Vec<_>>(), bytes);
+
+        // Length exceeding the actual data doesn't allocate the claimed memory
+        let mut reader = StrictReader::in_memory::<{ usize::MAX }>([0xFF, 0xFF, 0xFF, 0xFF, 1]);
+        let err = LargeBlob::strict_decode(&mut reader).unwrap_err();
+        assert!(matches!(err, DecodeError::UnexpectedEof));
+
+        // Length exceeding the maximum is rejected
+        let mut reader = StrictReader::in_memory::<{ usize::MAX }>([3, 1, 2, 3]);
+        assert!(Confined::<Vec<u8>, 0, 2>::strict_decode(&mut reader).is_err());
+    }
+
+    #[test]
+    fn read_raw_into() {
+        let mut reader = StreamReader::in_memory::<8>([1, 2, 3, 4, 5]);
+        let mut buf = Vec::with_capacity(4);
+        let ptr = buf.as_ptr();
+        reader.read_raw_into(&mut buf, 2).unwrap();
+        reader.read_raw_into(&mut buf, 1).unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+
+        // The buffer is reused without reallocation
+        buf.clear();
+        reader.read_raw_into(&mut buf, 2).unwrap();
+        assert_eq!(buf, [4, 5]);
+        assert_eq!(buf.as_ptr(), ptr);
+
+        let err = reader.read_raw_into(&mut buf, 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn skip_raw() {
+        let mut reader = StrictReader::in_memory::<8>([1, 0, 0, 0, 2, 0]);
+        assert_eq!(u8::strict_decode(&mut reader).unwrap(), 1);
+        reader.skip_raw(3).unwrap();
+        assert_eq!(u16::strict_decode(&mut reader).unwrap(), 2);
+        reader.skip_raw(0).unwrap();
+
+        // Skipping past the end of the data
+        let mut reader = StrictReader::in_memory::<8>([0, 0]);
+        let err = reader.skip_raw(3).unwrap_err();
+        assert!(matches!(err, DecodeError::UnexpectedEof));
+
+        // Skipped bytes count towards the read limit
+        let mut reader = StrictReader::in_memory::<4>([0u8; 8]);
+        assert!(reader.skip_raw(5).is_err());
+        let mut reader = StrictReader::in_memory::<4>([0u8; 8]);
+        reader.skip_raw(4).unwrap();
+        assert!(u8::strict_decode(&mut reader).is_err());
+    }
+
+    #[test]
+    fn max_depth() {
+        let val = Some(Box::new(Some((1u8, Some(2u16)))));
+        let data = encode(&val);
+
+        let mut reader = StrictReader::in_memory::<{ usize::MAX }>(data);
+        assert_eq!(reader.max_depth_reached(), 0);
+        let val2 = Option::<Box<Option<(u8, Option<u16>)>>>::strict_decode(&mut reader).unwrap();
+        assert_eq!(val2, val);
+        assert_eq!(reader.max_depth_reached(), 4);
+
+        let mut reader = StrictReader::in_memory::<{ usize::MAX }>([0u8]);
+        Option::<Box<Option<(u8, Option<u16>)>>>::strict_decode(&mut reader).unwrap();
+        assert_eq!(reader.max_depth_reached(), 1);
+    }
+
+    #[test]
+    fn depth_limit() {
+        #[derive(Clone, Eq, PartialEq, Debug, Default)]
+        #[derive(StrictType, StrictEncode, StrictDecode)]
+        #[strict_type(lib = "Test", crate = crate)]
+        struct Cons(Option<Box<Cons>>);
+
+        fn nested(levels: usize) -> Vec<u8> {
+            let mut data = vec![1u8; levels];
+            data.push(0);
+            data
+        }
+
+        // Each list element, including the terminating one, nests a tuple and an
+        // option union
+        let mut reader =
+            StrictReader::with_depth_limit(StreamReader::in_memory::<32>(nested(5)), 12);
+        let list = Cons::strict_decode(&mut reader).unwrap();
+        assert_eq!(reader.max_depth_reached(), 12);
+        assert_eq!(encode(&list), nested(5));
+
+        let mut reader =
+            StrictReader::with_depth_limit(StreamReader::in_memory::<32>(nested(5)), 11);
+        assert_eq!(Cons::strict_decode(&mut reader).unwrap_err(), DecodeError::RecursionLimit(11));
+
+        let data = nested(1_000_000);
+        let mut reader = StrictReader::in_memory::<{ usize::MAX }>(data);
+        assert_eq!(reader.depth_limit(), DEFAULT_DEPTH_LIMIT);
+        assert_eq!(
+            Cons::strict_decode(&mut reader).unwrap_err(),
+            DecodeError::RecursionLimit(DEFAULT_DEPTH_LIMIT)
+        );
+    }
+
+    #[test]
+    fn decode_iter() {
+        #[derive(Clone, Eq, PartialEq, Debug, Default)]
+        #[derive(StrictType, StrictEncode, StrictDecode)]
+        #[strict_type(lib = "Test", crate = crate)]
+        struct Record {
+            id: u8,
+            value: u16,
+        }
+
+        let records = [
+            Record {
+                id: 1,
+                value: 0x0102,
+            },
+            Record {
+                id: 2,
+                value: 0x0304,
+            },
+            Record {
+                id: 3,
+                value: 0x0506,
+            },
+        ];
+        let mut data = vec![];
+        for record in &records {
+            data.extend(encode(record));
+        }
+        assert_eq!(data, [1, 2, 1, 2, 4, 3, 3, 6, 5]);
+
+        let mut iter: DecodeIter<&[u8], Record> =
+            strict_decode_iter::<Record, 9, _>(data.as_slice());
+        for record in &records {
+            assert_eq!(&iter.next().unwrap().unwrap(), record);
+        }
+        assert!(iter.next().is_none());
+        assert_eq!(iter.consumed(), 9);
+
+        let mut iter = strict_decode_iter::<Record, 9, _>(&data[..8]);
+        assert_eq!(iter.next().unwrap().unwrap(), records[0]);
+        assert_eq!(iter.next().unwrap().unwrap(), records[1]);
+        assert!(matches!(iter.next(), Some(Err(DecodeError::UnexpectedEof))));
+        assert!(iter.next().is_none());
+
+        assert!(strict_decode_iter::<Record, 9, _>(&[][..]).next().is_none());
+        let mut iter = strict_decode_iter::<Record, 6, _>(data.as_slice());
+        assert_eq!(iter.by_ref().filter_map(Result::ok).count(), 2);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn retryable() {
+        struct WouldBlock;
+        impl io::Read for WouldBlock {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+        }
+
+        let mut reader = StrictReader::with(StreamReader::new::<1>(WouldBlock));
+        assert!(matches!(u8::strict_decode(&mut reader), Err(DecodeError::Retryable(_))));
+
+        // Once some data are consumed, the retry can't restart the value
+        let mut reader =
+            StrictReader::with(StreamReader::new::<4>(io::Read::chain(&[1u8][..], WouldBlock)));
+        assert!(matches!(u16::strict_decode(&mut reader), Err(DecodeError::Io(_))));
+        let mut reader =
+            StrictReader::with(StreamReader::new::<4>(io::Read::chain(&[1u8][..], WouldBlock)));
+        assert_eq!(u8::strict_decode(&mut reader).unwrap(), 1);
+        assert!(matches!(u8::strict_decode(&mut reader), Err(DecodeError::Io(_))));
+
+        let mut reader = StrictReader::in_memory::<1>([]);
+        assert!(matches!(u8::strict_decode(&mut reader), Err(DecodeError::UnexpectedEof)));
+    }
+
+    #[test]
+    fn truncated() {
+        let data = encode(&(0x01020304u32, 5u8));
+        for len in 0..data.len() {
+            let mut reader = StrictReader::in_memory::<8>(&data[..len]);
+            assert_eq!(
+                <(u32, u8)>::strict_decode(&mut reader).unwrap_err(),
+                DecodeError::UnexpectedEof
+            );
+        }
+
+        // Exceeding the read limit is not an end of data
+        let mut reader = StrictReader::in_memory::<2>(data);
+        assert!(matches!(u32::strict_decode(&mut reader), Err(DecodeError::Io(_))));
+    }
+
+    #[test]
+    fn rewind_reset() {
+        let mut reader = StrictReader::in_memory::<4>(encode(&(1u8, 2u16)));
+        assert_eq!(<(u8, u16)>::strict_decode(&mut reader).unwrap(), (1, 2));
+        assert!(u8::strict_decode(&mut reader).is_err());
+
+        reader.rewind();
+        assert_eq!(<(u8, u16)>::strict_decode(&mut reader).unwrap(), (1, 2));
+
+        reader.reset(encode(&(3u8, 4u16)));
+        assert_eq!(<(u8, u16)>::strict_decode(&mut reader).unwrap(), (3, 4));
+        assert_eq!(reader.into_cursor().position(), 3);
+    }
+
+    #[test]
+    fn read_raw_slice() {
+        let data = [1u8, 2, 3, 4, 5];
+        let mut reader = StreamReader::in_memory::<4>(data.as_slice());
+        assert_eq!(reader.read_raw_slice(2).unwrap(), &[1, 2]);
+        assert_eq!(reader.read_raw_checked(1).unwrap(), vec![3]);
+        // A length exceeding the remaining data is rejected before allocation
+        assert_eq!(
+            reader.read_raw_slice(usize::MAX).unwrap_err(),
+            DecodeError::InsufficientData(usize::MAX, 2)
+        );
+        // Read limit is still respected, leaving the position intact
+        assert!(matches!(reader.read_raw_slice(2).unwrap_err(), DecodeError::Io(_)));
+        assert_eq!(reader.read_raw_slice(1).unwrap(), &[4]);
+        assert_eq!(reader.read_raw_slice(0).unwrap(), &[] as &[u8]);
+        assert_eq!(reader.into_cursor().position(), 4);
+    }
+
+    #[test]
+    fn read_raw_slice_past_end() {
+        let data = [1u8, 2];
+        let mut cursor = io::Cursor::new(data.as_slice());
+        cursor.set_position(5);
+        let mut reader = StreamReader::new::<4>(cursor);
+        assert_eq!(reader.remaining(), 0);
+        assert_eq!(reader.read_raw_slice(0).unwrap(), &[] as &[u8]);
+        assert_eq!(reader.read_raw_ref(0).unwrap(), &[] as &[u8]);
+        let err = reader.read_raw_slice(1).unwrap_err();
+        assert_eq!(err, DecodeError::InsufficientData(1, 0));
+        assert!(err.is_eof());
+        assert!(DecodeError::UnexpectedEof.is_eof());
+        assert!(!DecodeError::DataIntegrityError(s!("test")).is_eof());
+    }
+
+    #[test]
+    fn read_bytes_ref() {
+        let data = encode(&(TinyBlob::try_from(vec![1, 2, 3]).unwrap(), 4u8));
+        let bytes = {
+            let mut reader = StrictReader::in_memory::<16>(data.as_slice());
+            let bytes = reader.read_bytes_ref::<0, { u8::MAX as usize }>().unwrap();
+            assert_eq!(u8::strict_decode(&mut reader).unwrap(), 4);
+            bytes
+        };
+        // The slice outlives the reader and points into the original data
+        assert_eq!(bytes, &[1, 2, 3]);
+        assert_eq!(bytes.as_ptr(), data[1..].as_ptr());
+
+        let mut reader = StrictReader::in_memory::<16>(data.as_slice());
+        assert_eq!(
+            reader.read_bytes_ref::<4, { u8::MAX as usize }>().unwrap_err(),
+            confinement::Error::Undersize { len: 3, min_len: 4 }.into()
+        );
+        let mut reader = StrictReader::in_memory::<16>(data.as_slice());
+        assert_eq!(
+            reader.read_bytes_ref::<0, 2>().unwrap_err(),
+            confinement::Error::Oversize { len: 3, max_len: 2 }.into()
+        );
+        let mut reader = StrictReader::in_memory::<16>(&data[..3]);
+        assert_eq!(
+            reader.read_bytes_ref::<0, { u8::MAX as usize }>().unwrap_err(),
+            DecodeError::InsufficientData(3, 2)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes_roundtrip() {
+        let value = (TinyBlob::try_from(vec![1, 2, 3]).unwrap(), 4u8);
+        let mut writer = value.strict_encode(StrictWriter::bytes_mut::<16>(16)).unwrap();
+        let data = writer.take_bytes().freeze();
+        assert_eq!(data, encode(&value));
+
+        let mut reader = StrictReader::in_memory::<16>(data.clone());
+        let bytes = reader.read_bytes_shared::<0, { u8::MAX as usize }>().unwrap();
+        assert_eq!(u8::strict_decode(&mut reader).unwrap(), 4);
+        // The bytes share the buffer with the original data
+        assert_eq!(bytes, &[1, 2, 3][..]);
+        assert_eq!(bytes.as_ptr(), data[1..].as_ptr());
+
+        let mut reader = StrictReader::in_memory::<16>(data.clone());
+        assert_eq!(<(TinyBlob, u8)>::strict_decode(&mut reader).unwrap(), value);
+
+        let mut reader = StrictReader::in_memory::<16>(data.clone());
+        assert_eq!(
+            reader.read_bytes_shared::<0, 2>().unwrap_err(),
+            confinement::Error::Oversize { len: 3, max_len: 2 }.into()
+        );
+        let mut reader = StrictReader::in_memory::<16>(data.slice(..3));
+        assert_eq!(
+            reader.read_bytes_shared::<0, { u8::MAX as usize }>().unwrap_err(),
+            DecodeError::InsufficientData(3, 2)
+        );
+    }
+
+    #[test]
+    fn with_limit() {
+        const MB: usize = 1 << 20;
+
+        let mut data = vec![];
+        data.extend((3 * MB as u32).to_le_bytes());
+        data.resize(4 + 3 * MB, 0xAB);
+
+        let mut reader = StrictReader::with_limit::<U32>(data.as_slice(), 2 * MB);
+        let err = LargeBlob::strict_decode(&mut reader).unwrap_err();
+        assert!(matches!(err, DecodeError::Io(e) if e.kind() == io::ErrorKind::InvalidInput));
+
+        let mut reader = StrictReader::with_limit::<U32>(data.as_slice(), 4 * MB);
+        assert_eq!(LargeBlob::strict_decode(&mut reader).unwrap().len(), 3 * MB);
+
+        // The smaller of the two limits applies
+        assert_eq!(StreamReader::with_limit::<U32>(data.as_slice(), 2 * MB).limit(), 2 * MB);
+        assert_eq!(StreamReader::with_limit::<16>(data.as_slice(), 2 * MB).limit(), 16);
+    }
+
+    #[test]
+    fn buffered() {
+        let data = [1u8, 2, 3, 4, 5, 6];
+        let mut reader = StrictReader::with(StreamReader::buffered::<4>(data.as_slice()));
+        // The data read ahead into the buffer do not count towards the limit
+        assert_eq!(u32::strict_decode(&mut reader).unwrap(), 0x04030201);
+        assert!(u8::strict_decode(&mut reader).is_err());
+
+        let mut reader = StrictReader::with(StreamReader::buffered::<6>(data.as_slice()));
+        assert_eq!(u32::strict_decode(&mut reader).unwrap(), 0x04030201);
+        // The remaining data stay in the buffer
+        let mut rest = reader.unbox().unconfine();
+        assert_eq!(io::BufRead::fill_buf(&mut rest).unwrap(), &[5, 6]);
+    }
+
+    #[test]
+    fn decode_exact() {
+        let data = encode(&(1u8, 0x0302u16));
+        assert_eq!(strict_decode_exact::<(u8, u16), 3>(data.as_slice()).unwrap(), (1, 0x0302));
+        assert_eq!(strict_decode_exact::<u16, 2>(&[2u8, 1][..]).unwrap(), 0x0102);
+
+        // Trailing data
+        assert_eq!(
+            strict_decode_exact::<u16, 3>(data.as_slice()).unwrap_err(),
+            DeserializeError::DataNotEntirelyConsumed
+        );
+        let mut reader = io::Cursor::new([1u8, 2, 3, 4]);
+        assert_eq!(
+            strict_decode_exact::<u16, 4>(&mut reader).unwrap_err(),
+            DeserializeError::DataNotEntirelyConsumed
+        );
+        assert_eq!(reader.position(), 3);
+
+        // Trailing data are checked without the `MAX` limit
+        assert_eq!(
+            strict_decode_exact::<u16, 2>(data.as_slice()).unwrap_err(),
+            DeserializeError::DataNotEntirelyConsumed
+        );
+        assert_eq!(
+            strict_decode_exact::<(u8, u16), 3>(&data[..2]).unwrap_err(),
+            DeserializeError::Decode(DecodeError::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn from_dyn() {
+        type Record = (u8, u16, TinyBlob);
+
+        fn write(record: &Record, writer: &mut dyn io::Write) -> io::Result<()> {
+            record.strict_encode(StrictWriter::from_dyn::<8>(writer)).map(|_| ())
+        }
+        fn read(reader: &mut dyn io::Read) -> Result<Record, DecodeError> {
+            Record::strict_decode(&mut StrictReader::from_dyn::<8>(reader))
+        }
+
+        let record = (1u8, 0x0302u16, TinyBlob::try_from(vec![4, 5]).unwrap());
+        let mut data = vec![];
+        write(&record, &mut data).unwrap();
+        assert_eq!(data, encode(&record));
+
+        let mut cursor = io::Cursor::new(&data);
+        assert_eq!(read(&mut cursor).unwrap(), record);
+        let mut bytes = data.as_slice();
+        assert_eq!(read(&mut bytes).unwrap(), record);
+
+        let large = (1u8, 2u16, TinyBlob::try_from(vec![0; 6]).unwrap());
+        assert!(write(&large, &mut vec![]).is_err());
+        assert!(read(&mut encode(&large).as_slice()).is_err());
+    }
+}
@@ -24,18 +24,22 @@
 use std::borrow::Borrow;
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
-use std::ops::Deref;
+use std::ops::{Deref, Range, RangeInclusive};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{any, io};
 
 use amplify::ascii::{AsAsciiStrError, AsciiChar, AsciiString, FromAsciiError};
 use amplify::confinement;
-use amplify::confinement::Confined;
+use amplify::confinement::{Confined, SmallString};
 use amplify::num::{u1, u2, u3, u4, u5, u6, u7};
 
+use crate::embedded::compose_id;
 use crate::{
-    type_name, DecodeError, StrictDecode, StrictDumb, StrictEncode, StrictEnum, StrictSum,
-    StrictType, TypeName, TypedRead, TypedWrite, VariantError, LIB_NAME_STD,
+    type_name, Byte, DecodeError, ReadTuple, StrictDecode, StrictDumb, StrictEncode, StrictEnum,
+    StrictProduct, StrictSum, StrictTuple, StrictType, StrictTypeId, TypeName, TypedRead,
+    TypedWrite, VariantError, WriteTuple, LIB_EMBEDDED, LIB_NAME_STD,
 };
 
 // TODO: Move RString and related ASCII types to amplify library
@@ -50,6 +54,9 @@ pub enum InvalidRString {
     DisallowedFirst(String, char),
 
     /// string '{0}' contains invalid character '{1}' at position {2}.
+    ///
+    /// The position is the index of the character (not of a byte) in the
+    /// string.
     InvalidChar(String, char, usize),
 
     #[from(AsAsciiStrError)]
@@ -109,6 +116,53 @@ impl<C1: RestrictedCharSet, C: RestrictedCharSet, const MIN: usize, const MAX: u
     fn borrow(&self) -> &str { self.s.as_str() }
 }
 
+impl<C1: RestrictedCharSet, C: RestrictedCharSet, const MIN: usize, const MAX: usize> PartialEq<str>
+    for RString<C1, C, MIN, MAX>
+{
+    fn eq(&self, other: &str) -> bool { self.s.as_str() == other }
+}
+
+impl<C1: RestrictedCharSet, C: RestrictedCharSet, const MIN: usize, const MAX: usize>
+    PartialEq<&str> for RString<C1, C, MIN, MAX>
+{
+    fn eq(&self, other: &&str) -> bool { self.s.as_str() == *other }
+}
+
+impl<C1: RestrictedCharSet, C: RestrictedCharSet, const MIN: usize, const MAX: usize>
+    PartialEq<RString<C1, C, MIN, MAX>> for str
+{
+    fn eq(&self, other: &RString<C1, C, MIN, MAX>) -> bool { self == other.s.as_str() }
+}
+
+impl<C1: RestrictedCharSet, C: RestrictedCharSet, const MIN: usize, const MAX: usize>
+    PartialEq<RString<C1, C, MIN, MAX>> for &str
+{
+    fn eq(&self, other: &RString<C1, C, MIN, MAX>) -> bool { *self == other.s.as_str() }
+}
+
+impl<C1: RestrictedCharSet, C: RestrictedCharSet, const MIN: usize, const MAX: usize>
+    RString<C1, C, MIN, MAX>
+{
+    /// Parses the string, checking it against the character sets and the
+    /// length bounds. Same as [`FromStr::from_str`].
+    #[inline]
+    pub fn parse(s: &str) -> Result<Self, InvalidRString> { Self::from_str(s) }
+
+    /// Checks whether the string matches the character sets and the length
+    /// bounds without constructing the value. The first character is checked
+    /// against `C1` and the rest of characters against `C` charset.
+    pub fn is_valid(s: &str) -> bool {
+        let bytes = s.as_bytes();
+        if bytes.is_empty() {
+            return MIN == 0;
+        }
+        if bytes.len() < MIN || bytes.len() > MAX {
+            return false;
+        }
+        C1::try_from(bytes[0]).is_ok() && bytes[1..].iter().all(|ch| C::try_from(*ch).is_ok())
+    }
+}
+
 impl<C1: RestrictedCharSet, C: RestrictedCharSet, const MIN: usize, const MAX: usize> FromStr
     for RString<C1, C, MIN, MAX>
 {
@@ -166,23 +220,25 @@ impl<C1: RestrictedCharSet, C: RestrictedCharSet, const MIN: usize, const MAX: u
                 rest: PhantomData,
             });
         }
+        // Characters are checked on the UTF-8 representation, such that the
+        // reported invalid character and its position always match (non-UTF-8
+        // byte sequences are reported as U+FFFD replacement characters).
         let utf8 = String::from_utf8_lossy(bytes);
-        let mut iter = bytes.iter();
-        let Some(first) = iter.next() else {
+        let is_valid = |ch: char, first: bool| {
+            u8::try_from(ch).is_ok_and(|byte| match first {
+                true => C1::try_from(byte).is_ok(),
+                false => C::try_from(byte).is_ok(),
+            })
+        };
+        let mut chars = utf8.chars();
+        let Some(first) = chars.next() else {
             return Err(InvalidRString::Empty);
         };
-        if C1::try_from(*first).is_err() {
-            return Err(InvalidRString::DisallowedFirst(
-                utf8.to_string(),
-                utf8.chars().next().unwrap_or('?'),
-            ));
+        if !is_valid(first, true) {
+            return Err(InvalidRString::DisallowedFirst(utf8.to_string(), first));
         }
-        if let Some(pos) = iter.position(|ch| C::try_from(*ch).is_err()) {
-            return Err(InvalidRString::InvalidChar(
-                utf8.to_string(),
-                utf8.chars().nth(pos + 1).unwrap_or('?'),
-                pos + 1,
-            ));
+        if let Some((pos, ch)) = chars.enumerate().find(|(_, ch)| !is_valid(*ch, false)) {
+            return Err(InvalidRString::InvalidChar(utf8.to_string(), ch, pos + 1));
         }
         let s = Confined::try_from(
             AsciiString::from_ascii(bytes).expect("not an ASCII characted subset"),
@@ -273,6 +329,10 @@ impl StrictType for bool {
     const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
     fn strict_name() -> Option<TypeName> { Some(tn!("Bool")) }
 }
+impl StrictTypeId for bool {
+    const STRICT_ID: u64 = compose_id("Bool", &[]);
+    const WIRE_FINGERPRINT: u64 = compose_id("Bool", &[]);
+}
 impl StrictEncode for bool {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
         writer.write_enum::<Bool>(Bool::from(self))
@@ -344,6 +404,318 @@ impl_u!(U7, u7, _0 _1 _2 _3 _4 _5 _6 _7 _8 _9
                 _110 _111 _112 _113 _114 _115 _116 _117 _118 _119
                 _120 _121 _122 _123 _124 _125 _126 _127);
 
+/// Unsigned integer with a bit width `N` in range `1..=64`, which is encoded
+/// into the minimal number of bytes able to hold `N` bits in little-endian
+/// order. Unused high bits of the last byte must be zero.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Bits<const N: u32>(u64);
+
+impl<const N: u32> Bits<N> {
+    /// Number of bytes used by the encoding.
+    pub const BYTES: usize = {
+        assert!(N > 0 && N <= 64, "bit width must be in range 1..=64");
+        N.div_ceil(8) as usize
+    };
+
+    /// Maximal value which can be represented with `N` bits.
+    pub const MAX: u64 = if N >= 64 { u64::MAX } else { (1u64 << N) - 1 };
+
+    /// Constructs a value from an unsigned integer, returning `None` if the
+    /// value doesn't fit into `N` bits.
+    pub const fn try_with(value: u64) -> Option<Self> {
+        if value > Self::MAX {
+            return None;
+        }
+        Some(Self(value))
+    }
+
+    /// Constructs a value from an unsigned integer.
+    ///
+    /// # Panics
+    ///
+    /// If the value doesn't fit into `N` bits.
+    pub const fn with(value: u64) -> Self {
+        match Self::try_with(value) {
+            Some(bits) => bits,
+            None => panic!("value doesn't fit into the bit width of the type"),
+        }
+    }
+
+    pub const fn to_u64(self) -> u64 { self.0 }
+}
+
+impl<const N: u32> From<Bits<N>> for u64 {
+    fn from(bits: Bits<N>) -> Self { bits.0 }
+}
+
+impl<const N: u32> StrictType for Bits<N> {
+    const STRICT_LIB_NAME: &'static str = LIB_EMBEDDED;
+    fn strict_name() -> Option<TypeName> { None }
+}
+impl<const N: u32> StrictEncode for Bits<N> {
+    fn strict_encode<W: TypedWrite>(&self, mut writer: W) -> io::Result<W> {
+        for byte in &self.0.to_le_bytes()[..Self::BYTES] {
+            writer = byte.strict_encode(writer)?;
+        }
+        Ok(unsafe { writer.register_array(&Byte::strict_dumb(), Self::BYTES as u16) })
+    }
+}
+impl<const N: u32> StrictDecode for Bits<N> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let mut bytes = [0u8; 8];
+        for byte in &mut bytes[..Self::BYTES] {
+            *byte = u8::strict_decode(reader)?;
+        }
+        Self::try_with(u64::from_le_bytes(bytes)).ok_or(DecodeError::NonZeroPadding)
+    }
+}
+
+/// Unicode scalar value, encoded as a little-endian `u32` newtype.
+impl StrictType for char {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Char")) }
+}
+impl StrictTypeId for char {
+    const STRICT_ID: u64 = compose_id("Char", &[]);
+    const WIRE_FINGERPRINT: u64 = compose_id("Char", &[]);
+}
+impl StrictProduct for char {}
+impl StrictTuple for char {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for char {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&u32::from(*self))
+    }
+}
+impl StrictDecode for char {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let code: u32 = r.read_field()?;
+            char::from_u32(code).ok_or(DecodeError::InvalidCharCode(code))
+        })
+    }
+}
+
+/// Named zero-sized placeholder type.
+///
+/// Unlike `()`, which is an anonymous `UNIT` primitive, `Unit` is a named
+/// newtype around it, so the schemas can reference it by its name. Unlike
+/// `PhantomData`, which is not encoded and doesn't participate in the type
+/// system at all, `Unit` registers itself in the type library. All three of
+/// them serialize to zero bytes.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Unit;
+
+impl StrictType for Unit {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Unit")) }
+}
+impl StrictTypeId for Unit {
+    const STRICT_ID: u64 = compose_id("Unit", &[]);
+    const WIRE_FINGERPRINT: u64 = compose_id("Unit", &[]);
+}
+impl StrictProduct for Unit {}
+impl StrictTuple for Unit {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for Unit {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&())
+    }
+}
+impl StrictDecode for Unit {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let () = r.read_field()?;
+            Ok(Unit)
+        })
+    }
+}
+
+/// Half-open range, encoded as a tuple of its start and end.
+///
+/// Decoding fails with [`DecodeError::DataIntegrityError`] if the start of the
+/// range exceeds its end.
+impl<T: StrictType + Default> StrictType for Range<T> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("Range")) }
+}
+impl<T: StrictType + Default> StrictProduct for Range<T> {}
+impl<T: StrictType + Default> StrictTuple for Range<T> {
+    const FIELD_COUNT: u8 = 2;
+}
+impl<T: StrictEncode + Default> StrictEncode for Range<T> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_tuple::<Self>(|w| {
+            Ok(w.write_field(&self.start)?.write_field(&self.end)?.complete())
+        })
+    }
+}
+impl<T: StrictDecode + Default + PartialOrd> StrictDecode for Range<T> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let start = r.read_field()?;
+            let end = r.read_field()?;
+            check_range_bounds(&start, &end)?;
+            Ok(start..end)
+        })
+    }
+}
+
+/// Tuple representation of [`RangeInclusive`], which is not [`Default`] and
+/// thus can't be a strict product type itself.
+#[derive(Default)]
+struct InclusiveRange<T>(T, T);
+
+impl<T: StrictType> StrictType for InclusiveRange<T> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { Some(tn!("RangeInclusive")) }
+}
+impl<T: StrictType + Default> StrictProduct for InclusiveRange<T> {}
+impl<T: StrictType + Default> StrictTuple for InclusiveRange<T> {
+    const FIELD_COUNT: u8 = 2;
+}
+
+/// Closed range, encoded as a tuple of its start and end.
+///
+/// Decoding fails with [`DecodeError::DataIntegrityError`] if the start of the
+/// range exceeds its end.
+impl<T: StrictType> StrictType for RangeInclusive<T> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { InclusiveRange::<T>::strict_name() }
+}
+impl<T: StrictEncode + Default> StrictEncode for RangeInclusive<T> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_tuple::<InclusiveRange<T>>(|w| {
+            Ok(w.write_field(self.start())?.write_field(self.end())?.complete())
+        })
+    }
+}
+impl<T: StrictDecode + Default + PartialOrd> StrictDecode for RangeInclusive<T> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let InclusiveRange(start, end) = reader.read_tuple(|r| {
+            let start = r.read_field()?;
+            let end = r.read_field()?;
+            check_range_bounds(&start, &end)?;
+            Ok(InclusiveRange(start, end))
+        })?;
+        Ok(start..=end)
+    }
+}
+
+fn check_range_bounds<T: PartialOrd>(start: &T, end: &T) -> Result<(), DecodeError> {
+    if start > end {
+        return Err(DecodeError::DataIntegrityError(s!("range start exceeds its end")));
+    }
+    Ok(())
+}
+
+/// Structure representation of [`SystemTime`], which is not [`Default`] and
+/// thus can't be a strict product type itself.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STD, rename = "SystemTime", crate = crate)]
+struct UnixTime {
+    secs: u64,
+    nanos: u32,
+}
+
+/// System time, encoded as a duration since the Unix epoch: seconds (`u64`)
+/// and subsecond nanoseconds (`u32`).
+///
+/// Encoding fails with [`io::ErrorKind::InvalidInput`] for the time before the
+/// Unix epoch; decoding fails with [`DecodeError::DataIntegrityError`] if the
+/// number of nanoseconds exceeds a second or the time is not representable
+/// on the platform.
+impl StrictType for SystemTime {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { UnixTime::strict_name() }
+}
+impl StrictEncode for SystemTime {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let duration = self.duration_since(UNIX_EPOCH).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "system time precedes the Unix epoch")
+        })?;
+        let time = UnixTime {
+            secs: duration.as_secs(),
+            nanos: duration.subsec_nanos(),
+        };
+        time.strict_encode(writer)
+    }
+}
+impl StrictDecode for SystemTime {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let time = UnixTime::strict_decode(reader)?;
+        if time.nanos >= 1_000_000_000 {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "system time has {} nanoseconds, exceeding a second",
+                time.nanos
+            )));
+        }
+        UNIX_EPOCH
+            .checked_add(Duration::new(time.secs, time.nanos))
+            .ok_or_else(|| DecodeError::DataIntegrityError(s!("system time is out of range")))
+    }
+}
+
+/// Newtype representation of [`PathBuf`], holding the path as a UTF-8
+/// string.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STD, rename = "Path", crate = crate)]
+struct PathString(SmallString);
+
+impl PathString {
+    fn with(path: &Path) -> io::Result<Self> {
+        let s = path.to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("path '{}' is not a valid UTF-8 string", path.display()),
+            )
+        })?;
+        SmallString::try_from(s.to_owned())
+            .map(Self)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+    }
+}
+
+/// File system path, encoded as a UTF-8 string of up to `u16::MAX` bytes.
+///
+/// The path is written verbatim, including its separators and prefixes, so a
+/// path encoded on one platform may not be meaningful on another one (for
+/// instance, Windows paths containing backslashes and drive letters).
+///
+/// Encoding fails with [`io::ErrorKind::InvalidInput`] if the path is not a
+/// valid UTF-8 string or is too long; when serializing, the error is reported
+/// as [`crate::SerializeError::Io`].
+impl StrictType for PathBuf {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { PathString::strict_name() }
+}
+impl StrictEncode for PathBuf {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.as_path().strict_encode(writer)
+    }
+}
+impl StrictDecode for PathBuf {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        let PathString(s) = PathString::strict_decode(reader)?;
+        Ok(PathBuf::from(s.release()))
+    }
+}
+
+/// Borrowed file system path, encoded in the same way as [`PathBuf`].
+impl StrictType for &Path {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+    fn strict_name() -> Option<TypeName> { PathString::strict_name() }
+}
+impl StrictEncode for &Path {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        PathString::with(self)?.strict_encode(writer)
+    }
+}
+
 #[derive(Wrapper, WrapperMut, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, From)]
 #[wrapper(Deref, Display, Debug)]
 #[wrapper_mut(DerefMut)]
@@ -530,6 +902,9 @@ impl TryFrom<u8> for AsciiPrintable {
     type Error = VariantError<u8>;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if !(b' '..=b'~').contains(&value) {
+            return Err(VariantError::with::<AsciiPrintable>(value));
+        }
         AsciiChar::from_ascii(value)
             .map_err(|_| VariantError::with::<AsciiPrintable>(value))
             .map(Self)
@@ -656,6 +1031,64 @@ impl StrictDecode for AsciiPrintable {
     }
 }
 
+/// Printable ASCII characters excluding space.
+#[derive(Wrapper, WrapperMut, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, From)]
+#[wrapper(Deref, Display, Debug)]
+#[wrapper_mut(DerefMut)]
+#[derive(StrictDumb)]
+#[strict_type(lib = LIB_NAME_STD, dumb = Self(AsciiChar::A), crate = crate)]
+pub struct AsciiGraphic(AsciiChar);
+
+impl From<AsciiGraphic> for u8 {
+    fn from(value: AsciiGraphic) -> Self { value.0.as_byte() }
+}
+
+impl TryFrom<u8> for AsciiGraphic {
+    type Error = VariantError<u8>;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if !value.is_ascii_graphic() {
+            return Err(VariantError::with::<AsciiGraphic>(value));
+        }
+        AsciiChar::from_ascii(value)
+            .map_err(|_| VariantError::with::<AsciiGraphic>(value))
+            .map(Self)
+    }
+}
+
+impl StrictType for AsciiGraphic {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_STD;
+}
+
+impl StrictSum for AsciiGraphic {
+    // All printable characters except the leading space
+    const ALL_VARIANTS: &'static [(u8, &'static str)] = AsciiPrintable::ALL_VARIANTS.split_at(1).1;
+    fn variant_name(&self) -> &'static str {
+        Self::ALL_VARIANTS
+            .iter()
+            .find(|(s, _)| *s == self.as_byte())
+            .map(|(_, v)| *v)
+            .expect("missed ASCII character variant")
+    }
+}
+impl StrictEnum for AsciiGraphic {}
+impl StrictEncode for AsciiGraphic {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> { writer.write_enum(*self) }
+}
+impl StrictDecode for AsciiGraphic {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_enum()
+    }
+}
+
+/// String of printable ASCII characters, including spaces.
+pub type AsciiPrintableString<const MIN: usize = 1, const MAX: usize = 255> =
+    RString<AsciiPrintable, AsciiPrintable, MIN, MAX>;
+
+/// String of printable ASCII characters without spaces.
+pub type AsciiGraphicString<const MIN: usize = 1, const MAX: usize = 255> =
+    RString<AsciiGraphic, AsciiGraphic, MIN, MAX>;
+
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
 #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_STD, tags = repr, into_u8, try_from_u8, crate = crate)]
@@ -1720,25 +2153,61 @@ pub enum AlphaNumLodash {
     z = b'z',
 }
 
-impl RestrictedCharSet for AsciiPrintable {}
-impl RestrictedCharSet for AsciiSym {}
-impl RestrictedCharSet for Alpha {}
-impl RestrictedCharSet for AlphaLodash {}
-impl RestrictedCharSet for AlphaCaps {}
-impl RestrictedCharSet for AlphaCapsLodash {}
-impl RestrictedCharSet for AlphaSmall {}
-impl RestrictedCharSet for AlphaSmallLodash {}
-impl RestrictedCharSet for AlphaNum {}
-impl RestrictedCharSet for AlphaNumDash {}
-impl RestrictedCharSet for AlphaNumLodash {}
-impl RestrictedCharSet for AlphaCapsNum {}
-impl RestrictedCharSet for Dec {}
-impl RestrictedCharSet for HexDecCaps {}
-impl RestrictedCharSet for HexDecSmall {}
+macro_rules! impl_charset {
+    ($($ty:ident),+ $(,)?) => { $(
+        impl RestrictedCharSet for $ty {}
+
+        impl TryFrom<char> for $ty {
+            type Error = VariantError<char>;
+
+            fn try_from(ch: char) -> Result<Self, Self::Error> {
+                u8::try_from(ch)
+                    .ok()
+                    .filter(u8::is_ascii)
+                    .and_then(|byte| $ty::try_from(byte).ok())
+                    .ok_or_else(|| VariantError::with::<$ty>(ch))
+            }
+        }
+    )+ };
+}
+
+impl_charset!(
+    AsciiPrintable,
+    AsciiGraphic,
+    AsciiSym,
+    Alpha,
+    AlphaLodash,
+    AlphaCaps,
+    AlphaCapsLodash,
+    AlphaSmall,
+    AlphaSmallLodash,
+    AlphaNum,
+    AlphaNumDash,
+    AlphaNumLodash,
+    AlphaCapsNum,
+    Dec,
+    HexDecCaps,
+    HexDecSmall,
+);
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test::encoding;
+    use crate::{StrictReader, StrictWriter};
+
+    #[test]
+    fn bool_array() {
+        encoding(&[true, false, true], [1, 0, 1]);
+        encoding(&[false; 8], [0; 8]);
+        encoding(&[[true, false], [false, true]], [1, 0, 0, 1]);
+
+        let mut reader = StrictReader::in_memory::<4>([1, 2]);
+        assert!(matches!(
+            <[bool; 2]>::strict_decode(&mut reader),
+            Err(DecodeError::EnumTagNotKnown(name, 2)) if name == "Bool"
+        ));
+    }
 
     #[test]
     fn rstring_utf8() {
@@ -1753,5 +2222,241 @@ mod test {
             RString::<AlphaCaps, Alpha, 1, 8>::from_str(s).unwrap_err(),
             InvalidRString::InvalidChar(s.to_owned(), 'н', 1)
         );
+
+        let s = "ABCЮ";
+        assert_eq!(
+            RString::<AlphaCaps, Alpha, 1, 8>::from_str(s).unwrap_err(),
+            InvalidRString::InvalidChar(s.to_owned(), 'Ю', 3)
+        );
+
+        let s = "AB ünï";
+        assert_eq!(
+            RString::<AlphaCaps, AsciiPrintable, 1, 8>::from_str(s).unwrap_err(),
+            InvalidRString::InvalidChar(s.to_owned(), 'ü', 3)
+        );
+
+        let s = "ab€";
+        assert_eq!(
+            RString::<AlphaSmall, AlphaSmall, 1, 8>::from_str(s).unwrap_err(),
+            InvalidRString::InvalidChar(s.to_owned(), '€', 2)
+        );
+
+        let bytes = [b'A', b'B', 0xE2, 0x82, b'C'];
+        assert_eq!(
+            RString::<AlphaCaps, Alpha, 1, 8>::try_from(&bytes[..]).unwrap_err(),
+            InvalidRString::InvalidChar(s!("AB\u{FFFD}C"), '\u{FFFD}', 2)
+        );
+    }
+
+    #[test]
+    fn rstring_parse() {
+        type Ident = RString<AlphaCaps, AlphaNum, 2, 4>;
+
+        for s in ["AB", "A1", "Zz9", "ABCD"] {
+            assert!(Ident::is_valid(s));
+            let ident = Ident::parse(s).unwrap();
+            assert_eq!(ident.as_str(), s);
+            assert_eq!(ident.to_string().parse::<Ident>().unwrap(), ident);
+            assert_eq!(Ident::try_from(s.to_owned()).unwrap(), ident);
+        }
+
+        for s in ["", "A", "ABCDE", "1A", "aB", "A_", "AЮ"] {
+            assert!(!Ident::is_valid(s));
+            assert!(Ident::parse(s).is_err());
+        }
+
+        // Numbers are allowed in the rest, but not in the first position
+        assert!(RString::<AlphaNum, AlphaCaps, 1, 4>::is_valid("1AB"));
+        assert!(!RString::<AlphaNum, AlphaCaps, 1, 4>::is_valid("A1B"));
+
+        assert!(RString::<AlphaCaps, AlphaCaps, 0, 4>::is_valid(""));
+        assert_eq!(RString::<AlphaCaps, AlphaCaps, 0, 4>::parse("").unwrap().as_str(), "");
+    }
+
+    #[test]
+    fn rstring_str_eq() {
+        let ident = RString::<AlphaCaps, AlphaNum, 1, 8>::from("ABC1");
+        assert_eq!(ident, "ABC1");
+        assert_eq!(ident, *"ABC1");
+        assert_eq!("ABC1", ident);
+        assert_eq!(*"ABC1", ident);
+        assert_ne!(ident, "ABC");
+        assert_ne!(ident, "abc1");
+        assert_ne!("ABC12", ident);
+        assert_ne!(*"", ident);
+    }
+
+    #[test]
+    fn named_unit() {
+        encoding(&Unit, []);
+        encoding(&(), []);
+        assert_eq!(Unit::strict_name(), Some(tn!("Unit")));
+        assert_eq!(<()>::strict_name(), None);
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(Range::<u16>::strict_name(), Some(tn!("Range")));
+        assert_eq!(RangeInclusive::<u16>::strict_name(), Some(tn!("RangeInclusive")));
+
+        encoding(&(0x0102u16..0x0304), [0x02, 0x01, 0x04, 0x03]);
+        encoding(&(5u8..5), [5, 5]);
+        encoding(&(0x0102u16..=0x0304), [0x02, 0x01, 0x04, 0x03]);
+        encoding(&(5u8..=5), [5, 5]);
+
+        let err = DecodeError::DataIntegrityError(s!("range start exceeds its end"));
+        assert_eq!(
+            Range::<u8>::strict_decode(&mut StrictReader::in_memory::<2>([2, 1])).unwrap_err(),
+            err
+        );
+        assert_eq!(
+            RangeInclusive::<u8>::strict_decode(&mut StrictReader::in_memory::<2>([2, 1]))
+                .unwrap_err(),
+            err
+        );
+    }
+
+    #[test]
+    fn path() {
+        assert_eq!(PathBuf::strict_name(), Some(tn!("Path")));
+        assert_eq!(<&Path>::strict_name(), Some(tn!("Path")));
+        assert_eq!(PathBuf::STRICT_LIB_NAME, LIB_NAME_STD);
+
+        let path = PathBuf::from("config/node.toml");
+        let mut data = vec![16, 0];
+        data.extend_from_slice(b"config/node.toml");
+        encoding(&path, &data);
+        assert_eq!(crate::test::encode(&path.as_path()), data);
+        encoding(&PathBuf::new(), [0, 0]);
+        // Separators are kept as is
+        encoding(&PathBuf::from(r"C:\data"), [7, 0, b'C', b':', b'\\', b'd', b'a', b't', b'a']);
+
+        let err = PathBuf::from("a".repeat(0x10000))
+            .strict_encode(StrictWriter::in_memory::<0x20000>())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn path_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"dir/\xFF"));
+        let err = path.strict_encode(StrictWriter::in_memory::<16>()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "path 'dir/\u{FFFD}' is not a valid UTF-8 string");
+    }
+
+    #[test]
+    fn system_time() {
+        assert_eq!(SystemTime::strict_name(), Some(tn!("SystemTime")));
+
+        let now = SystemTime::now();
+        let data = crate::test::encode(&now);
+        assert_eq!(data.len(), 12);
+        let decoded = SystemTime::strict_decode(&mut StrictReader::in_memory::<12>(data)).unwrap();
+        assert_eq!(decoded, now);
+
+        let time = UNIX_EPOCH + Duration::new(0x0102, 3);
+        encoding(&time, [2, 1, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0]);
+        encoding(&UNIX_EPOCH, [0; 12]);
+
+        let err = (UNIX_EPOCH - Duration::from_secs(1))
+            .strict_encode(StrictWriter::in_memory::<12>())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let data = [0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0xCA, 0x9A, 0x3B];
+        assert_eq!(
+            SystemTime::strict_decode(&mut StrictReader::in_memory::<12>(data)).unwrap_err(),
+            DecodeError::DataIntegrityError(s!(
+                "system time has 1000000000 nanoseconds, exceeding a second"
+            ))
+        );
+    }
+
+    #[test]
+    fn bits12() {
+        assert_eq!(Bits::<12>::BYTES, 2);
+        assert_eq!(Bits::<12>::MAX, 0xFFF);
+        assert_eq!(Bits::<12>::try_with(0x1000), None);
+        encoding(&Bits::<12>::with(0xABC), [0xBC, 0x0A]);
+        encoding(&Bits::<12>::with(0), [0x00, 0x00]);
+        encoding(&Bits::<12>::with(0xFFF), [0xFF, 0x0F]);
+        assert_eq!(
+            Bits::<12>::strict_decode(&mut StrictReader::in_memory::<2>([0xFF, 0x1F])).unwrap_err(),
+            DecodeError::NonZeroPadding
+        );
+    }
+
+    #[test]
+    fn bits20() {
+        assert_eq!(Bits::<20>::BYTES, 3);
+        assert_eq!(Bits::<20>::MAX, 0xF_FFFF);
+        encoding(&Bits::<20>::with(0xA_BCDE), [0xDE, 0xBC, 0x0A]);
+        encoding(&Bits::<20>::with(0xF_FFFF), [0xFF, 0xFF, 0x0F]);
+        assert_eq!(
+            Bits::<20>::strict_decode(&mut StrictReader::in_memory::<3>([0x00, 0x00, 0x10]))
+                .unwrap_err(),
+            DecodeError::NonZeroPadding
+        );
+    }
+
+    #[test]
+    fn unicode_char() {
+        encoding(&'a', [0x61, 0x00, 0x00, 0x00]);
+        encoding(&'Ю', [0x2E, 0x04, 0x00, 0x00]);
+        encoding(&'\u{10FFFF}', [0xFF, 0xFF, 0x10, 0x00]);
+        assert_eq!(
+            char::strict_decode(&mut StrictReader::in_memory::<4>([0x00, 0xD8, 0x00, 0x00]))
+                .unwrap_err(),
+            DecodeError::InvalidCharCode(0xD800)
+        );
+        assert_eq!(
+            char::strict_decode(&mut StrictReader::in_memory::<4>([0x00, 0x00, 0x11, 0x00]))
+                .unwrap_err(),
+            DecodeError::InvalidCharCode(0x110000)
+        );
+    }
+
+    #[test]
+    fn ascii_strings() {
+        let s = AsciiPrintableString::<1, 16>::from_str("Hello, world!").unwrap();
+        assert_eq!(s.as_str(), "Hello, world!");
+        assert_eq!(
+            AsciiPrintableString::<1, 16>::from_str("line\nbreak").unwrap_err(),
+            InvalidRString::InvalidChar(s!("line\nbreak"), '\n', 4)
+        );
+
+        let s = AsciiGraphicString::<1, 16>::from_str("key=value;").unwrap();
+        assert_eq!(s.as_str(), "key=value;");
+        assert_eq!(
+            AsciiGraphicString::<1, 16>::from_str("key value").unwrap_err(),
+            InvalidRString::InvalidChar(s!("key value"), ' ', 3)
+        );
+        assert_eq!(
+            AsciiGraphicString::<1, 16>::from_str(" key").unwrap_err(),
+            InvalidRString::DisallowedFirst(s!(" key"), ' ')
+        );
+
+        assert_eq!(AsciiGraphic::ALL_VARIANTS.len(), 94);
+        assert_eq!(AsciiGraphic::ALL_VARIANTS[0], (b'!', "excl"));
+        assert_eq!(AsciiPrintable::try_from(0x0a), Err(VariantError::with::<AsciiPrintable>(0x0a)));
+    }
+
+    #[test]
+    fn charset_from_char() {
+        assert_eq!(Alpha::try_from('q').unwrap(), Alpha::q);
+        assert_eq!(Dec::try_from('7').unwrap(), Dec::Seven);
+        assert_eq!(AlphaNum::try_from('Z').map(u8::from), Ok(b'Z'));
+        assert_eq!(AsciiPrintable::try_from(' ').map(u8::from), Ok(b' '));
+
+        assert_eq!(Dec::try_from('a'), Err(VariantError::with::<Dec>('a')));
+        assert_eq!(AsciiGraphic::try_from(' '), Err(VariantError::with::<AsciiGraphic>(' ')));
+        assert_eq!(Alpha::try_from('Ю'), Err(VariantError::with::<Alpha>('Ю')));
+        // Chars in the Latin-1 range fit a byte but are not ASCII
+        assert_eq!(AsciiSym::try_from('é'), Err(VariantError::with::<AsciiSym>('é')));
     }
 }
@@ -40,7 +40,7 @@ pub fn decode<T: StrictDecode + Debug + Eq>(data: impl AsRef<[u8]>) -> T {
     let mut reader = StrictReader::in_memory::<MAX>(data);
     let val2 = T::strict_decode(&mut reader).unwrap();
     let mut cursor = reader.into_cursor();
-    assert!(!cursor.fill_buf().unwrap().is_empty(), "data not entirely consumed");
+    assert!(cursor.fill_buf().unwrap().is_empty(), "data not entirely consumed");
 
     val2
 }
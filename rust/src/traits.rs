@@ -19,11 +19,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::{BufRead, Seek};
+use std::collections::BTreeMap;
+use std::io::BufRead;
 use std::marker::PhantomData;
+use std::mem::{ManuallyDrop, MaybeUninit};
 use std::{fs, io};
 
-use amplify::confinement::{Collection, Confined};
+use amplify::confinement::{self, Collection, Confined, KeyedCollection};
 use amplify::num::u24;
 use amplify::Wrapper;
 
@@ -31,8 +33,9 @@ use super::{DecodeError, DecodeRawLe, VariantName};
 use crate::reader::StreamReader;
 use crate::writer::StreamWriter;
 use crate::{
-    DeserializeError, FieldName, Primitive, SerializeError, Sizing, StrictDumb, StrictEnum,
-    StrictReader, StrictStruct, StrictSum, StrictTuple, StrictType, StrictUnion, StrictWriter,
+    DeserializeError, FieldName, Primitive, RawBuffer, SerializeError, Sizing, StrictDumb,
+    StrictEnum, StrictReader, StrictStruct, StrictSum, StrictTuple, StrictType, StrictUnion,
+    StrictWriter,
 };
 
 pub trait TypedParent: Sized {}
@@ -42,24 +45,72 @@ pub trait WriteRaw {
     fn write_raw_array<const LEN: usize>(&mut self, raw: [u8; LEN]) -> io::Result<()> {
         self.write_raw::<LEN>(raw)
     }
+    /// Writes a number given by its little-endian byte representation. All
+    /// integers, floats and collection lengths are written with this method,
+    /// allowing raw writers to change the byte order of numbers (see
+    /// [`crate::BigEndian`]).
+    fn write_raw_num<const LEN: usize>(&mut self, le_bytes: [u8; LEN]) -> io::Result<()> {
+        self.write_raw_array(le_bytes)
+    }
+    /// Writes a collection length with the integer width defined by
+    /// `MAX_LEN`: `u8` for `MAX_LEN` up to `u8::MAX` (inclusive), `u16` up to
+    /// `u16::MAX`, `u24` up to `u24::MAX`, `u32` up to `u32::MAX` and `u64`
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the length exceeds
+    /// `MAX_LEN`, which otherwise would be truncated to the width.
     fn write_raw_len<const MAX_LEN: usize>(&mut self, len: usize) -> io::Result<()> {
+        if len > MAX_LEN {
+            let err = confinement::Error::Oversize {
+                len,
+                max_len: MAX_LEN,
+            };
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, err));
+        }
         match MAX_LEN {
-            tiny if tiny <= u8::MAX as usize => self.write_raw_array((len as u8).to_le_bytes()),
-            small if small <= u16::MAX as usize => self.write_raw_array((len as u16).to_le_bytes()),
+            tiny if tiny <= u8::MAX as usize => self.write_raw_num((len as u8).to_le_bytes()),
+            small if small <= u16::MAX as usize => self.write_raw_num((len as u16).to_le_bytes()),
             medium if medium <= u24::MAX.into_usize() => {
-                self.write_raw_array((u24::with(len as u32)).to_le_bytes())
+                self.write_raw_num((u24::with(len as u32)).to_le_bytes())
             }
-            large if large <= u32::MAX as usize => self.write_raw_array((len as u32).to_le_bytes()),
-            huge if huge <= u64::MAX as usize => self.write_raw_array((len as u64).to_le_bytes()),
+            large if large <= u32::MAX as usize => self.write_raw_num((len as u32).to_le_bytes()),
+            huge if huge <= u64::MAX as usize => self.write_raw_num((len as u64).to_le_bytes()),
             _ => unreachable!("confined collections larger than u64::MAX must not exist"),
         }
     }
+    /// Constructs a buffer for the data which are written ahead of their
+    /// position and copied into this writer later, following the byte order
+    /// and the remaining size limit of this writer. Adaptors changing the
+    /// encoding of numbers (like [`crate::BigEndian`]) must override it.
+    #[doc(hidden)]
+    fn raw_buffer(&self) -> RawBuffer { RawBuffer::new(usize::MAX) }
 }
 
 impl<T: WriteRaw> WriteRaw for &mut T {
     fn write_raw<const MAX_LEN: usize>(&mut self, bytes: impl AsRef<[u8]>) -> io::Result<()> {
         (*self).write_raw::<MAX_LEN>(bytes)
     }
+    fn write_raw_array<const LEN: usize>(&mut self, raw: [u8; LEN]) -> io::Result<()> {
+        (*self).write_raw_array(raw)
+    }
+    fn write_raw_num<const LEN: usize>(&mut self, le_bytes: [u8; LEN]) -> io::Result<()> {
+        (*self).write_raw_num(le_bytes)
+    }
+    fn raw_buffer(&self) -> RawBuffer { (**self).raw_buffer() }
+}
+
+/// Checks that the tag of an unknown union variant isn't used by any of the
+/// known variants of `T`, which would make the data decode as that variant.
+pub(crate) fn check_unknown_tag<T: StrictUnion>(tag: u8) -> io::Result<()> {
+    if T::variant_name_by_tag(tag).is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("tag {tag} of an unknown variant is used by a known variant"),
+        ));
+    }
+    Ok(())
 }
 
 #[allow(unused_variables)]
@@ -90,6 +141,54 @@ pub trait TypedWrite: Sized {
         self.write_tuple::<T>(|writer| Ok(writer.write_field(value)?.complete()))
     }
 
+    /// Writes union variant which is not known to `T`, given by its raw tag
+    /// followed by the raw variant data. This is the counterpart of
+    /// [`TypedRead::read_union_lenient`], used by the types deriving strict
+    /// encoding with `#[strict_type(unknown = ...)]` attribute.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the tag belongs to one of
+    /// the variants of `T`, since the data would be decoded as that variant.
+    fn write_union_unknown<T: StrictUnion>(mut self, tag: u8, data: &[u8]) -> io::Result<Self> {
+        check_unknown_tag::<T>(tag)?;
+        unsafe {
+            self.raw_writer().write_raw_array([tag])?;
+            self.raw_writer().write_raw::<{ usize::MAX }>(data)?;
+        }
+        Ok(self)
+    }
+
+    /// Writes TLV extension records, which follow all other fields of a
+    /// structure deriving strict encoding with a field marked as
+    /// `#[strict_type(extensions)]`. This is the counterpart of
+    /// [`TypedRead::read_extensions`].
+    ///
+    /// Each record is written as a 16-bit type, followed by a 16-bit length
+    /// of the value and the value bytes; both numbers are little-endian. The
+    /// records go in the ascending order of their types and are not prefixed
+    /// with their count, thus they must be the last data in the output.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if a value is longer than
+    /// `u16::MAX` bytes.
+    fn write_extensions<const MIN_LEN: usize, const MAX_LEN: usize>(
+        mut self,
+        extensions: &Confined<BTreeMap<u16, Vec<u8>>, MIN_LEN, MAX_LEN>,
+    ) -> io::Result<Self> {
+        for (ty, value) in extensions {
+            let len = u16::try_from(value.len()).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("value of extension record {ty} is longer than {} bytes", u16::MAX),
+                )
+            })?;
+            unsafe {
+                self.raw_writer().write_raw_array(ty.to_le_bytes())?;
+                self.raw_writer().write_raw_array(len.to_le_bytes())?;
+                self.raw_writer().write_raw::<{ u16::MAX as usize }>(value)?;
+            }
+        }
+        Ok(self)
+    }
+
     #[doc(hidden)]
     unsafe fn register_primitive(self, prim: Primitive) -> Self { self }
     #[doc(hidden)]
@@ -152,7 +251,60 @@ pub trait TypedWrite: Sized {
         Ok(self)
     }
 
-    // TODO: Do `write_keyed_collection`
+    /// Vectors, boxed and borrowed slices, which have the same encoding as
+    /// other collections.
+    #[doc(hidden)]
+    unsafe fn write_slice<T: StrictEncode, const MAX_LEN: usize>(
+        mut self,
+        items: &[T],
+    ) -> io::Result<Self> {
+        self.raw_writer().write_raw_len::<MAX_LEN>(items.len())?;
+        for item in items {
+            self = item.strict_encode(self)?;
+        }
+        Ok(self)
+    }
+
+    /// Byte vectors and slices, which have the same encoding as other
+    /// slices. Raw writers override it to write all the bytes at once.
+    #[doc(hidden)]
+    unsafe fn write_bytes<const MAX_LEN: usize>(self, bytes: &[u8]) -> io::Result<Self> {
+        self.write_slice::<u8, MAX_LEN>(bytes)
+    }
+
+    /// Byte arrays, written as a sequence of bytes. Raw writers override it to
+    /// write all the bytes at once.
+    #[doc(hidden)]
+    unsafe fn write_byte_array<const LEN: usize>(mut self, array: &[u8; LEN]) -> io::Result<Self> {
+        for byte in array {
+            self = byte.strict_encode(self)?;
+        }
+        Ok(self)
+    }
+
+    /// Maps and other keyed collections, written as a sequence of key-value
+    /// pairs.
+    #[doc(hidden)]
+    unsafe fn write_keyed_collection<
+        C: KeyedCollection,
+        const MIN_LEN: usize,
+        const MAX_LEN: usize,
+    >(
+        mut self,
+        map: &Confined<C, MIN_LEN, MAX_LEN>,
+    ) -> io::Result<Self>
+    where
+        for<'a> &'a C: IntoIterator<Item = (&'a C::Key, &'a C::Value)>,
+        C::Key: StrictEncode,
+        C::Value: StrictEncode,
+    {
+        self.raw_writer().write_raw_len::<MAX_LEN>(map.len())?;
+        for (k, v) in map {
+            self = k.strict_encode(self)?;
+            self = v.strict_encode(self)?;
+        }
+        Ok(self)
+    }
 }
 
 pub trait ReadRaw {
@@ -160,6 +312,42 @@ pub trait ReadRaw {
 
     fn read_raw_array<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]>;
 
+    /// Reads `len` bytes, appending them to `buf`. Unlike [`Self::read_raw`],
+    /// which allocates a new vector on each call, this allows to reuse the
+    /// capacity of a buffer, which is cleared by the caller between the reads.
+    ///
+    /// Byte collections are decoded with this method straight into the
+    /// resulting vector, avoiding intermediate allocations. Since the decoded
+    /// values own their data, buffers are not reused across decoded records.
+    ///
+    /// If the read fails, the content of `buf` is unspecified.
+    fn read_raw_into(&mut self, buf: &mut Vec<u8>, len: usize) -> io::Result<()> {
+        buf.extend(self.read_raw::<{ usize::MAX }>(len)?);
+        Ok(())
+    }
+
+    /// Reads a number, returning its little-endian byte representation. All
+    /// integers, floats and collection lengths are read with this method,
+    /// allowing raw readers to change the byte order of numbers (see
+    /// [`crate::BigEndian`]).
+    fn read_raw_num<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]> {
+        self.read_raw_array::<LEN>()
+    }
+
+    /// Reads all the remaining data.
+    fn read_raw_to_end(&mut self) -> io::Result<Vec<u8>> {
+        let mut buf = vec![];
+        loop {
+            match self.read_raw_array::<1>() {
+                Ok([byte]) => buf.push(byte),
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(buf),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Reads a collection length written by [`WriteRaw::write_raw_len`] with
+    /// the same `MAX_LEN`. The length is not checked against `MAX_LEN`.
     fn read_raw_len<const MAX_LEN: usize>(&mut self) -> Result<usize, DecodeError> {
         Ok(match MAX_LEN {
             tiny if tiny <= u8::MAX as usize => u8::decode_raw_le(self)? as usize,
@@ -170,6 +358,17 @@ pub trait ReadRaw {
             _ => unreachable!("confined collections larger than u64::MAX must not exist"),
         })
     }
+
+    /// Returns the number of bytes read so far, if the reader counts them.
+    /// This allows to tell the data ending before a value from the data
+    /// ending in its middle.
+    ///
+    /// Readers returning `None` fall back to taking any end of data for
+    /// absent fields of extensible structures (see
+    /// [`ReadStruct::read_field_or_else`]), thus the readers supporting such
+    /// structures should implement it.
+    #[doc(hidden)]
+    fn raw_count(&self) -> Option<usize> { None }
 }
 
 impl<T: ReadRaw> ReadRaw for &mut T {
@@ -180,6 +379,18 @@ impl<T: ReadRaw> ReadRaw for &mut T {
     fn read_raw_array<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]> {
         (*self).read_raw_array::<LEN>()
     }
+
+    fn read_raw_into(&mut self, buf: &mut Vec<u8>, len: usize) -> io::Result<()> {
+        (*self).read_raw_into(buf, len)
+    }
+
+    fn read_raw_num<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]> {
+        (*self).read_raw_num::<LEN>()
+    }
+
+    fn read_raw_to_end(&mut self) -> io::Result<Vec<u8>> { (*self).read_raw_to_end() }
+
+    fn raw_count(&self) -> Option<usize> { (**self).raw_count() }
 }
 
 pub trait TypedRead {
@@ -192,12 +403,32 @@ pub trait TypedRead {
 
     #[doc(hidden)]
     unsafe fn raw_reader(&mut self) -> &mut Self::RawReader;
+    #[doc(hidden)]
+    unsafe fn register_primitive(&mut self, _prim: Primitive) {}
 
     fn read_union<T: StrictUnion>(
         &mut self,
         inner: impl FnOnce(VariantName, &mut Self::UnionReader) -> Result<T, DecodeError>,
     ) -> Result<T, DecodeError>;
 
+    /// Reads union like [`Self::read_union`] does, but instead of failing with
+    /// [`DecodeError::UnionTagNotKnown`] passes a tag which is not known to `T`
+    /// to the `unknown` closure, which may read the rest of the variant data
+    /// from the raw reader.
+    ///
+    /// Since the data of unknown variants can't be delimited, they are
+    /// decodable only at the end of the input. Readers which do not operate
+    /// on tags and have no other representation of unknown variants default
+    /// to [`Self::read_union`].
+    fn read_union_lenient<T: StrictUnion>(
+        &mut self,
+        inner: impl FnOnce(VariantName, &mut Self::UnionReader) -> Result<T, DecodeError>,
+        unknown: impl FnOnce(u8, &mut Self::RawReader) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        let _ = unknown;
+        self.read_union(inner)
+    }
+
     fn read_enum<T: StrictEnum>(&mut self) -> Result<T, DecodeError>
     where u8: From<T>;
 
@@ -222,11 +453,129 @@ pub trait TypedRead {
         self.read_tuple(|reader| reader.read_field().map(T::from_inner))
     }
 
+    /// Reads a sequence of items prefixed with its length, encoded in the same
+    /// way as `Confined<Vec<T>, 0, MAX_LEN>`, allowing to decode it into an
+    /// arbitrary collection. The `item` closure is called with the reader and
+    /// the index of the item for each of the items, and must read the item
+    /// from the reader. Returns the number of the items read.
+    ///
+    /// Fails with [`DecodeError::Confinement`] before reading any item if the
+    /// length exceeds `MAX_LEN`.
+    ///
+    /// ```
+    /// # use std::collections::LinkedList;
+    /// # use amplify::confinement::TinyVec;
+    /// # use strict_encoding::{StrictDecode, StrictEncode, StrictReader, StrictWriter, TypedRead};
+    /// let items = TinyVec::try_from(vec![1u16, 2, 3]).unwrap();
+    /// let data = items.strict_encode(StrictWriter::in_memory::<16>()).unwrap();
+    /// let data = data.unbox().unconfine();
+    ///
+    /// let mut list = LinkedList::new();
+    /// let mut reader = StrictReader::in_memory::<16>(data);
+    /// let count = reader
+    ///     .read_collection::<{ u8::MAX as usize }>(|r, _| {
+    ///         list.push_front(u16::strict_decode(r)?);
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(count, 3);
+    /// assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    fn read_collection<const MAX_LEN: usize>(
+        &mut self,
+        mut item: impl FnMut(&mut Self, usize) -> Result<(), DecodeError>,
+    ) -> Result<usize, DecodeError> {
+        let len = unsafe { self.read_collection_len::<MAX_LEN>()? };
+        if len > MAX_LEN {
+            return Err(confinement::Error::Oversize {
+                len,
+                max_len: MAX_LEN,
+            }
+            .into());
+        }
+        for index in 0..len {
+            item(self, index)?;
+        }
+        Ok(len)
+    }
+
+    /// Reads TLV extension records written by
+    /// [`TypedWrite::write_extensions`] until the end of the input.
+    ///
+    /// The input may end only at a record boundary; a truncated record fails
+    /// the decoding. Records must go in the strictly ascending order of their
+    /// types.
+    fn read_extensions<const MIN_LEN: usize, const MAX_LEN: usize>(
+        &mut self,
+    ) -> Result<Confined<BTreeMap<u16, Vec<u8>>, MIN_LEN, MAX_LEN>, DecodeError> {
+        let mut extensions = BTreeMap::new();
+        loop {
+            let reader = unsafe { self.raw_reader() };
+            let [lo] = match reader.read_raw_array::<1>() {
+                Ok(byte) => byte,
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(err) => return Err(err.into()),
+            };
+            let [hi] = reader.read_raw_array::<1>()?;
+            let ty = u16::from_le_bytes([lo, hi]);
+            let len = u16::from_le_bytes(reader.read_raw_array::<2>()?);
+            let value = reader.read_raw::<{ u16::MAX as usize }>(len as usize)?;
+            if matches!(extensions.last_key_value(), Some((last, _)) if *last > ty) {
+                return Err(DecodeError::BrokenMapOrder);
+            }
+            if extensions.insert(ty, value).is_some() {
+                return Err(DecodeError::RepeatedMapValue);
+            }
+        }
+        Confined::try_from(extensions).map_err(DecodeError::from)
+    }
+
     #[doc(hidden)]
     unsafe fn read_string<const MAX_LEN: usize>(&mut self) -> Result<Vec<u8>, DecodeError> {
         let len = self.raw_reader().read_raw_len::<MAX_LEN>()?;
         self.raw_reader().read_raw::<MAX_LEN>(len).map_err(DecodeError::from)
     }
+
+    /// Vec and sets - excluding strings, read by [`Self::read_string`].
+    /// Returns the number of items, which are read next by the caller.
+    #[doc(hidden)]
+    unsafe fn read_collection_len<const MAX_LEN: usize>(&mut self) -> Result<usize, DecodeError> {
+        self.raw_reader().read_raw_len::<MAX_LEN>()
+    }
+
+    /// Byte vectors, which have the same encoding as other collections. Raw
+    /// readers override it to read all the bytes at once.
+    #[doc(hidden)]
+    unsafe fn read_bytes<const MAX_LEN: usize>(&mut self) -> Result<Vec<u8>, DecodeError>
+    where Self: Sized {
+        let len = self.read_collection_len::<MAX_LEN>()?;
+        let mut bytes = Vec::with_capacity(len);
+        for _ in 0..len {
+            bytes.push(u8::strict_decode(self)?);
+        }
+        Ok(bytes)
+    }
+
+    /// Byte arrays, read as a sequence of bytes. Raw readers override it to
+    /// read all the bytes at once.
+    #[doc(hidden)]
+    unsafe fn read_byte_array<const LEN: usize>(&mut self) -> Result<[u8; LEN], DecodeError>
+    where Self: Sized {
+        let mut array = [0u8; LEN];
+        for byte in array.iter_mut() {
+            *byte = u8::strict_decode(self)?;
+        }
+        Ok(array)
+    }
+
+    /// Maps and other keyed collections. Returns the number of key-value
+    /// pairs, which are read next by the caller.
+    #[doc(hidden)]
+    unsafe fn read_keyed_collection_len<const MAX_LEN: usize>(
+        &mut self,
+    ) -> Result<usize, DecodeError> {
+        self.raw_reader().read_raw_len::<MAX_LEN>()
+    }
 }
 
 pub trait DefineTuple: Sized {
@@ -248,17 +597,90 @@ pub trait ReadTuple {
 pub trait DefineStruct: Sized {
     type Parent: TypedParent;
     fn define_field<T: StrictEncode + StrictDumb>(self, name: FieldName) -> Self;
+    /// Defines field at an explicit position (ordinal) within the structure.
+    /// Ordinals of all fields must cover positions from zero to the number of
+    /// fields exactly once.
+    ///
+    /// Definers which don't depend on the field order may ignore the ordinal.
+    fn define_field_ord<T: StrictEncode + StrictDumb>(self, name: FieldName, ord: u8) -> Self {
+        let _ = ord;
+        self.define_field::<T>(name)
+    }
     fn complete(self) -> Self::Parent;
 }
 
 pub trait WriteStruct: Sized {
     type Parent: TypedParent;
     fn write_field(self, name: FieldName, value: &impl StrictEncode) -> io::Result<Self>;
+    /// Writes field at an explicit position (ordinal) within the structure,
+    /// allowing to write fields in an order different from their declaration.
+    /// Ordinals of all written fields must cover positions from zero to the
+    /// number of fields exactly once.
+    ///
+    /// Writers which don't depend on the field order may ignore the ordinal.
+    fn write_field_ord(
+        self,
+        name: FieldName,
+        ord: u8,
+        value: &impl StrictEncode,
+    ) -> io::Result<Self> {
+        let _ = ord;
+        self.write_field(name, value)
+    }
+    /// Writes field only if it is `present`, otherwise the field is not a part
+    /// of the data. Used for the fields which presence depends on the values
+    /// of the preceding fields, like flags.
+    fn write_field_if(
+        self,
+        name: FieldName,
+        present: bool,
+        value: &impl StrictEncode,
+    ) -> io::Result<Self> {
+        if present {
+            self.write_field(name, value)
+        } else {
+            Ok(self)
+        }
+    }
     fn complete(self) -> Self::Parent;
 }
 
 pub trait ReadStruct {
     fn read_field<T: StrictDecode>(&mut self, field: FieldName) -> Result<T, DecodeError>;
+
+    /// Reads a field which may be absent at the end of the data, created by
+    /// an older version of the structure, returning the value constructed by
+    /// `default` in such case.
+    ///
+    /// The field is treated as absent only when the data end right before it,
+    /// while a field truncated in the middle fails with the end-of-data error
+    /// (see [`DecodeError::is_eof`]). Readers which do not count the data
+    /// read can't tell these cases apart and treat both as an absent field.
+    fn read_field_or_else<T: StrictDecode>(
+        &mut self,
+        field: FieldName,
+        default: impl FnOnce() -> T,
+    ) -> Result<T, DecodeError> {
+        match self.read_field(field) {
+            Err(err) if err.is_eof() => Ok(default()),
+            res => res,
+        }
+    }
+
+    /// Reads field only if it is `present`, otherwise returns the default
+    /// value of the field without reading any data. This is the counterpart of
+    /// [`WriteStruct::write_field_if`].
+    fn read_field_if<T: StrictDecode + Default>(
+        &mut self,
+        field: FieldName,
+        present: bool,
+    ) -> Result<T, DecodeError> {
+        if present {
+            self.read_field(field)
+        } else {
+            Ok(T::default())
+        }
+    }
 }
 
 pub trait DefineEnum: Sized {
@@ -350,19 +772,139 @@ pub trait ReadUnion: Sized {
 
 pub trait StrictEncode: StrictType {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W>;
+
+    /// Writes a slice of the values with [`TypedWrite::write_slice`].
+    /// Overridden by `u8` to write byte slices with
+    /// [`TypedWrite::write_bytes`].
+    #[doc(hidden)]
+    fn strict_encode_slice<W: TypedWrite, const MAX_LEN: usize>(
+        items: &[Self],
+        writer: W,
+    ) -> io::Result<W> {
+        unsafe { writer.write_slice::<Self, MAX_LEN>(items) }
+    }
+
+    /// Writes an array of the values item by item. Overridden by `u8` to write
+    /// byte arrays with [`TypedWrite::write_byte_array`].
+    #[doc(hidden)]
+    fn strict_encode_array<W: TypedWrite, const LEN: usize>(
+        array: &[Self; LEN],
+        mut writer: W,
+    ) -> io::Result<W> {
+        for item in array {
+            writer = item.strict_encode(writer)?;
+        }
+        Ok(writer)
+    }
     fn strict_write(&self, writer: impl WriteRaw) -> io::Result<()> {
         let w = StrictWriter::with(writer);
         self.strict_encode(w)?;
         Ok(())
     }
+
+    /// Writes the strict encoding of the value prefixed with its length in
+    /// bytes, such that multiple values can be concatenated in a stream and
+    /// read back one by one with [`StrictDecode::strict_read_framed`]. The
+    /// length prefix uses the same encoding as collection lengths for `MAX`.
+    fn strict_write_framed<const MAX: usize>(&self, mut writer: impl WriteRaw) -> io::Result<()> {
+        let data = self.strict_encode(StrictWriter::in_memory::<MAX>())?.unbox().unconfine();
+        writer.write_raw_len::<MAX>(data.len())?;
+        writer.write_raw::<MAX>(data)
+    }
+}
+
+/// Array which is being filled item by item. Drops the initialized items if
+/// the filling is not completed because of an error or a panic.
+struct ArrayGuard<T, const LEN: usize> {
+    items: [MaybeUninit<T>; LEN],
+    init: usize,
+}
+
+impl<T, const LEN: usize> ArrayGuard<T, LEN> {
+    fn new() -> Self {
+        // SAFETY: an array of `MaybeUninit`s doesn't require initialization.
+        let items = unsafe { MaybeUninit::<[MaybeUninit<T>; LEN]>::uninit().assume_init() };
+        ArrayGuard { items, init: 0 }
+    }
+
+    fn push(&mut self, item: T) {
+        self.items[self.init].write(item);
+        self.init += 1;
+    }
+
+    fn into_inner(self) -> [T; LEN] {
+        debug_assert_eq!(self.init, LEN, "array is not completely initialized");
+        let this = ManuallyDrop::new(self);
+        // SAFETY: all the items are initialized, and `MaybeUninit<T>` has the same layout as
+        // `T`. The guard is not dropped, so the items are moved out only once.
+        unsafe { (&this.items as *const [MaybeUninit<T>; LEN] as *const [T; LEN]).read() }
+    }
+}
+
+impl<T, const LEN: usize> Drop for ArrayGuard<T, LEN> {
+    fn drop(&mut self) {
+        for item in &mut self.items[..self.init] {
+            // SAFETY: the first `init` items are initialized.
+            unsafe { item.assume_init_drop() }
+        }
+    }
 }
 
 pub trait StrictDecode: StrictType {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError>;
+
+    /// Reads a vector of the values as a collection. Overridden by `u8` to
+    /// read byte vectors with [`TypedRead::read_bytes`].
+    #[doc(hidden)]
+    fn strict_decode_vec<const MIN_LEN: usize, const MAX_LEN: usize>(
+        reader: &mut impl TypedRead,
+    ) -> Result<Confined<Vec<Self>, MIN_LEN, MAX_LEN>, DecodeError> {
+        let len = unsafe { reader.read_collection_len::<MAX_LEN>()? };
+        let mut col = Vec::<Self>::with_capacity(len);
+        for _ in 0..len {
+            col.push(StrictDecode::strict_decode(reader)?);
+        }
+        Confined::try_from(col).map_err(DecodeError::from)
+    }
+
+    /// Reads an array of the values item by item. Overridden by `u8` to read
+    /// byte arrays with [`TypedRead::read_byte_array`].
+    #[doc(hidden)]
+    fn strict_decode_array<const LEN: usize>(
+        reader: &mut impl TypedRead,
+    ) -> Result<[Self; LEN], DecodeError> {
+        let mut array = ArrayGuard::<Self, LEN>::new();
+        while array.init < LEN {
+            let item = Self::strict_decode(reader)?;
+            array.push(item);
+        }
+        Ok(array.into_inner())
+    }
     fn strict_read(reader: impl ReadRaw) -> Result<Self, DecodeError> {
         let mut r = StrictReader::with(reader);
         Self::strict_decode(&mut r)
     }
+
+    /// Reads a value written with [`StrictEncode::strict_write_framed`]. Reads
+    /// the length prefix and then exactly that number of bytes, so the data
+    /// following the frame are not consumed. Errors if the frame contains
+    /// data beyond the decoded value.
+    fn strict_read_framed<const MAX: usize>(mut reader: impl ReadRaw) -> Result<Self, DecodeError> {
+        let len = reader.read_raw_len::<MAX>()?;
+        if len > MAX {
+            return Err(confinement::Error::Oversize { len, max_len: MAX }.into());
+        }
+        let data = reader.read_raw::<MAX>(len)?;
+        let mut r = StrictReader::in_memory::<MAX>(data);
+        let me = Self::strict_decode(&mut r)?;
+        let rest = r.remaining();
+        if rest > 0 {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "frame contains {rest} bytes remaining after the decoded value"
+            )));
+        }
+        Ok(me)
+    }
 }
 
 impl<T: StrictEncode> StrictEncode for &T {
@@ -385,6 +927,15 @@ pub trait StrictSerialize: StrictEncode {
         Ok(self.strict_encode(counter)?.unbox().unconfine().count)
     }
 
+    /// Computes the length of the serialized data together with its CRC-32
+    /// checksum (see [`crate::Crc32Writer`]) in a single encoding pass,
+    /// without retaining the data.
+    fn strict_len_and_checksum<const MAX: usize>(&self) -> io::Result<(usize, u32)> {
+        let writer = StrictWriter::checksum_counter::<MAX>();
+        let crc32 = self.strict_encode(writer)?.unbox().unconfine();
+        Ok((crc32.as_inner().count, crc32.checksum()))
+    }
+
     fn to_strict_serialized<const MAX: usize>(
         &self,
     ) -> Result<Confined<Vec<u8>, 0, MAX>, SerializeError> {
@@ -393,6 +944,13 @@ pub trait StrictSerialize: StrictEncode {
         Confined::<Vec<u8>, 0, MAX>::try_from(data).map_err(SerializeError::from)
     }
 
+    /// Serializes the value into a lower-case hex string.
+    #[cfg(feature = "hex")]
+    fn to_strict_hex<const MAX: usize>(&self) -> Result<String, SerializeError> {
+        use amplify::hex::ToHex;
+        self.to_strict_serialized::<MAX>().map(|data| data.to_hex())
+    }
+
     fn strict_serialize_to_file<const MAX: usize>(
         &self,
         path: impl AsRef<std::path::Path>,
@@ -411,24 +969,281 @@ pub trait StrictDeserialize: StrictDecode {
     ) -> Result<Self, DeserializeError> {
         let mut reader = StrictReader::in_memory::<MAX>(ast_data);
         let me = Self::strict_decode(&mut reader)?;
-        let mut cursor = reader.into_cursor();
-        if !cursor.fill_buf()?.is_empty() {
+        if reader.remaining() > 0 {
             return Err(DeserializeError::DataNotEntirelyConsumed);
         }
         Ok(me)
     }
 
+    /// Deserializes the value from the given bytes, which must be entirely
+    /// consumed by the decoding. Unlike [`Self::from_strict_serialized`], the
+    /// data are not required to be confined to some maximal length in advance.
+    ///
+    /// ```
+    /// # use strict_encoding::{DeserializeError, StrictDeserialize};
+    /// # use strict_encoding::{StrictDecode, StrictEncode, StrictType};
+    /// #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    /// #[derive(StrictType, StrictEncode, StrictDecode)]
+    /// #[strict_type(lib = "Example")]
+    /// struct Id(u16);
+    ///
+    /// impl StrictDeserialize for Id {}
+    ///
+    /// assert_eq!(Id::strict_deserialize([0xCD, 0xAB]).unwrap(), Id(0xABCD));
+    /// assert_eq!(Id::strict_deserialize(&[0xCD, 0xAB][..]).unwrap(), Id(0xABCD));
+    /// assert_eq!(
+    ///     Id::strict_deserialize(vec![0xCD, 0xAB, 0]).unwrap_err(),
+    ///     DeserializeError::DataNotEntirelyConsumed
+    /// );
+    /// ```
+    fn strict_deserialize(bytes: impl Into<Vec<u8>>) -> Result<Self, DeserializeError> {
+        let mut reader = StrictReader::in_memory::<{ usize::MAX }>(bytes.into());
+        let me = Self::strict_decode(&mut reader)?;
+        if reader.remaining() > 0 {
+            return Err(DeserializeError::DataNotEntirelyConsumed);
+        }
+        Ok(me)
+    }
+
+    /// Deserializes the value from a hex string, which may use both lower- and
+    /// upper-case characters. Fails if the string has odd length, contains
+    /// non-hex characters or encodes more than `MAX` bytes.
+    #[cfg(feature = "hex")]
+    fn from_strict_hex<const MAX: usize>(s: &str) -> Result<Self, DeserializeError> {
+        use amplify::hex::FromHex;
+        let data = Vec::<u8>::from_hex(s)?;
+        let data = Confined::try_from(data).map_err(DecodeError::from)?;
+        Self::from_strict_serialized::<MAX>(data)
+    }
+
     fn strict_deserialize_from_file<const MAX: usize>(
         path: impl AsRef<std::path::Path>,
     ) -> Result<Self, DeserializeError> {
         let file = fs::File::open(path)?;
-        // TODO: Do FileReader
-        let mut reader = StrictReader::with(StreamReader::new::<MAX>(file));
+        let mut reader = StrictReader::with(StreamReader::buffered::<MAX>(file));
         let me = Self::strict_decode(&mut reader)?;
-        let mut file = reader.unbox().unconfine();
-        if file.stream_position()? != file.seek(io::SeekFrom::End(0))? {
+        // Part of the remaining data may be already read into the buffer
+        if !reader.unbox().unconfine().fill_buf()?.is_empty() {
             return Err(DeserializeError::DataNotEntirelyConsumed);
         }
         Ok(me)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn len_boundary<const MAX_LEN: usize>(len: usize, width: usize) {
+        let mut writer = StreamWriter::in_memory::<8>();
+        writer.write_raw_len::<MAX_LEN>(len).unwrap();
+        let data = writer.unconfine();
+        assert_eq!(data.len(), width, "length prefix width for MAX_LEN {MAX_LEN}");
+        assert_eq!(data, &(len as u64).to_le_bytes()[..width]);
+
+        let mut reader = StreamReader::in_memory::<8>(data);
+        assert_eq!(reader.read_raw_len::<MAX_LEN>().unwrap(), len);
+        assert_eq!(reader.remaining(), 0);
+
+        if let Some(len) = MAX_LEN.checked_add(1) {
+            let err = StreamWriter::in_memory::<8>().write_raw_len::<MAX_LEN>(len).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn len_width_boundaries() {
+        const U24: usize = 0xFF_FFFF;
+        const U32: usize = u32::MAX as usize;
+
+        len_boundary::<0xFF>(0xFF, 1);
+        len_boundary::<0xFF>(0, 1);
+        len_boundary::<0x100>(0x100, 2);
+        len_boundary::<0x100>(0xFF, 2);
+        len_boundary::<0xFFFF>(0xFFFF, 2);
+        len_boundary::<0x1_0000>(0x1_0000, 3);
+        len_boundary::<U24>(U24, 3);
+        len_boundary::<{ U24 + 1 }>(U24 + 1, 4);
+        len_boundary::<U32>(U32, 4);
+        len_boundary::<{ U32 + 1 }>(U32 + 1, 8);
+        len_boundary::<{ usize::MAX }>(usize::MAX, 8);
+    }
+
+    #[test]
+    fn collection_len_boundaries() {
+        fn check<const MAX_LEN: usize>(width: usize) {
+            let col = Confined::<Vec<u8>, 0, MAX_LEN>::try_from(vec![0xA5; MAX_LEN]).unwrap();
+            let data = col.strict_encode(StrictWriter::in_memory::<{ 1 << 20 }>()).unwrap();
+            let data = data.unbox().unconfine();
+            assert_eq!(data[..width], (MAX_LEN as u64).to_le_bytes()[..width]);
+            assert_eq!(data.len(), width + MAX_LEN);
+
+            let mut reader = StrictReader::in_memory::<{ 1 << 20 }>(data);
+            assert_eq!(Confined::<Vec<u8>, 0, MAX_LEN>::strict_decode(&mut reader).unwrap(), col);
+            assert_eq!(reader.remaining(), 0);
+        }
+
+        check::<0xFF>(1);
+        check::<0x100>(2);
+        check::<0xFFFF>(2);
+        check::<0x1_0000>(3);
+    }
+
+    #[test]
+    fn framed_roundtrip() {
+        let mut writer = StreamWriter::in_memory::<256>();
+        0xABu8.strict_write_framed::<256>(&mut writer).unwrap();
+        Some(0x1234u16).strict_write_framed::<256>(&mut writer).unwrap();
+        (7u8, -1i32).strict_write_framed::<256>(&mut writer).unwrap();
+        let data = writer.unconfine();
+        assert_eq!(data, [
+            0x01, 0x00, 0xAB, // first frame
+            0x03, 0x00, 0x01, 0x34, 0x12, // second frame
+            0x05, 0x00, 0x07, 0xFF, 0xFF, 0xFF, 0xFF, // third frame
+        ]);
+
+        let mut reader = StreamReader::in_memory::<256>(data);
+        assert_eq!(u8::strict_read_framed::<256>(&mut reader).unwrap(), 0xAB);
+        assert_eq!(Option::<u16>::strict_read_framed::<256>(&mut reader).unwrap(), Some(0x1234));
+        assert_eq!(<(u8, i32)>::strict_read_framed::<256>(&mut reader).unwrap(), (7, -1));
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn framed_trailing_data() {
+        let mut reader = StreamReader::in_memory::<8>([0x02, 0xAB, 0xCD, 0xEF]);
+        assert!(matches!(
+            u8::strict_read_framed::<8>(&mut reader),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(crate::StrictType, crate::StrictEncode, crate::StrictDecode)]
+    #[strict_type(lib = "Test", crate = crate)]
+    struct Samples(Confined<Vec<u16>, 0, { u16::MAX as usize }>);
+
+    impl StrictSerialize for Samples {}
+    impl StrictDeserialize for Samples {}
+
+    #[test]
+    fn file_roundtrip() {
+        /// Removes the file once the test ends, including on a failed assertion.
+        struct TempFile(std::path::PathBuf);
+        impl Drop for TempFile {
+            fn drop(&mut self) { let _ = fs::remove_file(&self.0); }
+        }
+
+        let file = TempFile(
+            std::env::temp_dir()
+                .join(format!("strict_encoding_file_roundtrip_{}.bin", std::process::id())),
+        );
+        let path = &file.0;
+        // The values take less than the read buffer, one and a half of it and
+        // exactly two buffers of 8 KiB, so the trailing data are either read
+        // ahead into the buffer or remain in the file
+        for len in [3, 0x17FF, 0x1FFF] {
+            let val = Samples(Confined::try_from((0..len).collect::<Vec<_>>()).unwrap());
+            val.strict_serialize_to_file::<0x8000>(path).unwrap();
+            assert_eq!(Samples::strict_deserialize_from_file::<0x8000>(path).unwrap(), val);
+
+            let mut data = fs::read(path).unwrap();
+            data.push(0);
+            fs::write(path, data).unwrap();
+            assert_eq!(
+                Samples::strict_deserialize_from_file::<0x8000>(path).unwrap_err(),
+                DeserializeError::DataNotEntirelyConsumed
+            );
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+    #[derive(crate::StrictType, crate::StrictEncode, crate::StrictDecode)]
+    #[strict_type(lib = "Test", crate = crate)]
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    #[derive(PartialEq, Eq, Debug, Default)]
+    struct Polygon {
+        points: Vec<Point>,
+    }
+
+    #[test]
+    fn read_collection() {
+        let points = vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+        let col = Confined::<Vec<Point>, 0, 8>::try_from(points.clone()).unwrap();
+        let data = col.strict_encode(StrictWriter::in_memory::<16>()).unwrap().unbox().unconfine();
+        assert_eq!(data, [2, 1, 2, 3, 4]);
+
+        let mut polygon = Polygon::default();
+        let mut indexes = vec![];
+        let mut reader = StrictReader::in_memory::<16>(data.as_slice());
+        let count = reader
+            .read_collection::<8>(|r, index| {
+                indexes.push(index);
+                polygon.points.push(Point::strict_decode(r)?);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(indexes, [0, 1]);
+        assert_eq!(polygon, Polygon { points });
+        assert_eq!(reader.remaining(), 0);
+
+        // Length is checked before reading the items
+        let mut reader = StrictReader::in_memory::<16>(data.as_slice());
+        let err = reader.read_collection::<1>(|_, _| unreachable!()).unwrap_err();
+        assert_eq!(err, confinement::Error::Oversize { len: 2, max_len: 1 }.into());
+
+        // Errors of the items are returned as they are
+        let mut reader = StrictReader::in_memory::<16>([2u8, 1, 2, 3]);
+        let mut polygon = Polygon::default();
+        assert!(reader
+            .read_collection::<8>(|r, _| {
+                polygon.points.push(Point::strict_decode(r)?);
+                Ok(())
+            })
+            .is_err());
+        assert_eq!(polygon.points.len(), 1);
+    }
+
+    #[cfg(feature = "hex")]
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+    #[derive(crate::StrictType, crate::StrictEncode, crate::StrictDecode)]
+    #[strict_type(lib = "Test", crate = crate)]
+    struct Pair(u8, u16);
+
+    #[cfg(feature = "hex")]
+    impl StrictSerialize for Pair {}
+    #[cfg(feature = "hex")]
+    impl StrictDeserialize for Pair {}
+
+    #[test]
+    #[cfg(feature = "hex")]
+    fn hex_roundtrip() {
+        let val = Pair(0xAB, 0x1234);
+        assert_eq!(val.to_strict_hex::<3>().unwrap(), "ab3412");
+        assert_eq!(Pair::from_strict_hex::<3>("ab3412").unwrap(), val);
+        assert_eq!(Pair::from_strict_hex::<3>("AB3412").unwrap(), val);
+        assert!(matches!(val.to_strict_hex::<2>(), Err(SerializeError::Io(_))));
+
+        assert_eq!(
+            Pair::from_strict_hex::<3>("ab341").unwrap_err(),
+            DeserializeError::InvalidHex(amplify::hex::Error::OddLengthString(5))
+        );
+        assert_eq!(
+            Pair::from_strict_hex::<3>("ab34zz").unwrap_err(),
+            DeserializeError::InvalidHex(amplify::hex::Error::InvalidChar(b'z'))
+        );
+        assert_eq!(
+            Pair::from_strict_hex::<3>("ab341200").unwrap_err(),
+            DeserializeError::Decode(confinement::Error::Oversize { len: 4, max_len: 3 }.into())
+        );
+        assert!(matches!(
+            Pair::from_strict_hex::<3>("ab34").unwrap_err(),
+            DeserializeError::Decode(DecodeError::UnexpectedEof)
+        ));
+    }
+}
@@ -21,7 +21,7 @@
 
 use std::any;
 use std::collections::BTreeSet;
-use std::fmt::{Debug, Display};
+use std::fmt::{self, Debug, Display, Formatter};
 use std::marker::PhantomData;
 
 use crate::{LibName, TypeName, VariantName};
@@ -35,7 +35,8 @@ pub fn type_name<T>() -> String {
     let mut ident = vec![];
     for mut arg in name.split([',', '<', '>', '(', ')']) {
         arg = arg.trim();
-        if arg.is_empty() {
+        // Lifetimes are not a part of the type name
+        if arg.is_empty() || arg.starts_with('\'') {
             continue;
         }
         ident.push(get_ident(arg));
@@ -43,14 +44,40 @@ pub fn type_name<T>() -> String {
     ident.join("")
 }
 
-#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
-#[display("unexpected variant {1} for enum or union {0:?}")]
-pub struct VariantError<V: Debug + Display>(pub Option<String>, pub V);
+/// Error returned when a value doesn't match any of the enum or union
+/// variants, carrying the name of the type, if known, and the value itself.
+#[derive(Clone, Eq, PartialEq, Debug, Error)]
+pub struct VariantError<V: Debug + Display>(pub Option<TypeName>, pub V);
 
 impl<V: Debug + Display> VariantError<V> {
-    pub fn with<T>(val: V) -> Self { VariantError(Some(type_name::<T>()), val) }
-    pub fn typed(name: impl Into<String>, val: V) -> Self { VariantError(Some(name.into()), val) }
+    /// Constructs error for the Rust type `T`, whose name is used as the type
+    /// name if it is a valid [`TypeName`].
+    pub fn with<T>(val: V) -> Self { VariantError(TypeName::try_from(type_name::<T>()).ok(), val) }
+    pub fn typed(name: impl Into<TypeName>, val: V) -> Self { VariantError(Some(name.into()), val) }
     pub fn untyped(val: V) -> Self { VariantError(None, val) }
+
+    /// Returns the value which doesn't match any of the variants.
+    pub fn value(&self) -> V
+    where V: Copy {
+        self.1
+    }
+
+    /// Returns the name of the type, if it is known.
+    ///
+    /// The name is the one of the Rust type for the errors constructed with
+    /// [`VariantError::with`], and thus may differ from the strict type name;
+    /// Rust type names which are not valid type names (like `u8`) are absent.
+    pub fn type_name(&self) -> Option<&TypeName> { self.0.as_ref() }
+}
+
+impl<V: Debug + Display> Display for VariantError<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "value {} is not a valid variant", self.1)?;
+        match &self.0 {
+            Some(name) => write!(f, " of {name}"),
+            None => Ok(()),
+        }
+    }
 }
 
 pub trait StrictDumb: Sized {
@@ -65,11 +92,51 @@ where T: StrictType + Default
 
 pub trait StrictType: Sized {
     const STRICT_LIB_NAME: &'static str;
+    /// Whether the encoding of the type ends with TLV extension records (see
+    /// [`crate::TypedWrite::write_extensions`]), which can't be followed by any other
+    /// data. Set by the derivation macros.
+    const HAS_EXTENSIONS: bool = false;
     fn strict_name() -> Option<TypeName> { Some(tn!(type_name::<Self>())) }
 }
 
+/// Value of [`StrictType::HAS_EXTENSIONS`] for the types containing values of
+/// `T` followed by other data, like collections or tuples. Fails the constant
+/// evaluation if `T` ends with TLV extensions, since they must be the last
+/// data of the encoding.
+#[doc(hidden)]
+pub const fn non_terminal<T: StrictType>() -> bool {
+    assert!(
+        !T::HAS_EXTENSIONS,
+        "types ending with TLV extensions can't be followed by other data, thus they can't be \
+         items of collections, tuples or arrays"
+    );
+    false
+}
+
 impl<T: StrictType> StrictType for &T {
     const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+    const HAS_EXTENSIONS: bool = T::HAS_EXTENSIONS;
+}
+
+/// Compile-time identity of a strict type and of its wire format.
+///
+/// The implementations for the user types are provided by the derivation
+/// macros with `#[strict_type(strict_id)]` attribute, which compose the ids of
+/// a type from the ids of its field types. Thus, all field types of such a
+/// type must implement the trait as well.
+pub trait StrictTypeId: StrictType {
+    /// 64-bit id of the strict type, covering its library and strict name,
+    /// the strict names of its fields and variants and the ids of the field
+    /// types.
+    const STRICT_ID: u64;
+    /// 64-bit fingerprint of the wire format of the type, which doesn't depend
+    /// on the library, type, field or variant names.
+    const WIRE_FINGERPRINT: u64;
+}
+
+impl<T: StrictTypeId> StrictTypeId for &T {
+    const STRICT_ID: u64 = T::STRICT_ID;
+    const WIRE_FINGERPRINT: u64 = T::WIRE_FINGERPRINT;
 }
 
 impl<T> StrictType for PhantomData<T> {
@@ -103,6 +170,14 @@ pub trait StrictTuple: StrictProduct {
 
 pub trait StrictStruct: StrictProduct {
     const ALL_FIELDS: &'static [&'static str];
+    /// Number of the fields, which are listed in [`Self::ALL_FIELDS`].
+    ///
+    /// Evaluating the constant fails the compilation for a structure with more
+    /// than 255 fields.
+    const FIELD_COUNT: u8 = {
+        assert!(Self::ALL_FIELDS.len() <= u8::MAX as usize, "structure has more than 255 fields");
+        Self::ALL_FIELDS.len() as u8
+    };
 
     fn strict_check_fields() {
         let name = Self::strict_name().unwrap_or_else(|| tn!("__unnamed"));
@@ -133,6 +208,11 @@ pub trait StrictStruct: StrictProduct {
 
 pub trait StrictSum: StrictType {
     const ALL_VARIANTS: &'static [(u8, &'static str)];
+    /// Number of the variants, which are listed in [`Self::ALL_VARIANTS`].
+    ///
+    /// Since the tags are `u8`, a type may have up to 256 variants, which
+    /// doesn't fit into `u8`.
+    const VARIANT_COUNT: u16 = Self::ALL_VARIANTS.len() as u16;
 
     fn strict_check_variants() {
         let name = Self::strict_name().unwrap_or_else(|| tn!("__unnamed"));
@@ -217,6 +297,7 @@ where
     }
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum TypeClass {
     Embedded,
     Enum(&'static [(u8, &'static str)]),
@@ -232,12 +313,153 @@ pub struct TypeInfo<T: StrictType> {
     pub dumb: T,
 }
 
+/// Object-safe runtime introspection of strict type layouts, allowing to
+/// explore heterogeneous types stored as `Box<dyn StrictLayout>`.
+///
+/// The trait is implemented by [`TypeInfo`], which is constructed with
+/// `strict_type_info` methods of [`StrictTuple`], [`StrictStruct`],
+/// [`StrictUnion`] and [`StrictEnum`] traits.
+pub trait StrictLayout {
+    fn strict_lib_name(&self) -> &LibName;
+    fn strict_type_name(&self) -> Option<&TypeName>;
+    fn strict_type_class(&self) -> TypeClass;
+
+    fn is_tuple(&self) -> bool { matches!(self.strict_type_class(), TypeClass::Tuple(_)) }
+    fn is_struct(&self) -> bool { matches!(self.strict_type_class(), TypeClass::Struct(_)) }
+    fn is_enum(&self) -> bool { matches!(self.strict_type_class(), TypeClass::Enum(_)) }
+    fn is_union(&self) -> bool { matches!(self.strict_type_class(), TypeClass::Union(_)) }
+
+    /// Returns number of fields in a tuple or a structure, or zero for other
+    /// types.
+    fn field_count(&self) -> u8 {
+        match self.strict_type_class() {
+            TypeClass::Tuple(count) => count,
+            TypeClass::Struct(fields) => fields.len() as u8,
+            _ => 0,
+        }
+    }
+
+    /// Returns names of structure fields, or an empty slice for other types.
+    fn field_names(&self) -> &'static [&'static str] {
+        match self.strict_type_class() {
+            TypeClass::Struct(fields) => fields,
+            _ => &[],
+        }
+    }
+
+    /// Returns tags and names of enum or union variants, or an empty slice for
+    /// other types.
+    fn variants(&self) -> &'static [(u8, &'static str)] {
+        match self.strict_type_class() {
+            TypeClass::Enum(variants) | TypeClass::Union(variants) => variants,
+            _ => &[],
+        }
+    }
+}
+
+impl<T: StrictType> StrictLayout for TypeInfo<T> {
+    fn strict_lib_name(&self) -> &LibName { &self.lib }
+    fn strict_type_name(&self) -> Option<&TypeName> { self.name.as_ref() }
+    fn strict_type_class(&self) -> TypeClass { self.cls }
+}
+
 #[cfg(test)]
 mod test {
     use amplify::confinement::TinyVec;
 
     use super::*;
+    use crate::StrictType;
+
+    #[test]
+    fn variant_error() {
+        #[allow(dead_code)]
+        struct Flag;
+
+        let err = VariantError::with::<Flag>(200u8);
+        assert_eq!(err.value(), 200);
+        assert_eq!(err.type_name(), Some(&tn!("Flag")));
+        assert_eq!(err.to_string(), "value 200 is not a valid variant of Flag");
+
+        let err = VariantError::untyped('x');
+        assert_eq!(err.value(), 'x');
+        assert_eq!(err.type_name(), None);
+        assert_eq!(err.to_string(), "value x is not a valid variant");
+
+        assert_eq!(VariantError::with::<u8>(1u8).type_name(), None);
+    }
+
+    #[test]
+    fn variant_count_full_range() {
+        #[derive(Default)]
+        struct Byte;
+        impl StrictType for Byte {
+            const STRICT_LIB_NAME: &'static str = "Test";
+        }
+        impl StrictSum for Byte {
+            const ALL_VARIANTS: &'static [(u8, &'static str)] = &{
+                let mut variants = [(0u8, "byte"); 256];
+                let mut tag = 0;
+                while tag < variants.len() {
+                    variants[tag].0 = tag as u8;
+                    tag += 1;
+                }
+                variants
+            };
+            fn variant_name(&self) -> &'static str { "byte" }
+        }
+
+        assert_eq!(Byte::VARIANT_COUNT, 256);
+    }
 
     #[test]
     fn name_derivation() { assert_eq!(Option::<TinyVec<u8>>::strict_name(), None) }
+
+    #[test]
+    fn name_with_lifetime() {
+        #[allow(dead_code)]
+        struct Borrowed<'a, T>(&'a T);
+
+        assert_eq!(type_name::<Borrowed<'static, u8>>(), "Borrowedu8");
+    }
+
+    #[test]
+    fn layout() {
+        #[allow(dead_code)]
+        #[derive(Clone, Default, Debug)]
+        #[derive(StrictType)]
+        #[strict_type(lib = "Test", crate = crate)]
+        struct Record {
+            id: u8,
+            name: u16,
+        }
+
+        let layouts: Vec<Box<dyn StrictLayout>> = vec![
+            Box::new(Record::strict_type_info()),
+            Box::new(<(u8, u16)>::strict_type_info()),
+            Box::new(Option::<u8>::strict_type_info()),
+            Box::new(crate::Bool::strict_type_info()),
+        ];
+
+        assert!(layouts[0].is_struct());
+        assert_eq!(layouts[0].strict_lib_name().as_str(), "Test");
+        assert_eq!(layouts[0].strict_type_name(), Some(&tn!("Record")));
+        assert_eq!(layouts[0].field_names(), &["id", "name"]);
+        assert_eq!(layouts[0].field_count(), 2);
+
+        assert!(layouts[1].is_tuple());
+        assert_eq!(layouts[1].field_count(), 2);
+        assert_eq!(layouts[1].field_names(), &[] as &[&str]);
+        assert_eq!(layouts[1].variants(), &[]);
+        assert_eq!(layouts[1].strict_type_name(), None);
+
+        assert!(layouts[2].is_union());
+        assert_eq!(layouts[2].variants(), &[(0, "none"), (1, "some")]);
+        assert_eq!(layouts[2].field_count(), 0);
+
+        assert!(layouts[3].is_enum());
+        assert!(!layouts[3].is_union());
+        assert_eq!(layouts[3].strict_lib_name().as_str(), crate::LIB_NAME_STD);
+        assert_eq!(layouts[3].strict_type_name(), Some(&tn!("Bool")));
+        assert_eq!(layouts[3].variants(), &[(0, "false"), (1, "true")]);
+    }
 }
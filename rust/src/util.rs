@@ -23,10 +23,10 @@ use std::cmp::Ordering;
 use std::fmt::{self, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::io;
+use std::str::FromStr;
 
-use crate::{ReadStruct, VariantName, WriteStruct, STRICT_TYPES_LIB};
+use crate::{InvalidIdent, ReadStruct, VariantName, WriteStruct, STRICT_TYPES_LIB};
 
-// TODO: Control that min > max!
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
 pub struct Sizing {
@@ -59,7 +59,26 @@ impl Sizing {
         max: u16::MAX as u64,
     };
 
-    pub const fn new(min: u64, max: u64) -> Self { Sizing { min, max } }
+    /// Constructs sizing for the provided range.
+    ///
+    /// # Panics
+    ///
+    /// If `min` is greater than `max`.
+    pub const fn new(min: u64, max: u64) -> Self {
+        match Self::try_new(min, max) {
+            Some(sizing) => sizing,
+            None => panic!("sizing minimum must not exceed its maximum"),
+        }
+    }
+
+    /// Constructs sizing for the provided range, returning `None` if `min` is
+    /// greater than `max`.
+    pub const fn try_new(min: u64, max: u64) -> Option<Self> {
+        if min > max {
+            return None;
+        }
+        Some(Sizing { min, max })
+    }
 
     pub const fn fixed(len: u64) -> Self { Sizing { min: len, max: len } }
 
@@ -69,6 +88,19 @@ impl Sizing {
         let len = len as u64;
         len >= self.min && len <= self.max
     }
+
+    /// Returns sizing which is satisfied by the lengths satisfying both
+    /// `self` and `other`, or `None` if the ranges don't overlap.
+    pub const fn intersect(self, other: Sizing) -> Option<Sizing> {
+        let min = if self.min > other.min { self.min } else { other.min };
+        let max = if self.max < other.max { self.max } else { other.max };
+        Self::try_new(min, max)
+    }
+
+    /// Detects whether all the lengths satisfying `other` satisfy `self`.
+    pub const fn contains(self, other: Sizing) -> bool {
+        self.min <= other.min && self.max >= other.max
+    }
 }
 
 impl Display for Sizing {
@@ -83,6 +115,71 @@ impl Display for Sizing {
     }
 }
 
+/// Errors parsing [`Sizing`] from a string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum SizingParseError {
+    /// sizing '{0}' must be either empty or start with ' ^ '.
+    NoPrefix(String),
+
+    /// invalid sizing bound '{0}'; minimum and fixed lengths must be decimal
+    /// numbers, and maximum must be a hexadecimal number with '0x' prefix.
+    InvalidBound(String),
+
+    /// sizing minimum {min} exceeds its maximum {max}.
+    Inverted { min: u64, max: u64 },
+}
+
+/// Parses the string produced by the [`Display`] implementation: an empty
+/// string for `0..=0xFFFF` range, or ` ^ ` followed by either a fixed length
+/// or a `min..max` range, where the minimum is decimal and may be omitted
+/// when it is zero, and the maximum is hexadecimal and may be omitted when it
+/// is `0xFFFF`.
+impl FromStr for Sizing {
+    type Err = SizingParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Sizing::U16);
+        }
+        let sizing =
+            s.strip_prefix(" ^ ").ok_or_else(|| SizingParseError::NoPrefix(s.to_owned()))?;
+        let invalid = |bound: &str| SizingParseError::InvalidBound(bound.to_owned());
+        let (min, max) = match sizing.split_once("..") {
+            None => {
+                let len = sizing.parse().map_err(|_| invalid(sizing))?;
+                (len, len)
+            }
+            Some((min, max)) => {
+                let min = match min {
+                    "" => 0,
+                    min => min.parse().map_err(|_| invalid(min))?,
+                };
+                let max = match max {
+                    "" => u16::MAX as u64,
+                    max => max
+                        .strip_prefix("0x")
+                        .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+                        .ok_or_else(|| invalid(max))?,
+                };
+                (min, max)
+            }
+        };
+        Sizing::try_new(min, max).ok_or(SizingParseError::Inverted { min, max })
+    }
+}
+
+/// Union or enum variant, identified by its tag and name.
+///
+/// Two variants are equal if either their tags or their names match, which
+/// allows to look up variants by any of them. Thus, the equality and ordering
+/// are not transitive: `(1, "a")` equals `(1, "b")`, which equals `(2, "b")`,
+/// while `(1, "a")` is less than `(2, "b")`. When used as keys of sets and
+/// maps (including confined collections decoded from strict encoding),
+/// variants must have both unique tags and unique names; otherwise lookups
+/// give inconsistent results, and duplicated names may remain undetected if
+/// the keys with them are not adjacent. Use [`VariantKey`] for keys which
+/// require total order.
 #[derive(Clone, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
 pub struct Variant {
@@ -94,6 +191,12 @@ impl_strict_struct!(Variant, STRICT_TYPES_LIB; name, tag);
 impl Variant {
     pub fn named(tag: u8, name: VariantName) -> Variant { Variant { name, tag } }
 
+    /// Constructs variant from a raw name, checking that it is a valid
+    /// variant name.
+    pub fn try_named(tag: u8, name: &str) -> Result<Variant, InvalidIdent> {
+        VariantName::try_new(name).map(|name| Variant { name, tag })
+    }
+
     pub fn none() -> Variant {
         Variant {
             name: vname!("none"),
@@ -138,3 +241,238 @@ impl Display for Variant {
         Ok(())
     }
 }
+
+/// Variant identifier with total ordering by the tag and then by the name,
+/// suitable for keys of sets and maps. Unlike [`Variant`], two keys are equal
+/// only if both their tags and names match. Encoded in the same way as
+/// [`Variant`].
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
+pub struct VariantKey {
+    // Field order defines the derived ordering
+    pub tag: u8,
+    pub name: VariantName,
+}
+impl_strict_struct!(VariantKey, STRICT_TYPES_LIB; name, tag);
+
+impl From<Variant> for VariantKey {
+    fn from(variant: Variant) -> Self {
+        VariantKey {
+            tag: variant.tag,
+            name: variant.name,
+        }
+    }
+}
+
+impl From<VariantKey> for Variant {
+    fn from(key: VariantKey) -> Self { Variant::named(key.tag, key.name) }
+}
+
+impl Display for VariantKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { write!(f, "{}", self.name) }
+}
+
+/// Incremental 64-bit FNV-1a hasher which can be used in const context. Used
+/// for computing strict type ids (see [`crate::StrictTypeId`]).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ConstHasher(u64);
+
+impl Default for ConstHasher {
+    fn default() -> Self { Self::new() }
+}
+
+impl ConstHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    pub const fn new() -> Self { ConstHasher(Self::OFFSET_BASIS) }
+
+    pub const fn write_u8(self, byte: u8) -> Self {
+        ConstHasher((self.0 ^ byte as u64).wrapping_mul(Self::PRIME))
+    }
+
+    /// Hashes the little-endian representation of the value, which is used for
+    /// composing the ids from the ids of the nested types.
+    pub const fn write_u64(mut self, value: u64) -> Self {
+        let bytes = value.to_le_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            self = self.write_u8(bytes[i]);
+            i += 1;
+        }
+        self
+    }
+
+    /// Hashes the data prefixed with their length, such that a sequence of
+    /// writes is unambiguous.
+    pub const fn write(mut self, bytes: &[u8]) -> Self {
+        let len = (bytes.len() as u64).to_le_bytes();
+        let mut i = 0;
+        while i < len.len() {
+            self = self.write_u8(len[i]);
+            i += 1;
+        }
+        let mut i = 0;
+        while i < bytes.len() {
+            self = self.write_u8(bytes[i]);
+            i += 1;
+        }
+        self
+    }
+
+    pub const fn finish(self) -> u64 { self.0 }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn variant_try_named() {
+        let variant = Variant::try_named(3, "fooBar").unwrap();
+        assert_eq!(variant.tag, 3);
+        assert_eq!(variant.name, vname!("fooBar"));
+        assert_eq!(Variant::try_named(0, "none").unwrap(), Variant::none());
+
+        assert_eq!(Variant::try_named(1, ""), Err(InvalidIdent::Empty));
+        assert_eq!(
+            Variant::try_named(1, "Some"),
+            Err(InvalidIdent::DisallowedFirst(s!("Some"), 'S'))
+        );
+        assert_eq!(
+            Variant::try_named(1, "some-thing"),
+            Err(InvalidIdent::InvalidChar(s!("some-thing"), '-', 4))
+        );
+        assert!(matches!(
+            Variant::try_named(1, &"a".repeat(200)),
+            Err(InvalidIdent::TooLong { len: 200, .. })
+        ));
+    }
+
+    #[test]
+    fn sizing_range() {
+        assert_eq!(Sizing::try_new(0, 0), Some(Sizing::fixed(0)));
+        assert_eq!(Sizing::try_new(1, u8::MAX as u64), Some(Sizing::U8_NONEMPTY));
+        assert_eq!(Sizing::try_new(2, 1), None);
+        assert_eq!(Sizing::new(3, 5), Sizing { min: 3, max: 5 });
+        assert!(Sizing::new(3, 5).check(4));
+    }
+
+    #[test]
+    fn sizing_intersect() {
+        let a = Sizing::new(2, 10);
+        let b = Sizing::new(5, 20);
+        assert_eq!(a.intersect(b), Some(Sizing::new(5, 10)));
+        assert_eq!(b.intersect(a), Some(Sizing::new(5, 10)));
+        assert!(!a.contains(b));
+        assert!(!b.contains(a));
+
+        let nested = Sizing::new(3, 4);
+        assert_eq!(a.intersect(nested), Some(nested));
+        assert!(a.contains(nested));
+        assert!(!nested.contains(a));
+        assert!(a.contains(a));
+        assert_eq!(a.intersect(a), Some(a));
+
+        assert_eq!(a.intersect(Sizing::new(10, 12)), Some(Sizing::fixed(10)));
+        assert_eq!(a.intersect(Sizing::new(11, 12)), None);
+        assert_eq!(Sizing::U8.intersect(Sizing::fixed(0x100)), None);
+        assert!(Sizing::U16.contains(Sizing::U8_NONEMPTY));
+    }
+
+    #[test]
+    fn sizing_from_str() {
+        for sizing in [
+            Sizing::U16,
+            Sizing::U8,
+            Sizing::U16_NONEMPTY,
+            Sizing::fixed(0),
+            Sizing::fixed(0xFFFF),
+            Sizing::new(0, u64::MAX),
+            Sizing::new(u64::MAX, u64::MAX),
+            Sizing::new(3, 0x10),
+        ] {
+            assert_eq!(sizing.to_string().parse::<Sizing>(), Ok(sizing), "{sizing:?}");
+        }
+        assert_eq!(" ^ 2..".parse::<Sizing>(), Ok(Sizing::new(2, 0xFFFF)));
+        assert_eq!(" ^ ..0xff".parse::<Sizing>(), Ok(Sizing::U8));
+
+        assert_eq!("1..2".parse::<Sizing>(), Err(SizingParseError::NoPrefix(s!("1..2"))));
+        assert_eq!(" ^ 0x1".parse::<Sizing>(), Err(SizingParseError::InvalidBound(s!("0x1"))));
+        assert_eq!(" ^ 1..2".parse::<Sizing>(), Err(SizingParseError::InvalidBound(s!("2"))));
+        assert_eq!(" ^ -1..".parse::<Sizing>(), Err(SizingParseError::InvalidBound(s!("-1"))));
+        assert_eq!(
+            " ^ 5..0x3".parse::<Sizing>(),
+            Err(SizingParseError::Inverted { min: 5, max: 3 })
+        );
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn sizing_display_roundtrip(a: u64, b: u64) {
+            let sizing = Sizing::new(a.min(b), a.max(b));
+            proptest::prop_assert_eq!(sizing.to_string().parse::<Sizing>(), Ok(sizing));
+        }
+
+        #[test]
+        fn sizing_display_roundtrip_small(a in 0u64..0x20000, b in 0u64..0x20000) {
+            let sizing = Sizing::new(a.min(b), a.max(b));
+            proptest::prop_assert_eq!(sizing.to_string().parse::<Sizing>(), Ok(sizing));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "sizing minimum must not exceed its maximum")]
+    fn sizing_inverted() { Sizing::new(5, 3); }
+
+    #[test]
+    fn const_hasher() {
+        assert_eq!(ConstHasher::new().write_u8(b'a').finish(), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(
+            ConstHasher::new().write(b"ab").write(b"c").finish(),
+            ConstHasher::new().write(b"a").write(b"bc").finish()
+        );
+    }
+
+    #[test]
+    fn variant_key_order() {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        use amplify::confinement::TinyOrdMap;
+
+        use crate::test::{decode, encode};
+        use crate::{StrictDecode, StrictReader};
+
+        let a1 = Variant::named(1, vname!("a"));
+        let b1 = Variant::named(1, vname!("b"));
+        let b2 = Variant::named(2, vname!("b"));
+        // Lenient equality is not transitive
+        assert_eq!(a1, b1);
+        assert_eq!(b1, b2);
+        assert!(a1 < b2);
+        assert_eq!(BTreeSet::from([a1.clone(), b1.clone()]).len(), 1);
+
+        let keys = [a1.clone(), b1.clone(), b2.clone()].map(VariantKey::from);
+        assert!(keys[0] < keys[1] && keys[1] < keys[2]);
+        assert_eq!(BTreeSet::from(keys.clone()).len(), 3);
+        assert_eq!(Variant::from(keys[1].clone()).name, vname!("b"));
+
+        // Keys with the same tag are distinct and keep their order
+        let map = TinyOrdMap::from_checked(BTreeMap::from([
+            (keys[1].clone(), 2u8),
+            (keys[0].clone(), 1u8),
+        ]));
+        let data = encode(&map);
+        assert_eq!(data, [2, 1, b'a', 1, 1, 1, b'b', 1, 2]);
+        assert_eq!(decode::<TinyOrdMap<VariantKey, u8>>(&data), map);
+        // Same data can't be decoded into a map keyed by variants
+        assert!(TinyOrdMap::<Variant, u8>::strict_decode(&mut StrictReader::in_memory::<16>(
+            &data
+        ))
+        .is_err());
+
+        // Keys are encoded in the same way as variants
+        assert_eq!(encode(&keys[2]), encode(&b2));
+    }
+}
@@ -77,6 +77,65 @@ impl<W: io::Write> io::Write for ConfinedWriter<W> {
     fn flush(&mut self) -> io::Result<()> { self.writer.flush() }
 }
 
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Writer computing CRC-32 (ISO-HDLC, the one used by zip and ethernet)
+/// checksum of the data passing through it into the inner writer.
+///
+/// Wrapping [`WriteCounter`] or [`Sink`] computes the checksum without
+/// retaining the data.
+#[derive(Clone, Debug)]
+pub struct Crc32Writer<W: io::Write> {
+    crc: u32,
+    writer: W,
+}
+
+impl<W: io::Write> From<W> for Crc32Writer<W> {
+    fn from(writer: W) -> Self { Self::new(writer) }
+}
+
+impl<W: io::Write> Crc32Writer<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            crc: u32::MAX,
+            writer,
+        }
+    }
+
+    /// Returns the checksum of all the data written so far.
+    pub fn checksum(&self) -> u32 { !self.crc }
+
+    pub fn as_inner(&self) -> &W { &self.writer }
+
+    pub fn into_inner(self) -> W { self.writer }
+}
+
+impl<W: io::Write> io::Write for Crc32Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let count = self.writer.write(buf)?;
+        for byte in &buf[..count] {
+            self.crc = CRC32_TABLE[((self.crc ^ *byte as u32) & 0xFF) as usize] ^ (self.crc >> 8);
+        }
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.writer.flush() }
+}
+
 #[derive(Clone, Debug)]
 pub struct StreamWriter<W: io::Write>(ConfinedWriter<W>);
 
@@ -91,16 +150,103 @@ impl<W: io::Write> WriteRaw for StreamWriter<W> {
         self.0.write_all(bytes.as_ref())?;
         Ok(())
     }
+    fn raw_buffer(&self) -> RawBuffer { RawBuffer::new(self.0.limit.saturating_sub(self.0.count)) }
+}
+
+/// In-memory raw writer for the data which are written ahead of their position
+/// (see [`WriteStruct::write_field_ord`]) and copied into the parent writer
+/// later. Constructed with [`WriteRaw::raw_buffer`], it follows the byte order
+/// and the remaining size limit of the parent writer.
+#[derive(Clone, Debug)]
+pub struct RawBuffer {
+    writer: ConfinedWriter<Vec<u8>>,
+    reverse_nums: bool,
+}
+
+impl RawBuffer {
+    /// Constructs little-endian buffer accepting no more than `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            writer: ConfinedWriter::with(limit, vec![]),
+            reverse_nums: false,
+        }
+    }
+
+    /// Switches the byte order of the numbers written into the buffer, which
+    /// is used by the adaptors like [`crate::BigEndian`].
+    pub fn reverse_nums(mut self) -> Self {
+        self.reverse_nums = !self.reverse_nums;
+        self
+    }
+
+    pub fn into_data(self) -> Vec<u8> { self.writer.unconfine() }
+}
+
+impl WriteRaw for RawBuffer {
+    fn write_raw<const MAX_LEN: usize>(&mut self, bytes: impl AsRef<[u8]>) -> io::Result<()> {
+        use io::Write;
+        self.writer.write_all(bytes.as_ref())
+    }
+    fn write_raw_num<const LEN: usize>(&mut self, mut le_bytes: [u8; LEN]) -> io::Result<()> {
+        if self.reverse_nums {
+            le_bytes.reverse();
+        }
+        self.write_raw_array(le_bytes)
+    }
+    fn raw_buffer(&self) -> RawBuffer {
+        let mut buffer = RawBuffer::new(self.writer.limit.saturating_sub(self.writer.count));
+        buffer.reverse_nums = self.reverse_nums;
+        buffer
+    }
 }
 
 impl StreamWriter<Vec<u8>> {
     pub fn in_memory<const MAX: usize>() -> Self { Self::new::<MAX>(vec![]) }
+
+    /// Constructs in-memory writer with the buffer preallocated to `cap` bytes
+    /// (but no more than `MAX`).
+    pub fn in_memory_with_capacity<const MAX: usize>(cap: usize) -> Self {
+        Self::new::<MAX>(Vec::with_capacity(cap.min(MAX)))
+    }
+
+    /// Takes the data written so far, leaving the writer with an empty buffer
+    /// and resetting the count of the written bytes.
+    pub fn take_buffer(&mut self) -> Vec<u8> {
+        self.0.count = 0;
+        std::mem::take(&mut self.0.writer)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl StreamWriter<bytes::buf::Writer<bytes::BytesMut>> {
+    /// Constructs writer into [`bytes::BytesMut`] buffer with `cap` bytes
+    /// preallocated (but no more than `MAX`).
+    pub fn bytes_mut<const MAX: usize>(cap: usize) -> Self {
+        use bytes::BufMut;
+        Self::new::<MAX>(bytes::BytesMut::with_capacity(cap.min(MAX)).writer())
+    }
+
+    /// Splits off the data written so far without copying them, leaving the
+    /// writer with the rest of the buffer capacity and resetting the count of
+    /// the written bytes.
+    pub fn take_bytes(&mut self) -> bytes::BytesMut {
+        self.0.count = 0;
+        self.0.writer.get_mut().split()
+    }
+
+    pub fn into_bytes_mut(self) -> bytes::BytesMut { self.unconfine().into_inner() }
 }
 
 impl StreamWriter<WriteCounter> {
     pub fn counter<const MAX: usize>() -> Self { Self::new::<MAX>(WriteCounter::default()) }
 }
 
+impl StreamWriter<Crc32Writer<WriteCounter>> {
+    pub fn checksum_counter<const MAX: usize>() -> Self {
+        Self::new::<MAX>(Crc32Writer::new(WriteCounter::default()))
+    }
+}
+
 impl StreamWriter<Sink> {
     pub fn sink<const MAX: usize>() -> Self { Self::new::<MAX>(Sink::default()) }
 }
@@ -108,14 +254,110 @@ impl StreamWriter<Sink> {
 #[derive(Debug, From)]
 pub struct StrictWriter<W: WriteRaw>(W);
 
+impl<W: io::Write> StrictWriter<StreamWriter<W>> {
+    /// Constructs writer over an arbitrary [`io::Write`] implementation, which
+    /// fails once more than `MAX` bytes are written.
+    ///
+    /// This is a shorthand for `StrictWriter::with(StreamWriter::new::<MAX>(writer))`.
+    ///
+    /// ```
+    /// # use strict_encoding::{StrictEncode, StrictWriter};
+    /// let mut data = vec![];
+    /// 0xABCDu16.strict_encode(StrictWriter::from_io::<2>(&mut data)).unwrap();
+    /// assert_eq!(data, vec![0xCD, 0xAB]);
+    ///
+    /// assert!(0xABCDu16.strict_encode(StrictWriter::from_io::<1>(&mut data)).is_err());
+    /// ```
+    pub fn from_io<const MAX: usize>(writer: W) -> Self { Self(StreamWriter::new::<MAX>(writer)) }
+}
+
+impl<'a> StrictWriter<StreamWriter<&'a mut dyn io::Write>> {
+    /// Constructs writer over an [`io::Write`] trait object, which fails once
+    /// more than `MAX` bytes are written.
+    ///
+    /// All the writers constructed in this way share the same type, so the
+    /// encoding code is instantiated once for all of them instead of once per
+    /// each concrete writer type. The price is a dynamic dispatch on every
+    /// write to the underlying writer, which happens for each primitive value;
+    /// thus the generic [`StrictWriter::from_io`] remains preferable for hot
+    /// paths.
+    ///
+    /// ```
+    /// # use std::io;
+    /// # use strict_encoding::{StrictEncode, StrictWriter};
+    /// fn write_u16(value: u16, writer: &mut dyn io::Write) {
+    ///     value.strict_encode(StrictWriter::from_dyn::<2>(writer)).unwrap();
+    /// }
+    ///
+    /// let mut data = vec![];
+    /// write_u16(0xABCD, &mut data);
+    /// assert_eq!(data, vec![0xCD, 0xAB]);
+    /// ```
+    pub fn from_dyn<const MAX: usize>(writer: &'a mut dyn io::Write) -> Self {
+        Self::from_io::<MAX>(writer)
+    }
+}
+
 impl StrictWriter<StreamWriter<Vec<u8>>> {
     pub fn in_memory<const MAX: usize>() -> Self { Self(StreamWriter::in_memory::<MAX>()) }
+
+    /// Constructs in-memory writer with the buffer preallocated to `cap` bytes
+    /// (but no more than `MAX`).
+    ///
+    /// When the size of the serialized data is known in advance (for instance,
+    /// from [`StrictSerialize::strict_serialized_len`]), this avoids buffer
+    /// reallocations: encoding a structure with a 64 kiB collection of `u16`
+    /// into the writer made with [`StrictWriter::in_memory`] grows the buffer
+    /// through 15 allocations, ending up with a 128 kiB buffer, while the
+    /// preallocated buffer is allocated exactly once.
+    ///
+    /// [`StrictSerialize::strict_serialized_len`]: crate::StrictSerialize::strict_serialized_len
+    pub fn in_memory_with_capacity<const MAX: usize>(cap: usize) -> Self {
+        Self(StreamWriter::in_memory_with_capacity::<MAX>(cap))
+    }
+
+    /// Takes the data serialized so far, leaving the writer ready for the
+    /// serialization of the next object. The `MAX` limit applies to each of
+    /// the taken buffers separately.
+    ///
+    /// ```
+    /// # use strict_encoding::{StrictEncode, StrictWriter};
+    /// let mut writer = 0xABCDu16.strict_encode(StrictWriter::in_memory::<2>()).unwrap();
+    /// assert_eq!(writer.take_buffer(), vec![0xCD, 0xAB]);
+    ///
+    /// let mut writer = 0x1234u16.strict_encode(writer).unwrap();
+    /// assert_eq!(writer.take_buffer(), vec![0x34, 0x12]);
+    /// ```
+    pub fn take_buffer(&mut self) -> Vec<u8> { self.0.take_buffer() }
+}
+
+#[cfg(feature = "bytes")]
+impl StrictWriter<StreamWriter<bytes::buf::Writer<bytes::BytesMut>>> {
+    /// Constructs writer into [`bytes::BytesMut`] buffer with `cap` bytes
+    /// preallocated (but no more than `MAX`).
+    pub fn bytes_mut<const MAX: usize>(cap: usize) -> Self {
+        Self(StreamWriter::bytes_mut::<MAX>(cap))
+    }
+
+    /// Takes the data serialized so far like [`StrictWriter::take_buffer`]
+    /// does, but splits them off the buffer without copying, so they may be
+    /// frozen into [`bytes::Bytes`] and sent while the writer reuses the
+    /// remaining buffer capacity.
+    pub fn take_bytes(&mut self) -> bytes::BytesMut { self.0.take_bytes() }
 }
 
 impl StrictWriter<StreamWriter<WriteCounter>> {
     pub fn counter<const MAX: usize>() -> Self { Self(StreamWriter::counter::<MAX>()) }
 }
 
+impl StrictWriter<StreamWriter<Crc32Writer<WriteCounter>>> {
+    /// Constructs writer which counts the length of the serialized data and
+    /// computes its CRC-32 checksum without retaining the data.
+    pub fn checksum_counter<const MAX: usize>() -> Self {
+        Self(StreamWriter::checksum_counter::<MAX>())
+    }
+}
+
 impl StrictWriter<StreamWriter<Sink>> {
     pub fn sink<const MAX: usize>() -> Self { Self(StreamWriter::sink::<MAX>()) }
 }
@@ -167,14 +409,42 @@ impl<W: WriteRaw> TypedWrite for StrictWriter<W> {
         let writer = StructWriter::structure::<T>(self);
         inner(writer)
     }
+
+    unsafe fn write_bytes<const MAX_LEN: usize>(mut self, bytes: &[u8]) -> io::Result<Self> {
+        self.0.write_raw_len::<MAX_LEN>(bytes.len())?;
+        self.0.write_raw::<MAX_LEN>(bytes)?;
+        Ok(self)
+    }
+
+    unsafe fn write_byte_array<const LEN: usize>(mut self, array: &[u8; LEN]) -> io::Result<Self> {
+        self.0.write_raw::<LEN>(array)?;
+        Ok(self)
+    }
 }
 
+/// Writer of tuple and structure fields.
+///
+/// Fields of the declared structures must be written in the order of their
+/// declaration (unless they are written with their ordinals), otherwise the
+/// writing fails with [`io::ErrorKind::InvalidInput`].
+///
+/// # Panics
+///
+/// Completing a structure panics if fewer fields are written than declared.
 #[derive(Debug)]
 pub struct StructWriter<W: WriteRaw, P: StrictParent<W>> {
     lib: LibName,
     name: Option<TypeName>,
     named_fields: Vec<FieldName>,
     tuple_fields: Option<u8>,
+    /// Whether the number of tuple fields is known from the type declaration
+    /// and has to be checked when the fields are written.
+    fields_declared: bool,
+    /// Ordinals of the fields which were defined.
+    field_ords: BTreeSet<u8>,
+    /// Encoded fields written ahead of their ordinal position, which are
+    /// waiting for the preceding fields to be written.
+    pending: BTreeMap<u8, Vec<u8>>,
     parent: P,
     cursor: usize,
     _phantom: PhantomData<W>,
@@ -187,6 +457,9 @@ impl<W: WriteRaw, P: StrictParent<W>> StructWriter<W, P> {
             name: T::strict_name(),
             named_fields: T::ALL_FIELDS.iter().map(|name| fname!(*name)).collect(),
             tuple_fields: None,
+            fields_declared: true,
+            field_ords: empty!(),
+            pending: empty!(),
             parent,
             cursor: 0,
             _phantom: default!(),
@@ -199,6 +472,9 @@ impl<W: WriteRaw, P: StrictParent<W>> StructWriter<W, P> {
             name: T::strict_name(),
             named_fields: empty!(),
             tuple_fields: Some(T::FIELD_COUNT),
+            fields_declared: true,
+            field_ords: empty!(),
+            pending: empty!(),
             parent,
             cursor: 0,
             _phantom: default!(),
@@ -211,6 +487,9 @@ impl<W: WriteRaw, P: StrictParent<W>> StructWriter<W, P> {
             name: None,
             named_fields: empty!(),
             tuple_fields: if tuple { Some(0) } else { None },
+            fields_declared: false,
+            field_ords: empty!(),
+            pending: empty!(),
             parent,
             cursor: 0,
             _phantom: default!(),
@@ -238,60 +517,164 @@ impl<W: WriteRaw, P: StrictParent<W>> StructWriter<W, P> {
         self.parent = P::from_write_split(writer, remnant);
         Ok(self)
     }
-}
 
-impl<W: WriteRaw, P: StrictParent<W>> DefineStruct for StructWriter<W, P> {
-    type Parent = P;
-    fn define_field<T: StrictEncode>(mut self, field: FieldName) -> Self {
+    fn define_field_at(mut self, field: FieldName, ord: u8) -> Self {
         assert!(
             !self.named_fields.contains(&field),
             "field '{:#}' is already defined as a part of '{}'",
             field,
             self.name()
         );
+        assert!(
+            self.field_ords.insert(ord),
+            "ordinal {ord} of field '{:#}' is already used by another field of '{}'",
+            field,
+            self.name()
+        );
         self.named_fields.push(field);
         self
     }
+
+    /// Checks that the field name matches the name of the declared field at the
+    /// given position, failing with [`io::ErrorKind::InvalidInput`] otherwise.
+    /// Struct variants of unions don't declare their fields, thus their field
+    /// names are not checked.
+    fn check_field_name(&self, field: &FieldName, pos: usize) -> io::Result<()> {
+        if !self.fields_declared {
+            return Ok(());
+        }
+        let msg = match self.named_fields.get(pos) {
+            None => format!(
+                "field '{:#}' is written after all {} fields declared for '{}'",
+                field,
+                self.named_fields.len(),
+                self.name()
+            ),
+            Some(expected) if expected != field => {
+                let msg = if self.named_fields.contains(field) {
+                    "is written out of the declaration order"
+                } else {
+                    "is not declared"
+                };
+                format!(
+                    "field '{:#}' {msg} for '{}', while field '{:#}' is expected at position {pos}",
+                    field,
+                    self.name(),
+                    expected
+                )
+            }
+            Some(_) => return Ok(()),
+        };
+        Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+    }
+
+    /// Writes fields which were written ahead of their position once all the
+    /// preceding fields are written.
+    fn write_pending(mut self) -> io::Result<Self> {
+        while let Some(data) =
+            u8::try_from(self.cursor).ok().and_then(|ord| self.pending.remove(&ord))
+        {
+            let (mut writer, remnant) = self.parent.into_write_split();
+            unsafe { writer.raw_writer().write_raw::<U64MAX>(data)? };
+            self.parent = P::from_write_split(writer, remnant);
+            self.cursor += 1;
+        }
+        Ok(self)
+    }
+}
+
+impl<W: WriteRaw, P: StrictParent<W>> DefineStruct for StructWriter<W, P> {
+    type Parent = P;
+    fn define_field<T: StrictEncode>(self, field: FieldName) -> Self {
+        let ord = self.named_fields.len() as u8;
+        self.define_field_at(field, ord)
+    }
+    fn define_field_ord<T: StrictEncode>(self, field: FieldName, ord: u8) -> Self {
+        self.define_field_at(field, ord)
+    }
     fn complete(self) -> P {
         assert!(
             !self.named_fields.is_empty(),
             "struct '{}' does not have fields defined",
             self.name()
         );
+        assert!(
+            self.field_ords.iter().copied().eq(0..self.named_fields.len() as u8),
+            "field ordinals of '{}' must cover positions from 0 to {} exactly once",
+            self.name(),
+            self.named_fields.len() - 1
+        );
         self.parent
     }
 }
 
 impl<W: WriteRaw, P: StrictParent<W>> WriteStruct for StructWriter<W, P> {
     type Parent = P;
-    fn write_field(mut self, _field: FieldName, value: &impl StrictEncode) -> io::Result<Self> {
+    fn write_field(mut self, field: FieldName, value: &impl StrictEncode) -> io::Result<Self> {
         debug_assert!(self.tuple_fields.is_none(), "using struct method on tuple");
-        /* TODO: Propagate information about the fields at the parent
-        debug_assert!(
-            !self.named_fields.is_empty(),
-            "struct without fields {} asks to write value for field '{field}'",
+        self.check_field_name(&field, self.cursor)?;
+        self.cursor += 1;
+        self.write_value(value)?.write_pending()
+    }
+    fn write_field_if(
+        mut self,
+        field: FieldName,
+        present: bool,
+        value: &impl StrictEncode,
+    ) -> io::Result<Self> {
+        debug_assert!(self.tuple_fields.is_none(), "using struct method on tuple");
+        self.check_field_name(&field, self.cursor)?;
+        self.cursor += 1;
+        if present {
+            self = self.write_value(value)?;
+        }
+        self.write_pending()
+    }
+    fn write_field_ord(
+        mut self,
+        field: FieldName,
+        ord: u8,
+        value: &impl StrictEncode,
+    ) -> io::Result<Self> {
+        debug_assert!(self.tuple_fields.is_none(), "using struct method on tuple");
+        assert!(
+            !self.fields_declared || (ord as usize) < self.named_fields.len(),
+            "ordinal {ord} of field '{:#}' exceeds the number of fields in '{}'",
+            field,
             self.name()
         );
-        assert_eq!(
-            &self.named_fields[self.cursor],
-            &field,
-            "field '{:#}' was not defined for '{}' or is written outside of the order",
-            field,
+        assert!(
+            ord as usize >= self.cursor && !self.pending.contains_key(&ord),
+            "field at ordinal {ord} is already written for '{}'",
             self.name()
         );
-         */
-        self.cursor += 1;
-        self.write_value(value)
+        if ord as usize == self.cursor {
+            return WriteStruct::write_field(self, field, value);
+        }
+        self.check_field_name(&field, ord as usize)?;
+        // The buffer follows the byte order and the limit of the parent writer
+        let (mut writer, remnant) = self.parent.into_write_split();
+        let buffer = unsafe { writer.raw_writer().raw_buffer() };
+        self.parent = P::from_write_split(writer, remnant);
+        let buffer = value.strict_encode(StrictWriter::with(buffer))?;
+        self.pending.insert(ord, buffer.unbox().into_data());
+        Ok(self)
     }
     fn complete(self) -> P {
-        /* TODO: Propagate information about the fields at the parent
-        assert_eq!(
+        assert!(
+            self.pending.is_empty(),
+            "fields preceding ordinals {:?} are not written for '{}'",
+            self.pending.keys().collect::<Vec<_>>(),
+            self.name()
+        );
+        // Fields of struct variants in unions are not declared in advance
+        assert!(
+            !self.fields_declared || self.cursor == self.named_fields.len(),
+            "only {} of {} declared fields were written for '{}'",
             self.cursor,
             self.named_fields.len(),
-            "not all fields were written for '{}'",
             self.name()
         );
-         */
         self.parent
     }
 }
@@ -320,26 +703,29 @@ impl<W: WriteRaw, P: StrictParent<W>> DefineTuple for StructWriter<W, P> {
 impl<W: WriteRaw, P: StrictParent<W>> WriteTuple for StructWriter<W, P> {
     type Parent = P;
     fn write_field(mut self, value: &impl StrictEncode) -> io::Result<Self> {
-        /* TODO: Propagate information about number of fields at the parent
+        let count = self.tuple_fields.expect("writing tuple field to structure");
+        // Fields of union variants are not known to the writer
         assert!(
-            self.tuple_fields.expect("writing tuple field to structure") as usize > self.cursor,
-            "writing more unnamed fields to the tuple {} than was defined",
-            self.name()
+            !self.fields_declared || self.cursor < count as usize,
+            "writing more unnamed fields to the tuple '{}' than {} fields it was declared with",
+            self.name(),
+            count
         );
-         */
         self.cursor += 1;
         self.write_value(value)
     }
     fn complete(self) -> P {
         assert_ne!(self.cursor, 0, "tuple '{}' does not have any fields written", self.name());
-        /* TODO: Propagate information about number of fields at the parent
-        assert_eq!(
-            Some(self.cursor as u8),
-            self.unnamed_fields,
-            "not all fields were written for '{}'",
-            self.name()
-        );
-         */
+        if let Some(count) = self.tuple_fields.filter(|_| self.fields_declared) {
+            assert_eq!(
+                self.cursor,
+                count as usize,
+                "only {} of {} declared fields were written for the tuple '{}'",
+                self.cursor,
+                count,
+                self.name()
+            );
+        }
         debug_assert!(self.named_fields.is_empty(), "tuple '{}' written as struct", self.name());
         self.parent
     }
@@ -413,6 +799,47 @@ impl<W: WriteRaw> UnionWriter<W> {
             .unwrap_or_else(|| panic!("unknown variant `{name}` for the enum `{}`", self.name()))
     }
 
+    pub fn name_by_tag(&self, tag: u8) -> Option<&VariantName> { self.declared_variants.get(&tag) }
+
+    pub fn declared_tags(&self) -> impl Iterator<Item = u8> + '_ {
+        self.declared_variants.keys().copied()
+    }
+
+    fn no_unit_variant(&self, tag: u8) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("no unit variant with tag {tag} in `{}`", self.name()),
+        )
+    }
+
+    /// Defines a unit variant by its tag instead of the name, which is taken
+    /// from the variants declared by the type.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the tag is not declared.
+    pub fn define_variant_by_tag(self, tag: u8) -> io::Result<Self> {
+        let name = self.name_by_tag(tag).cloned().ok_or_else(|| self.no_unit_variant(tag))?;
+        Ok(self._define_variant(name, VariantType::Unit))
+    }
+
+    /// Writes a unit variant by its tag instead of the name, which is taken
+    /// from the variants declared by the type.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the tag doesn't belong to
+    /// a unit variant defined before.
+    pub fn write_variant_by_tag(self, tag: u8) -> io::Result<Self> {
+        let name = self
+            .defined_variant
+            .iter()
+            .find(|(variant, ty)| variant.tag == tag && **ty == VariantType::Unit)
+            .map(|(variant, _)| variant.name.clone())
+            .ok_or_else(|| self.no_unit_variant(tag))?;
+        self._write_variant(name, VariantType::Unit)
+    }
+
     fn _define_variant(mut self, name: VariantName, variant_type: VariantType) -> Self {
         let tag = self.tag_by_name(&name);
         let variant = Variant::named(tag, name);
@@ -601,6 +1028,9 @@ impl<W: WriteRaw, P: StrictParent<W>> SplitParent for StructWriter<W, P> {
             name: remnant.name,
             named_fields: remnant.named_fields,
             tuple_fields: remnant.tuple_fields,
+            fields_declared: remnant.fields_declared,
+            field_ords: remnant.field_ords,
+            pending: remnant.pending,
             parent,
             cursor: remnant.cursor,
             _phantom: none!(),
@@ -612,6 +1042,9 @@ impl<W: WriteRaw, P: StrictParent<W>> SplitParent for StructWriter<W, P> {
             name: self.name,
             named_fields: self.named_fields,
             tuple_fields: self.tuple_fields,
+            fields_declared: self.fields_declared,
+            field_ords: self.field_ords,
+            pending: self.pending,
             parent: none!(),
             cursor: self.cursor,
             _phantom: none!(),
@@ -628,3 +1061,391 @@ impl<W: WriteRaw> StrictParent<W> for ParentDumb {
     fn from_write_split(_: StrictWriter<W>, _: Self::Remnant) -> Self { unreachable!() }
     fn into_write_split(self) -> (StrictWriter<W>, Self::Remnant) { unreachable!() }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::{SmallBlob, SmallVec, U24};
+
+    use super::*;
+    use crate::{BigEndian, StrictProduct, StrictSerialize, StrictType};
+
+    #[test]
+    fn crc32_checksum() {
+        use std::io::Write;
+
+        let mut writer = Crc32Writer::new(io::sink());
+        assert_eq!(writer.checksum(), 0);
+        writer.write_all(b"1234").unwrap();
+        writer.write_all(b"56789").unwrap();
+        assert_eq!(writer.checksum(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn len_and_checksum() {
+        #[derive(Clone, PartialEq, Eq, Debug, Default)]
+        #[derive(StrictType, StrictEncode)]
+        #[strict_type(lib = "Test", crate = crate)]
+        struct Record {
+            id: u16,
+            data: SmallBlob,
+        }
+        impl StrictSerialize for Record {}
+
+        let value = Record {
+            id: 0xDEAD,
+            data: SmallBlob::try_from(vec![0xA5; 300]).unwrap(),
+        };
+        let data = value.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        let mut crc32 = Crc32Writer::new(vec![]);
+        io::Write::write_all(&mut crc32, &data).unwrap();
+
+        let (len, checksum) = value.strict_len_and_checksum::<{ usize::MAX }>().unwrap();
+        assert_eq!(len, 304);
+        assert_eq!(len, value.strict_serialized_len::<{ usize::MAX }>().unwrap());
+        assert_eq!(checksum, crc32.checksum());
+        assert_eq!(crc32.into_inner(), data.release());
+
+        assert!(value.strict_len_and_checksum::<303>().is_err());
+    }
+
+    #[test]
+    fn union_declared_variants() {
+        let writer = UnionWriter::with::<Option<u8>>(StrictWriter::in_memory::<0>());
+        assert_eq!(writer.declared_tags().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(writer.name_by_tag(0), Some(&vname!("none")));
+        assert_eq!(writer.name_by_tag(1), Some(&vname!("some")));
+        assert_eq!(writer.name_by_tag(2), None);
+        assert_eq!(writer.tag_by_name(&vname!("some")), 1);
+    }
+
+    #[test]
+    fn variant_by_tag() {
+        #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+        #[derive(StrictType, StrictEncode)]
+        #[strict_type(lib = "Test", tags = repr, into_u8, try_from_u8, crate = crate)]
+        #[repr(u8)]
+        enum Level {
+            #[default]
+            Low = 1,
+            High = 5,
+        }
+
+        let writer = UnionWriter::with::<Level>(StrictWriter::in_memory::<1>());
+        let err = writer.define_variant_by_tag(4).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "no unit variant with tag 4 in `Level`");
+
+        let writer = UnionWriter::with::<Level>(StrictWriter::in_memory::<1>());
+        let writer = writer.define_variant_by_tag(1).unwrap().define_variant_by_tag(5).unwrap();
+        let writer = DefineEnum::complete(writer);
+        assert_eq!(writer.variants().keys().cloned().collect::<Vec<_>>(), vec![
+            Variant::named(1, vname!("low")),
+            Variant::named(5, vname!("high"))
+        ]);
+        let err = writer.write_variant_by_tag(0).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let writer = UnionWriter::with::<Level>(StrictWriter::in_memory::<1>());
+        let writer = writer.define_variant_by_tag(1).unwrap().define_variant_by_tag(5).unwrap();
+        let writer = DefineEnum::complete(writer).write_variant_by_tag(5).unwrap();
+        let mut writer = WriteEnum::complete(writer);
+        assert_eq!(writer.take_buffer(), crate::test::encode(&Level::High));
+
+        // Variants with fields can't be written by tag
+        let writer = UnionWriter::with::<Option<u8>>(StrictWriter::in_memory::<2>())
+            .define_variant_by_tag(0)
+            .unwrap()
+            .define_newtype::<u8>(vname!("some"));
+        let writer = DefineUnion::complete(writer);
+        let err = writer.write_variant_by_tag(1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "no unit variant with tag 1 in `<unnamed>`");
+    }
+
+    #[test]
+    fn take_buffer() {
+        #[derive(Clone, PartialEq, Eq, Debug, Default)]
+        #[derive(StrictType, StrictEncode)]
+        #[strict_type(lib = "Test", crate = crate)]
+        struct Record {
+            id: u32,
+            data: SmallBlob,
+        }
+
+        let first = Record {
+            id: 1,
+            data: SmallBlob::try_from(vec![0xA5; 10]).unwrap(),
+        };
+        let second = Record {
+            id: 2,
+            data: SmallBlob::try_from(vec![0x5A; 10]).unwrap(),
+        };
+
+        // Each of the objects fits into the limit, while both of them don't
+        let mut writer = first.strict_encode(StrictWriter::in_memory::<16>()).unwrap();
+        let data1 = writer.take_buffer();
+        let mut writer = second.strict_encode(writer).unwrap();
+        let data2 = writer.take_buffer();
+
+        assert_eq!(
+            data1,
+            first.strict_encode(StrictWriter::in_memory::<16>()).unwrap().unbox().unconfine()
+        );
+        assert_eq!(
+            data2,
+            second.strict_encode(StrictWriter::in_memory::<16>()).unwrap().unbox().unconfine()
+        );
+        assert_ne!(data1, data2);
+        assert!(writer.take_buffer().is_empty());
+
+        let writer = first.strict_encode(writer).unwrap();
+        assert!(second.strict_encode(writer).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes_mut() {
+        let mut writer = 0xABCDu16.strict_encode(StrictWriter::bytes_mut::<2>(16)).unwrap();
+        let first = writer.take_bytes();
+        assert_eq!(first, &[0xCD, 0xAB][..]);
+
+        // The limit applies to each of the taken buffers separately
+        let mut writer = 0x1234u16.strict_encode(writer).unwrap();
+        assert_eq!(writer.take_bytes(), &[0x34, 0x12][..]);
+        assert_eq!(first, &[0xCD, 0xAB][..]);
+        assert!(0u32.strict_encode(writer).is_err());
+
+        assert_eq!(StrictWriter::bytes_mut::<8>(1024).unbox().into_bytes_mut().capacity(), 8);
+    }
+
+    #[test]
+    fn in_memory_capacity() {
+        #[derive(Clone, PartialEq, Eq, Debug, Default)]
+        #[derive(StrictType, StrictEncode)]
+        #[strict_type(lib = "Test", crate = crate)]
+        struct Record {
+            id: u32,
+            data: SmallVec<u16>,
+        }
+        impl StrictSerialize for Record {}
+
+        let record = Record {
+            id: 1,
+            data: SmallVec::try_from(vec![0xAC; 0x7FFF]).unwrap(),
+        };
+        let len = record.strict_serialized_len::<U24>().unwrap();
+        assert_eq!(len, 4 + 2 + 2 * 0x7FFF);
+
+        // Growing buffer written item by item is reallocated each time its
+        // capacity doubles, so it ends up with a larger capacity than needed
+        let data = record.strict_encode(StrictWriter::in_memory::<U24>()).unwrap().unbox();
+        let data = data.unconfine();
+        assert_eq!(data.len(), len);
+        assert_eq!(data.capacity(), 0x20000);
+
+        // Preallocated buffer is never reallocated
+        let writer = StrictWriter::in_memory_with_capacity::<U24>(len);
+        let data2 = record.strict_encode(writer).unwrap().unbox().unconfine();
+        assert_eq!(data2, data);
+        assert_eq!(data2.capacity(), len);
+
+        assert_eq!(
+            StrictWriter::in_memory_with_capacity::<8>(1024).unbox().unconfine().capacity(),
+            8
+        );
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    struct Pair(u8, u8);
+
+    impl StrictType for Pair {
+        const STRICT_LIB_NAME: &'static str = "Test";
+    }
+    impl StrictProduct for Pair {}
+    impl StrictTuple for Pair {
+        const FIELD_COUNT: u8 = 2;
+    }
+
+    fn write_pair_fields(fields: &[u8]) -> Vec<u8> {
+        StrictWriter::in_memory::<8>()
+            .write_tuple::<Pair>(|mut w| {
+                for field in fields {
+                    w = WriteTuple::write_field(w, field)?;
+                }
+                Ok(WriteTuple::complete(w))
+            })
+            .unwrap()
+            .unbox()
+            .unconfine()
+    }
+
+    #[test]
+    #[should_panic(expected = "writing more unnamed fields to the tuple 'Pair' than 2 fields it \
+                               was declared with")]
+    fn tuple_fields_overflow() { write_pair_fields(&[1, 2, 3]); }
+
+    #[test]
+    #[should_panic(expected = "only 1 of 2 declared fields were written for the tuple 'Pair'")]
+    fn tuple_fields_underfill() { write_pair_fields(&[1]); }
+
+    #[test]
+    fn tuple_fields_exact() {
+        assert_eq!(write_pair_fields(&[1, 2]), [1, 2]);
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    struct Point {
+        x: u8,
+        y: u16,
+        z: u8,
+    }
+
+    impl StrictType for Point {
+        const STRICT_LIB_NAME: &'static str = "Test";
+    }
+    impl StrictProduct for Point {}
+    impl StrictStruct for Point {
+        const ALL_FIELDS: &'static [&'static str] = &["x", "y", "z"];
+    }
+
+    fn try_write_point_fields(fields: &[(&'static str, u8, u16)]) -> io::Result<Vec<u8>> {
+        let writer = StrictWriter::in_memory::<8>().write_struct::<Point>(|mut w| {
+            for (name, ord, value) in fields {
+                w = w.write_field_ord(fname!(*name), *ord, value)?;
+            }
+            Ok(WriteStruct::complete(w))
+        })?;
+        Ok(writer.unbox().unconfine())
+    }
+
+    fn write_point_fields(fields: &[(&'static str, u8, u16)]) -> Vec<u8> {
+        try_write_point_fields(fields).unwrap()
+    }
+
+    fn write_point_named(fields: &[&'static str]) -> io::Result<Vec<u8>> {
+        let writer = StrictWriter::in_memory::<8>().write_struct::<Point>(|mut w| {
+            for (value, name) in fields.iter().enumerate() {
+                w = WriteStruct::write_field(w, fname!(*name), &(value as u8))?;
+            }
+            Ok(WriteStruct::complete(w))
+        })?;
+        Ok(writer.unbox().unconfine())
+    }
+
+    fn assert_invalid_input(res: io::Result<Vec<u8>>, msg: &str) {
+        let err = res.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), msg);
+    }
+
+    #[test]
+    fn struct_field_names() {
+        assert_eq!(write_point_named(&["x", "y", "z"]).unwrap(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn struct_field_out_of_order() {
+        assert_invalid_input(
+            write_point_named(&["x", "z", "y"]),
+            "field 'z' is written out of the declaration order for 'Point', while field 'y' is \
+             expected at position 1",
+        );
+    }
+
+    #[test]
+    fn struct_field_unknown() {
+        assert_invalid_input(
+            write_point_named(&["w"]),
+            "field 'w' is not declared for 'Point', while field 'x' is expected at position 0",
+        );
+    }
+
+    #[test]
+    fn struct_field_overflow() {
+        assert_invalid_input(
+            write_point_named(&["x", "y", "z", "x"]),
+            "field 'x' is written after all 3 fields declared for 'Point'",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "only 2 of 3 declared fields were written for 'Point'")]
+    fn struct_field_underfill() { write_point_named(&["x", "y"]).unwrap(); }
+
+    #[test]
+    fn struct_field_ord_misnamed() {
+        assert_invalid_input(
+            try_write_point_fields(&[("x", 2, 3)]),
+            "field 'x' is written out of the declaration order for 'Point', while field 'z' is \
+             expected at position 2",
+        );
+    }
+
+    #[test]
+    fn struct_field_ord() {
+        let sequential = write_point_fields(&[("x", 0, 1), ("y", 1, 2), ("z", 2, 3)]);
+        assert_eq!(sequential, [1, 0, 2, 0, 3, 0]);
+        assert_eq!(write_point_fields(&[("z", 2, 3), ("x", 0, 1), ("y", 1, 2)]), sequential);
+        assert_eq!(write_point_fields(&[("y", 1, 2), ("z", 2, 3), ("x", 0, 1)]), sequential);
+    }
+
+    #[test]
+    fn struct_field_ord_config() {
+        let data = StrictWriter::with(BigEndian::new(StreamWriter::in_memory::<6>()))
+            .write_struct::<Point>(|w| {
+                let w = w.write_field_ord(fname!("z"), 2, &3u16)?;
+                let w = w.write_field_ord(fname!("y"), 1, &2u16)?;
+                let w = w.write_field_ord(fname!("x"), 0, &1u16)?;
+                Ok(WriteStruct::complete(w))
+            })
+            .unwrap()
+            .unbox()
+            .into_inner()
+            .unconfine();
+        assert_eq!(data, [0, 1, 0, 2, 0, 3]);
+
+        // Buffered fields are limited by the space left in the parent writer
+        let err = StrictWriter::in_memory::<3>()
+            .write_struct::<Point>(|w| {
+                let w = w.write_field_ord(fname!("x"), 0, &1u16)?;
+                let w = w.write_field_ord(fname!("z"), 2, &3u16)?;
+                Ok(WriteStruct::complete(w))
+            })
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    #[should_panic(expected = "field at ordinal 1 is already written for 'Point'")]
+    fn struct_field_ord_repeated() { write_point_fields(&[("x", 0, 1), ("y", 1, 2), ("z", 1, 3)]); }
+
+    #[test]
+    #[should_panic(expected = "fields preceding ordinals [2] are not written for 'Point'")]
+    fn struct_field_ord_missed() { write_point_fields(&[("x", 0, 1), ("z", 2, 3)]); }
+
+    #[test]
+    #[should_panic(expected = "ordinal 3 of field 'z' exceeds the number of fields in 'Point'")]
+    fn struct_field_ord_overflow() { write_point_fields(&[("z", 3, 3)]); }
+
+    #[test]
+    fn define_field_ord() {
+        let writer = UnionWriter::with::<Option<u8>>(StrictWriter::in_memory::<0>());
+        let writer = StructWriter::unnamed(writer, false)
+            .define_field_ord::<u8>(fname!("b"), 1)
+            .define_field_ord::<u8>(fname!("a"), 0);
+        assert_eq!(writer.named_fields(), &[fname!("b"), fname!("a")]);
+        let _ = DefineStruct::complete(writer);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "field ordinals of '<unnamed>' must cover positions from 0 to 1 exactly once"
+    )]
+    fn define_field_ord_gap() {
+        let writer = UnionWriter::with::<Option<u8>>(StrictWriter::in_memory::<0>());
+        let writer = StructWriter::unnamed(writer, false)
+            .define_field_ord::<u8>(fname!("b"), 2)
+            .define_field_ord::<u8>(fname!("a"), 0);
+        let _ = DefineStruct::complete(writer);
+    }
+}
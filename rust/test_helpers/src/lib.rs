@@ -56,6 +56,24 @@
 //! serialization (which should not be obtained by just encoding the object),
 //! use [`test_encoding_roundtrip`] method.
 //!
+//! Big integer types from `amplify` crate, like `u256` or `i512`, can be
+//! tested on their edge values with [`test_bigint_roundtrip`] method.
+//!
+//! # Testing decoders on malformed data
+//!
+//! Decoders must fail on any malformed data instead of panicking, which may
+//! be checked with [`assert_decode_no_panic`] method. It is best used with a
+//! fuzzer feeding it random data, like `cargo fuzz` target, whose body is:
+//!
+//! ```
+//! # use amplify::confinement::TinyBlob;
+//! // Inside `libfuzzer_sys::fuzz_target!(|data: &[u8]| { ... })`
+//! fn fuzz_target(data: &[u8]) {
+//!     strict_encoding_test::assert_decode_no_panic::<(u8, TinyBlob), 0xFFFF>(data);
+//! }
+//! # fuzz_target(&[1, 2, 3]);
+//! ```
+//!
 //! # General guidelines
 //!
 //! Proper testing should not exercise `asset`s and instead propagate errors
@@ -84,9 +102,12 @@
 #[macro_use]
 extern crate amplify;
 
+use std::any::type_name;
 use std::fmt::Debug;
 use std::io;
+use std::panic::{self, AssertUnwindSafe};
 
+use amplify::num::{i1024, i256, i512, u1024, u256, u512};
 use amplify::IoError;
 use strict_encoding::{DecodeError, StrictDecode, StrictEncode, StrictReader, StrictWriter};
 
@@ -656,3 +677,129 @@ where
     }
     Ok(())
 }
+
+/// Big integer types which edge values are tested with
+/// [`test_bigint_roundtrip`].
+pub trait BigInt: StrictEncode + StrictDecode + PartialEq + Clone + Debug {
+    /// Number of bytes in the strict encoding of the type.
+    const BYTES: usize;
+    /// Minimal value of the type.
+    const MIN: Self;
+    /// Maximal value of the type.
+    const MAX: Self;
+    /// Zero value.
+    const ZERO: Self;
+    /// Value of one.
+    const ONE: Self;
+}
+
+macro_rules! impl_bigint {
+    ($($ty:ident),+) => { $(
+        impl BigInt for $ty {
+            const BYTES: usize = $ty::BITS as usize / 8;
+            const MIN: Self = $ty::MIN;
+            const MAX: Self = $ty::MAX;
+            const ZERO: Self = $ty::ZERO;
+            const ONE: Self = $ty::ONE;
+        }
+    )+ }
+}
+
+impl_bigint!(u256, i256, u512, i512, u1024, i1024);
+
+/// Test helper performing encode-decode roundtrip for the edge values of a
+/// big integer type: `MIN`, `MAX`, zero and one.
+///
+/// # Error
+///
+/// Errors on:
+/// - encoding or decoding failures;
+/// - if an original value is not equivalent to its decoded version;
+/// - if a value is not encoded as a little-endian number of `T::BYTES` bytes.
+///
+/// # Panics
+///
+/// Function does not panics and instead returns [`DataEncodingTestFailure`] for
+/// each type of test failures.
+///
+/// # Example
+///
+/// ```
+/// # use amplify::num::u256;
+/// # use strict_encoding_test::test_bigint_roundtrip;
+/// test_bigint_roundtrip::<u256>().unwrap();
+/// ```
+pub fn test_bigint_roundtrip<T: BigInt>() -> Result<(), DataEncodingTestFailure<T>> {
+    let mut one = vec![0u8; T::BYTES];
+    one[0] = 1;
+    let zero = vec![0u8; T::BYTES];
+    for (value, test_vec) in
+        [(T::ZERO, Some(zero)), (T::ONE, Some(one)), (T::MIN, None), (T::MAX, None)]
+    {
+        let encoded = test_object_encoding_roundtrip::<T, 256>(&value)?;
+        let test_vec = test_vec.unwrap_or_else(|| encoded.clone());
+        if encoded.len() != T::BYTES || encoded != test_vec {
+            return Err(DataEncodingTestFailure::TranscodedVecDiffersFromOriginal {
+                original: test_vec,
+                transcoded: encoded,
+                object: value,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Test helper decoding a value of type `T` from arbitrary (usually malformed)
+/// data, which asserts that the decoding either succeeds or fails with an
+/// error, but never panics.
+///
+/// Aborts of the process, like the ones caused by memory allocation failures,
+/// can't be caught; fuzzers detect them as crashes.
+///
+/// # Panics
+///
+/// If the decoding panics, with the message of the original panic.
+///
+/// # Example
+///
+/// ```
+/// # use amplify::confinement::TinyBlob;
+/// # use strict_encoding_test::assert_decode_no_panic;
+/// for data in [&[][..], &[0xFF], &[2, 1], &[1, 3, 0]] {
+///     assert_decode_no_panic::<TinyBlob, 256>(data);
+///     assert_decode_no_panic::<(u8, Option<u16>), 256>(data);
+/// }
+/// ```
+///
+/// ```should_panic
+/// # use strict_encoding::{DecodeError, StrictDecode, StrictType, TypedRead};
+/// # use strict_encoding_test::assert_decode_no_panic;
+/// struct Fragile(u8);
+///
+/// impl StrictType for Fragile {
+///     const STRICT_LIB_NAME: &'static str = "Example";
+/// }
+/// impl StrictDecode for Fragile {
+///     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+///         let tag = u8::strict_decode(reader)?;
+///         assert!(tag < 8, "unchecked tag");
+///         Ok(Fragile(tag))
+///     }
+/// }
+///
+/// assert_decode_no_panic::<Fragile, 8>(&[0xFF]);
+/// ```
+pub fn assert_decode_no_panic<T: StrictDecode, const MAX: usize>(data: &[u8]) {
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut reader = StrictReader::in_memory::<MAX>(data);
+        let _ = T::strict_decode(&mut reader);
+    }));
+    if let Err(payload) = res {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        panic!("decoding of {} from data {data:02x?} panicked: {msg}", type_name::<T>());
+    }
+}